@@ -16,6 +16,14 @@ pub enum FileError {
     NotSupported(FileNotSupportedError),
     /// General io error
     IoError(io::Error),
+    /// The requested frame of an animated image does not exist
+    FrameNotFound(usize),
+    /// The image could not be encoded within the given maximum file size (in bytes)
+    SizeLimitExceeded(usize),
+    /// The requested region as (position_x, position_y, width, height) is empty or not fully inside the image
+    RegionOutOfBounds((u32, u32, u32, u32)),
+    /// Some items of a `Target` with multiple items could not be stored, the other items were stored
+    PartialStore(PartialStoreError),
     /// Error could not be correctly determined
     UnknownError,
 }
@@ -32,6 +40,43 @@ impl std::convert::From<std::io::Error> for FileError {
     }
 }
 
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FileError::GlobError(error) => write!(f, "Parsing the glob failed: {}", error),
+            FileError::NotFound(error) => write!(f, "{}", error),
+            FileError::NotSupported(error) => write!(f, "{}", error),
+            FileError::IoError(error) => write!(f, "Accessing the file failed: {}", error),
+            FileError::FrameNotFound(index) => write!(f, "Frame {} does not exist", index),
+            FileError::SizeLimitExceeded(max_bytes) => {
+                write!(f, "Image could not be encoded within {} bytes", max_bytes)
+            }
+            FileError::RegionOutOfBounds((x, y, width, height)) => write!(
+                f,
+                "Region {}x{} at ({}, {}) is not inside the image",
+                width, height, x, y
+            ),
+            FileError::PartialStore(error) => write!(
+                f,
+                "Storing {} of the target items failed",
+                error.get_errors().len()
+            ),
+            FileError::UnknownError => write!(f, "Unknown error"),
+        }
+    }
+}
+
+impl Error for FileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FileError::GlobError(error) | FileError::IoError(error) => Some(error),
+            FileError::NotFound(error) => Some(error),
+            FileError::NotSupported(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
 /// The `FileNotFoundError` type. Provides information for FileError::NotFound
 #[derive(Debug, Clone)]
 pub struct FileNotFoundError {
@@ -54,6 +99,59 @@ impl Error for FileNotFoundError {
         None
     }
 }
+/// The `PartialStoreError` type. Provides information for FileError::PartialStore
+///
+/// Each error is paired with the destination path of the target item that caused it, as it was given to the `Target`.
+///
+/// # Examples
+/// ```
+/// use std::path::Path;
+/// use thumbnailer::errors::{ApplyError, FileError};
+/// use thumbnailer::target::TargetFormat;
+/// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+///
+/// let dir = std::env::temp_dir().join("thumbnailer_doc_partial_store");
+/// std::fs::create_dir_all(&dir).unwrap();
+/// // A folder can not be created below a file
+/// std::fs::write(dir.join("file"), b"").unwrap();
+/// let invalid = dir.join("file/out/");
+///
+/// let target = Target::new(TargetFormat::Png, dir.join("thumb.png"))
+///     .add_target(TargetFormat::Jpeg, invalid.clone());
+/// let thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+///
+/// let error = match thumb.apply_store(&target) {
+///     Err(ApplyError::StoreError(FileError::PartialStore(error))) => error,
+///     _ => panic!("the invalid path was not reported"),
+/// };
+///
+/// assert_eq!(error.get_paths(), &vec![dir.join("thumb.png")]);
+/// assert!(image::open(dir.join("thumb.png")).is_ok());
+/// assert_eq!(error.get_errors().len(), 1);
+/// assert_eq!(error.get_errors()[0].0, invalid);
+/// ```
+#[derive(Debug)]
+pub struct PartialStoreError {
+    /// Output file paths of the items that were successfully stored, in the order of the items
+    paths: Vec<PathBuf>,
+    /// List of all errors that occurred while storing the items, with the destination path of the item
+    errors: Vec<(PathBuf, FileError)>,
+}
+
+impl PartialStoreError {
+    pub fn new(paths: Vec<PathBuf>, errors: Vec<(PathBuf, FileError)>) -> Self {
+        PartialStoreError { paths, errors }
+    }
+    /// Gets all paths that were successfully stored despite errors occurring
+    pub fn get_paths(&self) -> &Vec<PathBuf> {
+        &self.paths
+    }
+    /// Gets all errors that occurred while storing the items, with the destination path of the item
+    pub fn get_errors(&self) -> &Vec<(PathBuf, FileError)> {
+        &self.errors
+    }
+}
+
 /// The `FileNotSupportedError` type. Provides information for FileError::NotSupported
 #[derive(Debug)]
 pub struct FileNotSupportedError {
@@ -107,6 +205,10 @@ pub enum OperationErrorInfo {
     ImageBufferConversionFailure,
     /// A font could not be loaded
     FontLoadError,
+    /// A model, e.g. for face detection, could not be loaded
+    ModelLoadError,
+    /// The parameters given to the operation are not valid, for example an empty range
+    InvalidParameters,
 }
 
 /// Error that can occur while applying a single operation on a GenericThumbnail item
@@ -122,6 +224,14 @@ impl OperationError {
     pub fn new(op: Box<dyn Operation>, info: OperationErrorInfo) -> Self {
         OperationError { op, info }
     }
+    /// Gets the operation that failed
+    pub fn get_operation(&self) -> &dyn Operation {
+        self.op.as_ref()
+    }
+    /// Gets the additional information on why the operation failed
+    pub fn get_info(&self) -> &OperationErrorInfo {
+        &self.info
+    }
 }
 
 impl fmt::Display for OperationError {
@@ -138,20 +248,54 @@ impl Error for OperationError {
 
 /// Error that can occur while applying or storing a GenericThumbnail that contains multiple images.
 ///
+/// Each error is paired with the path of the source image that caused it.
+///
+/// # Examples
+/// ```
+/// use std::fs;
+/// use thumbnailer::errors::ApplyError;
+/// use thumbnailer::target::TargetFormat;
+/// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+/// use thumbnailer::{GenericThumbnail, Target};
+///
+/// let dir = std::env::temp_dir().join("thumbnailer_doc_collection_error");
+/// fs::create_dir_all(&dir).unwrap();
+/// fs::copy("resources/tests/test.jpg", dir.join("first.jpg")).unwrap();
+/// // The file starts like a JPEG, so it only fails when it is decoded
+/// fs::write(dir.join("broken.jpg"), b"\xFF\xD8\xFF\xE0 not a jpeg at all").unwrap();
+/// fs::copy("resources/tests/test.jpg", dir.join("second.jpg")).unwrap();
+///
+/// let mut builder = ThumbnailCollectionBuilder::new();
+/// for name in &["first.jpg", "broken.jpg", "second.jpg"] {
+///     assert!(builder.add_path(dir.join(name).to_str().unwrap()).is_ok());
+/// }
+/// let mut collection = builder.finalize();
+///
+/// let target = Target::new(TargetFormat::Png, dir.join("out/"));
+/// let error = match collection.apply_store_keep(&target) {
+///     Err(ApplyError::CollectionError(error)) => error,
+///     _ => panic!("the broken file was not reported"),
+/// };
+///
+/// assert_eq!(error.get_paths().len(), 2);
+/// assert_eq!(error.get_store_errors().len(), 1);
+/// assert_eq!(error.get_store_errors()[0].0, dir.join("broken.jpg"));
+/// assert_eq!(error.get_failed_paths(), vec![&dir.join("broken.jpg")]);
+/// ```
 pub struct CollectionError {
     /// Output file paths that weren't affected by the error and were successfully stored
     paths: Vec<PathBuf>,
-    /// List of all store errors that occurred while storing each item
-    store_errors: Vec<FileError>,
-    /// List of all operations errors that occurred while applying operations to each item
-    operation_errors: Vec<OperationError>,
+    /// List of all errors that occurred while loading or storing each item, with the source path of the item
+    store_errors: Vec<(PathBuf, FileError)>,
+    /// List of all operations errors that occurred while applying operations to each item, with the source path of the item
+    operation_errors: Vec<(PathBuf, OperationError)>,
 }
 
 impl CollectionError {
     pub fn new(
         paths: Vec<PathBuf>,
-        store_errors: Vec<FileError>,
-        operation_errors: Vec<OperationError>,
+        store_errors: Vec<(PathBuf, FileError)>,
+        operation_errors: Vec<(PathBuf, OperationError)>,
     ) -> Self {
         CollectionError {
             paths,
@@ -163,12 +307,85 @@ impl CollectionError {
     pub fn get_paths(&self) -> &Vec<PathBuf> {
         &self.paths
     }
-    /// Gets all StoreErrors that occurred while storing each item
-    pub fn get_store_errors(&self) -> &Vec<FileError> {
+    /// Gets all errors that occurred while loading or storing each item, with the source path of the item
+    pub fn get_store_errors(&self) -> &Vec<(PathBuf, FileError)> {
         &self.store_errors
     }
-    /// Gets all OperationErrors that occurred while applying all operations to each item
-    pub fn get_operation_errors(&self) -> &Vec<OperationError> {
+    /// Gets all OperationErrors that occurred while applying all operations to each item,
+    /// with the source path of the item
+    pub fn get_operation_errors(&self) -> &Vec<(PathBuf, OperationError)> {
         &self.operation_errors
     }
+    /// Gets the source paths of all items that caused an error
+    pub fn get_failed_paths(&self) -> Vec<&PathBuf> {
+        self.store_errors
+            .iter()
+            .map(|(path, _)| path)
+            .chain(self.operation_errors.iter().map(|(path, _)| path))
+            .collect()
+    }
+    /// Gets all errors, first the store errors and then the operation errors, without their source paths
+    ///
+    /// This allows to e.g. log all errors in one loop. The errors are `Send` and `Sync`,
+    /// so they can be passed on to other threads. `&CollectionError` can be iterated the same way.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::PathBuf;
+    /// use thumbnailer::errors::{CollectionError, FileError, OperationError, OperationErrorInfo};
+    /// use thumbnailer::thumbnail::operations::BlurOp;
+    ///
+    /// let error = CollectionError::new(
+    ///     vec![],
+    ///     vec![
+    ///         (PathBuf::from("a.jpg"), FileError::UnknownError),
+    ///         (PathBuf::from("b.jpg"), FileError::FrameNotFound(2)),
+    ///     ],
+    ///     vec![(
+    ///         PathBuf::from("c.jpg"),
+    ///         OperationError::new(Box::new(BlurOp::new(1.0)), OperationErrorInfo::InvalidParameters),
+    ///     )],
+    /// );
+    ///
+    /// assert_eq!(error.errors().count(), 3);
+    /// assert_eq!((&error).into_iter().count(), 3);
+    /// assert_eq!(error.to_string(), "2 store errors, 1 operation error");
+    /// let messages: Vec<String> = error.errors().map(|error| error.to_string()).collect();
+    /// assert_eq!(messages[1], "Frame 2 does not exist");
+    /// ```
+    pub fn errors(&self) -> impl Iterator<Item = &(dyn Error + Send + Sync + 'static)> {
+        self.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a CollectionError {
+    type Item = &'a (dyn Error + Send + Sync + 'static);
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let store_errors = self
+            .store_errors
+            .iter()
+            .map(|(_, error)| error as &(dyn Error + Send + Sync));
+        let operation_errors = self
+            .operation_errors
+            .iter()
+            .map(|(_, error)| error as &(dyn Error + Send + Sync));
+        Box::new(store_errors.chain(operation_errors))
+    }
+}
+
+impl fmt::Display for CollectionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let plural = |count: usize| if count == 1 { "" } else { "s" };
+        let (store, operation) = (self.store_errors.len(), self.operation_errors.len());
+        write!(
+            f,
+            "{} store error{}, {} operation error{}",
+            store,
+            plural(store),
+            operation,
+            plural(operation)
+        )
+    }
 }
@@ -1,12 +1,18 @@
 use crate::errors::ApplyError;
+#[cfg(feature = "face-detection")]
+use crate::thumbnail::operations::FaceCropOp;
 use crate::thumbnail::operations::{
-    BlurOp, BrightenOp, CombineOp, ContrastOp, CropOp, ExifOp, FlipOp, HuerotateOp, InvertOp,
-    Operation, ResizeOp, RotateOp, TextOp, UnsharpenOp,
+    AppendOp, AutoContrastOp, BlurOp, BlurRegionOp, BrightenOp, CaptionOp, CombineInRegionOp,
+    CombineOp, ContrastOp, CropOp, DiagonalWatermarkOp, DistortionOp, EdgeDetectOp, EmbossOp,
+    ExifOp, FastBlurOp, FlipOp, HuerotateOp, InvertOp, LevelsOp, MedianOp, OpacityOp, Operation,
+    PadToRatioOp, QuantizeOp, ResizeOp, RotateOp, SelectiveDesaturateOp, ShadowOp,
+    SmartWatermarkOp, TemperatureOp, TextOp, TintOp, TrimOp, UnsharpenOp,
 };
 use crate::{StaticThumbnail, Target};
 use std::path::PathBuf;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// The different options for the resize-operation as an enum
 pub enum Resize {
     /// Option: scale to a given height, keep aspect ratio.
@@ -30,6 +36,7 @@ pub enum Resize {
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Different positioning-options for overlays as an enum
 pub enum BoxPosition {
     /// Coordinates of the top-left-corner in the background image of the overlayed object.
@@ -55,6 +62,7 @@ pub enum BoxPosition {
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Different options for cropping as an enum
 pub enum Crop {
     /// Options for exactly cropping the image to a rectangle given by the coordinates of the top-left-corner and width and height.
@@ -70,9 +78,60 @@ pub enum Crop {
     /// * ratio_width: `u32`
     /// * ratio_height: `u32`
     Ratio(f32, f32),
+    /// Option for cropping the image to a rectangle given by a ratio of width and height, like `Crop::Ratio`.
+    /// Instead of the center, the part of the image given by the `Gravity` is kept.
+    /// ### Arguments:
+    /// * ratio_width: `f32`
+    /// * ratio_height: `f32`
+    /// * gravity: `Gravity`
+    RatioGravity(f32, f32, Gravity),
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The part of the image, that is kept when cropping to a ratio, as an enum
+pub enum Gravity {
+    /// Option to keep the top of the image
+    North,
+    /// Option to keep the bottom of the image
+    South,
+    /// Option to keep the right side of the image
+    East,
+    /// Option to keep the left side of the image
+    West,
+    /// Option to keep the center of the image
+    Center,
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The options to scale an overlay image to a region as an enum
+pub enum Fit {
+    /// Option to scale the image to cover the whole region, keep aspect ratio. Overflowing parts are cut off.
+    Cover,
+    /// Option to scale the image so that it fits inside the region, keep aspect ratio.
+    Contain,
+    /// Option to scale the image to the size of the region exactly, aspect ratio may be changed.
+    Stretch,
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The color channels of an image as an enum
+pub enum Channel {
+    /// Option for all color channels
+    #[default]
+    All,
+    /// Option for the red channel
+    Red,
+    /// Option for the green channel
+    Green,
+    /// Option for the blue channel
+    Blue,
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Orientation options as an enum
 pub enum Orientation {
     /// Option for a vertical orientation
@@ -82,6 +141,7 @@ pub enum Orientation {
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Exif {
     Keep,
     Clear,
@@ -90,6 +150,7 @@ pub enum Exif {
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Collection of filters that can be applied to images
 pub enum ResampleFilter {
     /// Nearest Neighbor Filter
@@ -105,6 +166,7 @@ pub enum ResampleFilter {
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Rotation options as an enum
 pub enum Rotation {
     /// Option for a 90 degree clockwise rotation
@@ -113,6 +175,30 @@ pub enum Rotation {
     Rotate180,
     /// Option for a 270 degree clockwise rotation
     Rotate270,
+    /// Option for a clockwise rotation by an arbitrary angle.
+    /// The canvas grows to fit the rotated image, the exposed corners are filled as given by `RotationFill`.
+    /// ### Arguments:
+    /// * degrees: `f32`
+    Degrees(f32),
+}
+
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The options to fill the corners, that are exposed by a rotation by an arbitrary angle, as an enum
+pub enum RotationFill {
+    /// Option to make the corners transparent.
+    /// File types without alpha channel, e.g. JPEG, store the corners black, unless the target is
+    /// composited over a background color, see `Target::flatten()`.
+    #[default]
+    Transparent,
+    /// Option to fill the corners with a RGBA color.
+    /// File types without alpha channel, e.g. JPEG, store the corners in the RGB part of the color,
+    /// unless the target is composited over a background color.
+    /// ### Arguments:
+    /// * color: `[u8; 4]`
+    Color([u8; 4]),
+    /// Option to repeat the nearest edge pixel of the image. This avoids visible borders on opaque images.
+    Edge,
 }
 
 /// A trait for the queueing of operations
@@ -126,6 +212,17 @@ pub trait OperationContainer {
     /// * `&mut self`: The object that contains a queue for which the function is implemented
     /// * `op`: The operation that should be added as `Box<dyn Operation>`
     fn add_op(&mut self, op: Box<dyn Operation>);
+
+    /// Gets the filter used by resize operations, that are queued without a custom filter
+    ///
+    /// Returns `None` by default, then the `ResizeOp` chooses its own default filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self`: The object that contains a queue for which the function is implemented
+    fn default_resize_filter(&self) -> Option<ResampleFilter> {
+        None
+    }
 }
 
 /// A trait for executing operations on a Thumbnail
@@ -202,6 +299,8 @@ pub trait GenericThumbnailOperations {
     ///
     /// * `&mut self` - The object on which resize should be applied
     /// * `size` - operation options represented by the `Resize` enum
+    ///
+    /// The image is resampled with `ResampleFilter::Triangle`, see `resize_filter()` for other filters.
     fn resize(&mut self, size: Resize) -> &mut dyn GenericThumbnail;
 
     /// Representation of the resize-operation with custom filter
@@ -227,6 +326,39 @@ pub trait GenericThumbnailOperations {
     /// * `sigma` - value of how much the image should be blurred. [Gaussian Blur] (https://en.wikipedia.org/wiki/Gaussian_blur)
     fn blur(&mut self, sigma: f32) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the blur operation for a region
+    ///
+    /// This function adds the blur operation for a rectangular region to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which blur should be applied
+    /// * `x` - The x coordinate of the top-left-corner of the region
+    /// * `y` - The y coordinate of the top-left-corner of the region
+    /// * `width` - The width of the region
+    /// * `height` - The height of the region
+    /// * `sigma` - value of how much the region should be blurred. [Gaussian Blur] (https://en.wikipedia.org/wiki/Gaussian_blur)
+    fn blur_region(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        sigma: f32,
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the fast-blur-operation
+    ///
+    /// This function adds the fast blur operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which fast blur should be applied
+    /// * `radius` - the radius of the box blur. [Box Blur] (https://en.wikipedia.org/wiki/Box_blur)
+    fn fast_blur(&mut self, radius: u32) -> &mut dyn GenericThumbnail;
+
     /// Representation of the brighten-operation
     ///
     /// This function adds the brighten operation to the queue of the oject represented by `&mut self`.
@@ -249,6 +381,53 @@ pub trait GenericThumbnailOperations {
     /// * `degree` - value of degrees to rotate each pixel by
     fn huerotate(&mut self, degree: i32) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the selective-desaturate operation
+    ///
+    /// This function adds the selective-desaturate operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which selective-desaturate should be applied
+    /// * `keep_hue` - The hue in degrees, whose colors are kept, e.g. 0.0 for red
+    /// * `range` - The maximum distance in degrees from `keep_hue`, of hues that are kept. All other colors turn gray.
+    fn selective_desaturate(&mut self, keep_hue: f32, range: f32) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the tint operation
+    ///
+    /// This function adds the tint operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which tint should be applied
+    /// * `color` - The RGB color the image should be tinted with
+    /// * `strength` - How much the image should be tinted, between 0.0 and 1.0
+    fn tint(&mut self, color: [u8; 3], strength: f32) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the duotone operation
+    ///
+    /// This function adds the duotone operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which duotone should be applied
+    /// * `shadow` - The RGB color the shadows should be mapped to
+    /// * `highlight` - The RGB color the highlights should be mapped to
+    fn duotone(&mut self, shadow: [u8; 3], highlight: [u8; 3]) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the color-temperature operation
+    ///
+    /// This function adds the color-temperature operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which color-temperature should be applied
+    /// * `shift` - The shift in Kelvin, positive values warm and negative values cool the image, e.g. 1000
+    fn temperature(&mut self, shift: i32) -> &mut dyn GenericThumbnail;
+
     /// Representation of the contrast operation
     ///
     /// This function adds the contrast operation to the queue of the oject represented by `&mut self`.
@@ -260,6 +439,60 @@ pub trait GenericThumbnailOperations {
     /// * `value` - Amount of adjusted contrast. Positiv values will increase, negative values will decrease contrast.
     fn contrast(&mut self, value: f32) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the auto-contrast operation
+    ///
+    /// This function adds the auto-contrast operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which auto-contrast should be applied
+    fn auto_contrast(&mut self) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the auto-contrast operation with clipping
+    ///
+    /// This function adds the auto-contrast operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which auto-contrast should be applied
+    /// * `clip` - the percentage of the darkest and of the brightest pixels, that are ignored when stretching
+    fn auto_contrast_clipped(&mut self, clip: f32) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the levels operation
+    ///
+    /// This function adds the levels operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which levels should be applied
+    /// * `black` - the input value that is mapped to black, has to be lower than `white`
+    /// * `white` - the input value that is mapped to white
+    /// * `gamma` - the gamma correction of the midtones, between 0.1 and 10.0. 1.0 keeps the midtones linear.
+    fn levels(&mut self, black: u8, white: u8, gamma: f32) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the levels operation for a single color channel
+    ///
+    /// This function adds the levels operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which levels should be applied
+    /// * `channel` - the color channel that is adjusted, represented by the `Channel` enum
+    /// * `black` - the input value that is mapped to black, has to be lower than `white`
+    /// * `white` - the input value that is mapped to white
+    /// * `gamma` - the gamma correction of the midtones, between 0.1 and 10.0. 1.0 keeps the midtones linear.
+    fn levels_channel(
+        &mut self,
+        channel: Channel,
+        black: u8,
+        white: u8,
+        gamma: f32,
+    ) -> &mut dyn GenericThumbnail;
+
     /// Representation of the unsharpen operation
     ///
     /// This function adds the unsharpen operation to the queue of the oject represented by `&mut self`.
@@ -274,6 +507,39 @@ pub trait GenericThumbnailOperations {
     /// More information: [Digital unsharp masking](https://en.wikipedia.org/wiki/Unsharp_masking#Digital_unsharp_masking)
     fn unsharpen(&mut self, sigma: f32, threshold: i32) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the median operation
+    ///
+    /// This function adds the median operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which median should be applied
+    /// * `radius` - the radius of the window, of which the median is taken. Large radii are slow on large images.
+    fn median(&mut self, radius: u32) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the quantize operation
+    ///
+    /// This function adds the quantize operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which quantize should be applied
+    /// * `max_colors` - the maximum number of colors of the image, between 2 and 256
+    fn quantize(&mut self, max_colors: u16) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the opacity operation
+    ///
+    /// This function adds the opacity operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which opacity should be applied
+    /// * `factor` - the factor the alpha channel is multiplied with, between 0.0 (transparent) and 1.0 (unchanged)
+    fn opacity(&mut self, factor: f32) -> &mut dyn GenericThumbnail;
+
     /// Representation of the crop operation
     ///
     /// This function adds the crop operation to the queue of the oject represented by `&mut self`.
@@ -285,6 +551,48 @@ pub trait GenericThumbnailOperations {
     /// * `c` - Options for the operation represented by the `Crop` enum
     fn crop(&mut self, c: Crop) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the pad-to-ratio operation
+    ///
+    /// This function adds the pad-to-ratio operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which pad-to-ratio should be applied
+    /// * `ratio_width` - The width part of the ratio, e.g. 16.0
+    /// * `ratio_height` - The height part of the ratio, e.g. 9.0
+    /// * `fill` - The color of the added bars as RGBA
+    fn pad_to_ratio(
+        &mut self,
+        ratio_width: f32,
+        ratio_height: f32,
+        fill: [u8; 4],
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the face-crop operation
+    ///
+    /// This function adds the face-crop operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`. Only available with the `face-detection` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which face-crop should be applied
+    /// * `model` - The path of the SeetaFace frontal face detection model used by `rustface`
+    /// * `padding` - The space around the face on each side, relative to the size of the face
+    #[cfg(feature = "face-detection")]
+    fn face_crop(&mut self, model: PathBuf, padding: f32) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the trim operation
+    ///
+    /// This function adds the trim operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which trim should be applied
+    /// * `tolerance` - the maximum difference of a channel to the background color, for a pixel to be removed
+    fn trim(&mut self, tolerance: u8) -> &mut dyn GenericThumbnail;
+
     /// Representation of the flip operation
     ///
     /// This function adds the crop operation to the queue of the oject represented by `&mut self`.
@@ -306,6 +614,46 @@ pub trait GenericThumbnailOperations {
     /// * `&mut self` - The object on which invert should be applied
     fn invert(&mut self) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the invert operation for a region
+    ///
+    /// This function adds the invert operation for a rectangular region to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which invert should be applied
+    /// * `x` - The x coordinate of the top-left-corner of the region
+    /// * `y` - The y coordinate of the top-left-corner of the region
+    /// * `width` - The width of the region
+    /// * `height` - The height of the region
+    fn invert_region(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the emboss operation
+    ///
+    /// This function adds the emboss operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which emboss should be applied
+    fn emboss(&mut self) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the edge-detection operation
+    ///
+    /// This function adds the edge-detection operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which edge-detection should be applied
+    fn detect_edges(&mut self) -> &mut dyn GenericThumbnail;
+
     fn exif(&mut self, metadata: Exif) -> &mut dyn GenericThumbnail;
 
     /// Representation of the draw-text operation
@@ -320,6 +668,69 @@ pub trait GenericThumbnailOperations {
     /// * `pos` - The position of the text represented by the `BoxPosition` enum
     fn text(&mut self, text: String, pos: BoxPosition) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the draw-text operation, with a font height relative to the image height
+    ///
+    /// This function adds the draw-text operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which draw-text should be applied
+    /// * `text` - The text that should be drawn
+    /// * `pos` - The position of the text represented by the `BoxPosition` enum
+    /// * `fraction` - The font height relative to the image height when the operation is applied, e.g. 0.1
+    fn text_relative(
+        &mut self,
+        text: String,
+        pos: BoxPosition,
+        fraction: f32,
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the caption operation
+    ///
+    /// This function adds the caption operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which caption should be applied
+    /// * `text` - The text that should be drawn centered along the bottom, in white or black depending on the background
+    fn caption(&mut self, text: String) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the diagonal-watermark operation
+    ///
+    /// This function adds the diagonal-watermark operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which diagonal-watermark should be applied
+    /// * `text` - The text of the watermark, e.g. "SAMPLE"
+    /// * `opacity` - The opacity of the watermark, between 0.0 (invisible) and 1.0 (opaque)
+    /// * `spacing` - The space in pixels between the repetitions of the watermark
+    fn diagonal_watermark(
+        &mut self,
+        text: String,
+        opacity: f32,
+        spacing: u32,
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the smart-watermark operation
+    ///
+    /// This function adds the smart-watermark operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which smart-watermark should be applied
+    /// * `logo` - The image that should be drawn on `self`
+    /// * `corner_candidates` - The positions `logo` may be placed at, the most visible one is used
+    fn smart_watermark(
+        &mut self,
+        logo: StaticThumbnail,
+        corner_candidates: &[BoxPosition],
+    ) -> &mut dyn GenericThumbnail;
+
     /// Representation of the combine operation
     ///
     /// This function adds the combine operation to the queue of the oject represented by `&mut self`.
@@ -332,6 +743,77 @@ pub trait GenericThumbnailOperations {
     /// * `pos` - The position of `image` represented by the `BoxPosition` enum
     fn combine(&mut self, image: StaticThumbnail, pos: BoxPosition) -> &mut dyn GenericThumbnail;
 
+    /// Representation of the combine operation with strict bounds
+    ///
+    /// This function adds the combine operation to the queue of the oject represented by `&mut self`.
+    /// Unlike `combine()` the operation fails, if `image` does not fully fit onto `self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which combine should be applied
+    /// * `image` - The image that should be drawn on `self`
+    /// * `pos` - The position of `image` represented by the `BoxPosition` enum
+    fn combine_strict_bounds(
+        &mut self,
+        image: StaticThumbnail,
+        pos: BoxPosition,
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the combine-in-region operation
+    ///
+    /// This function adds the combine-in-region operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which combine-in-region should be applied
+    /// * `image` - The image that should be drawn on `self`
+    /// * `region` - The region of `self` the image is drawn into, as (position_x, position_y, width, height)
+    /// * `fit` - How `image` is scaled to the region, represented by the `Fit` enum
+    fn combine_in_region(
+        &mut self,
+        image: StaticThumbnail,
+        region: (u32, u32, u32, u32),
+        fit: Fit,
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the append operation
+    ///
+    /// This function adds the append operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which append should be applied
+    /// * `image` - The image that should be appended to `self`
+    /// * `orientation` - Whether `image` is appended on the right side or at the bottom, represented by the `Orientation` enum
+    fn append(
+        &mut self,
+        image: StaticThumbnail,
+        orientation: Orientation,
+    ) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the drop-shadow operation
+    ///
+    /// This function adds the drop-shadow operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which drop-shadow should be applied
+    /// * `dx` - The horizontal offset of the shadow, positive values move it to the right
+    /// * `dy` - The vertical offset of the shadow, positive values move it down
+    /// * `blur` - The sigma of the gaussian blur, that softens the edges of the shadow
+    /// * `color` - The RGBA color of the shadow
+    fn drop_shadow(
+        &mut self,
+        dx: i32,
+        dy: i32,
+        blur: f32,
+        color: [u8; 4],
+    ) -> &mut dyn GenericThumbnail;
+
     /// Representation of the rotate operation
     ///
     /// This function adds the rotate operation to the queue of the oject represented by `&mut self`.
@@ -342,6 +824,30 @@ pub trait GenericThumbnailOperations {
     /// * `&mut self` - The object on which rotate should be applied
     /// * `rotation` - Options for the operation represented by the `Rotation` enum
     fn rotate(&mut self, rotation: Rotation) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the rotate operation with a fill for the exposed corners
+    ///
+    /// This function adds the rotate operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which rotate should be applied
+    /// * `rotation` - Options for the operation represented by the `Rotation` enum
+    /// * `fill` - How the corners exposed by `Rotation::Degrees` are filled, represented by the `RotationFill` enum
+    fn rotate_fill(&mut self, rotation: Rotation, fill: RotationFill) -> &mut dyn GenericThumbnail;
+
+    /// Representation of the distortion-correction operation
+    ///
+    /// This function adds the distortion-correction operation to the queue of the oject represented by `&mut self`.
+    /// It returns a `GenericThumbnail`.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which distortion-correction should be applied
+    /// * `k1` - The coefficient of the second power of the radius, negative values correct barrel distortion
+    /// * `k2` - The coefficient of the fourth power of the radius
+    fn distortion(&mut self, k1: f32, k2: f32) -> &mut dyn GenericThumbnail;
 }
 
 impl<T> GenericThumbnailOperations for T
@@ -351,6 +857,7 @@ where
     /// Representation of the resize operation without custom filter
     ///
     /// This function adds `ResizeOp` without the optional filter to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// If the object has a default filter, see `OperationContainer::default_resize_filter()`, that filter is used instead.
     /// It returns itself after that.
     ///
     /// # Arguments
@@ -362,7 +869,8 @@ where
     ///
     /// This function won't panic
     fn resize(&mut self, size: Resize) -> &mut dyn GenericThumbnail {
-        self.add_op(Box::new(ResizeOp::new(size, None)));
+        let filter = self.default_resize_filter();
+        self.add_op(Box::new(ResizeOp::new(size, filter)));
         self
     }
 
@@ -403,6 +911,53 @@ where
         self
     }
 
+    /// Representation of the blur operation for a region
+    ///
+    /// This function adds `BlurRegionOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `BlurRegionOp` should be applied
+    /// * `x` - The x coordinate of the top-left-corner of the region
+    /// * `y` - The y coordinate of the top-left-corner of the region
+    /// * `width` - The width of the region
+    /// * `height` - The height of the region
+    /// * `sigma` - value of how much the region should be blurred. [Gaussian Blur] (https://en.wikipedia.org/wiki/Gaussian_blur)
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn blur_region(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        sigma: f32,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(BlurRegionOp::new(x, y, width, height, sigma)));
+        self
+    }
+
+    /// Representation of the fast-blur-operation
+    ///
+    /// This function adds `FastBlurOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `FastBlurOp` should be applied
+    /// * `radius` - the radius of the box blur. [Box Blur] (https://en.wikipedia.org/wiki/Box_blur)
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn fast_blur(&mut self, radius: u32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(FastBlurOp::new(radius)));
+        self
+    }
+
     /// Representation of the brighten operation
     ///
     /// This function adds `BrightenOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
@@ -439,72 +994,348 @@ where
         self
     }
 
-    /// Representation of the contrast operation
+    /// Representation of the selective-desaturate operation
     ///
-    /// This function adds `ContrastOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// This function adds `SelectiveDesaturateOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
     /// It returns itself after that.
     ///
     /// # Arguments
     ///
-    /// * `&mut self` - The object on which `ContrastOp` should be applied
-    /// * `value` - Amount of adjusted contrast. Positiv values will increase, negative values will decrease contrast.
+    /// * `&mut self` - The object on which `SelectiveDesaturateOp` should be applied
+    /// * `keep_hue` - The hue in degrees, whose colors are kept, e.g. 0.0 for red
+    /// * `range` - The maximum distance in degrees from `keep_hue`, of hues that are kept. All other colors turn gray.
     ///
     /// # Panic
     ///
     /// This function won't panic
-    fn contrast(&mut self, value: f32) -> &mut dyn GenericThumbnail {
-        self.add_op(Box::new(ContrastOp::new(value)));
+    fn selective_desaturate(&mut self, keep_hue: f32, range: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(SelectiveDesaturateOp::new(keep_hue, range)));
         self
     }
 
-    /// Representation of the unsharpen operation
+    /// Representation of the tint operation
     ///
-    /// This function adds `UnsharpenOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// This function adds `TintOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
     /// It returns itself after that.
     ///
     /// # Arguments
     ///
-    /// * `&mut self` - The object on which `UnsharpenOp` should be applied
-    /// * `sigma` as amount to blur the 'DynamicImage'
-    /// * `threshold` as control of how much to sharpen
-    ///
-    /// More information: [Digital unsharp masking](https://en.wikipedia.org/wiki/Unsharp_masking#Digital_unsharp_masking)
+    /// * `&mut self` - The object on which `TintOp` should be applied
+    /// * `color` - The RGB color the image should be tinted with
+    /// * `strength` - How much the image should be tinted, between 0.0 and 1.0
     ///
     /// # Panic
     ///
     /// This function won't panic
-    fn unsharpen(&mut self, sigma: f32, threshold: i32) -> &mut dyn GenericThumbnail {
-        self.add_op(Box::new(UnsharpenOp::new(sigma, threshold)));
+    fn tint(&mut self, color: [u8; 3], strength: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(TintOp::new(color, strength)));
         self
     }
 
-    /// Representation of the crop operation
+    /// Representation of the duotone operation
     ///
-    /// This function adds `CropOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// This function adds `TintOp` as duotone to the queue of a `GenericThumbnail` represented by `&mut self`.
     /// It returns itself after that.
     ///
     /// # Arguments
     ///
-    /// * `&mut self` - The object on which `CropOp` should be applied
-    /// * `c` - Options for the operation represented by the `Crop` enum
+    /// * `&mut self` - The object on which `TintOp` should be applied
+    /// * `shadow` - The RGB color the shadows should be mapped to
+    /// * `highlight` - The RGB color the highlights should be mapped to
     ///
     /// # Panic
     ///
     /// This function won't panic
-    fn crop(&mut self, c: Crop) -> &mut dyn GenericThumbnail {
-        self.add_op(Box::new(CropOp::new(c)));
+    fn duotone(&mut self, shadow: [u8; 3], highlight: [u8; 3]) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(TintOp::new_duotone(shadow, highlight)));
         self
     }
 
-    /// Representation of the flip operation
+    /// Representation of the color-temperature operation
     ///
-    /// This function adds `FlipOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// This function adds `TemperatureOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
     /// It returns itself after that.
     ///
     /// # Arguments
     ///
-    /// * `&mut self` - The object on which `InvertOp` should be applied
-    /// * `orientation` -  Options for the operation represented by the `Orientation` enum
+    /// * `&mut self` - The object on which `TemperatureOp` should be applied
+    /// * `shift` - The shift in Kelvin, positive values warm and negative values cool `self`
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn temperature(&mut self, shift: i32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(TemperatureOp::new(shift)));
+        self
+    }
+
+    /// Representation of the contrast operation
+    ///
+    /// This function adds `ContrastOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `ContrastOp` should be applied
+    /// * `value` - Amount of adjusted contrast. Positiv values will increase, negative values will decrease contrast.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn contrast(&mut self, value: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(ContrastOp::new(value)));
+        self
+    }
+
+    /// Representation of the auto-contrast operation
+    ///
+    /// This function adds `AutoContrastOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `AutoContrastOp` should be applied
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn auto_contrast(&mut self) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(AutoContrastOp::new(0.0)));
+        self
+    }
+
+    /// Representation of the auto-contrast operation with clipping
+    ///
+    /// This function adds `AutoContrastOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `AutoContrastOp` should be applied
+    /// * `clip` - the percentage of the darkest and of the brightest pixels, that are ignored when stretching
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn auto_contrast_clipped(&mut self, clip: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(AutoContrastOp::new(clip)));
+        self
+    }
+
+    /// Representation of the levels operation
+    ///
+    /// This function adds `LevelsOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `LevelsOp` should be applied
+    /// * `black` - the input value that is mapped to black, has to be lower than `white`
+    /// * `white` - the input value that is mapped to white
+    /// * `gamma` - the gamma correction of the midtones, between 0.1 and 10.0. 1.0 keeps the midtones linear.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn levels(&mut self, black: u8, white: u8, gamma: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(LevelsOp::new(black, white, gamma)));
+        self
+    }
+
+    /// Representation of the levels operation for a single color channel
+    ///
+    /// This function adds `LevelsOp` for the given channel to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `LevelsOp` should be applied
+    /// * `channel` - the color channel that is adjusted, represented by the `Channel` enum
+    /// * `black` - the input value that is mapped to black, has to be lower than `white`
+    /// * `white` - the input value that is mapped to white
+    /// * `gamma` - the gamma correction of the midtones, between 0.1 and 10.0. 1.0 keeps the midtones linear.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn levels_channel(
+        &mut self,
+        channel: Channel,
+        black: u8,
+        white: u8,
+        gamma: f32,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(LevelsOp::new_channel(
+            channel, black, white, gamma,
+        )));
+        self
+    }
+
+    /// Representation of the unsharpen operation
+    ///
+    /// This function adds `UnsharpenOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `UnsharpenOp` should be applied
+    /// * `sigma` as amount to blur the 'DynamicImage'
+    /// * `threshold` as control of how much to sharpen
+    ///
+    /// More information: [Digital unsharp masking](https://en.wikipedia.org/wiki/Unsharp_masking#Digital_unsharp_masking)
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn unsharpen(&mut self, sigma: f32, threshold: i32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(UnsharpenOp::new(sigma, threshold)));
+        self
+    }
+
+    /// Representation of the median operation
+    ///
+    /// This function adds `MedianOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `MedianOp` should be applied
+    /// * `radius` - the radius of the window, of which the median is taken. Large radii are slow on large images.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn median(&mut self, radius: u32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(MedianOp::new(radius)));
+        self
+    }
+
+    /// Representation of the quantize operation
+    ///
+    /// This function adds `QuantizeOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `QuantizeOp` should be applied
+    /// * `max_colors` - the maximum number of colors of the image, between 2 and 256
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn quantize(&mut self, max_colors: u16) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(QuantizeOp::new(max_colors)));
+        self
+    }
+
+    /// Representation of the opacity operation
+    ///
+    /// This function adds `OpacityOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `OpacityOp` should be applied
+    /// * `factor` - the factor the alpha channel is multiplied with, between 0.0 (transparent) and 1.0 (unchanged)
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn opacity(&mut self, factor: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(OpacityOp::new(factor)));
+        self
+    }
+
+    /// Representation of the crop operation
+    ///
+    /// This function adds `CropOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `CropOp` should be applied
+    /// * `c` - Options for the operation represented by the `Crop` enum
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn crop(&mut self, c: Crop) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(CropOp::new(c)));
+        self
+    }
+
+    /// Representation of the pad-to-ratio operation
+    ///
+    /// This function adds `PadToRatioOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `PadToRatioOp` should be applied
+    /// * `ratio_width` - The width part of the ratio, e.g. 16.0
+    /// * `ratio_height` - The height part of the ratio, e.g. 9.0
+    /// * `fill` - The color of the added bars as RGBA
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn pad_to_ratio(
+        &mut self,
+        ratio_width: f32,
+        ratio_height: f32,
+        fill: [u8; 4],
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(PadToRatioOp::new(ratio_width, ratio_height, fill)));
+        self
+    }
+
+    /// Representation of the face-crop operation
+    ///
+    /// This function adds `FaceCropOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `FaceCropOp` should be applied
+    /// * `model` - The path of the SeetaFace frontal face detection model used by `rustface`
+    /// * `padding` - The space around the face on each side, relative to the size of the face
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    #[cfg(feature = "face-detection")]
+    fn face_crop(&mut self, model: PathBuf, padding: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(FaceCropOp::new(model, padding)));
+        self
+    }
+
+    /// Representation of the trim operation
+    ///
+    /// This function adds `TrimOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `TrimOp` should be applied
+    /// * `tolerance` - the maximum difference of a channel to the background color, for a pixel to be removed
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn trim(&mut self, tolerance: u8) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(TrimOp::new(tolerance)));
+        self
+    }
+
+    /// Representation of the flip operation
+    ///
+    /// This function adds `FlipOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `InvertOp` should be applied
+    /// * `orientation` -  Options for the operation represented by the `Orientation` enum
     ///
     /// # Panic
     ///
@@ -531,6 +1362,67 @@ where
         self
     }
 
+    /// Representation of the invert operation for a region
+    ///
+    /// This function adds `InvertOp` for a rectangular region to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `InvertOp` should be applied
+    /// * `x` - The x coordinate of the top-left-corner of the region
+    /// * `y` - The y coordinate of the top-left-corner of the region
+    /// * `width` - The width of the region
+    /// * `height` - The height of the region
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn invert_region(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(InvertOp::new_region(x, y, width, height)));
+        self
+    }
+
+    /// Representation of the emboss operation
+    ///
+    /// This function adds `EmbossOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `EmbossOp` should be applied
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn emboss(&mut self) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(EmbossOp::new()));
+        self
+    }
+
+    /// Representation of the edge-detection operation
+    ///
+    /// This function adds `EdgeDetectOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `EdgeDetectOp` should be applied
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn detect_edges(&mut self) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(EdgeDetectOp::new()));
+        self
+    }
+
     fn exif(&mut self, metadata: Exif) -> &mut dyn GenericThumbnail {
         self.add_op(Box::new(ExifOp::new(metadata)));
         self
@@ -555,6 +1447,100 @@ where
         self
     }
 
+    /// Representation of the draw-text operation, with a font height relative to the image height
+    ///
+    /// This function adds `TextOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `TextOp` should be applied
+    /// * `text` - The text that should be drawn on `self`
+    /// * `pos` - The position of `text` represented by the `BoxPosition` enum
+    /// * `fraction` - The font height relative to the height of `self`
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn text_relative(
+        &mut self,
+        text: String,
+        pos: BoxPosition,
+        fraction: f32,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(TextOp::new_relative(text, pos, fraction)));
+        self
+    }
+
+    /// Representation of the caption operation
+    ///
+    /// This function adds `CaptionOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `CaptionOp` should be applied
+    /// * `text` - The text that should be drawn centered along the bottom of `self`
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn caption(&mut self, text: String) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(CaptionOp::new(text)));
+        self
+    }
+
+    /// Representation of the diagonal-watermark operation
+    ///
+    /// This function adds `DiagonalWatermarkOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `DiagonalWatermarkOp` should be applied
+    /// * `text` - The text of the watermark, e.g. "SAMPLE"
+    /// * `opacity` - The opacity of the watermark, between 0.0 (invisible) and 1.0 (opaque)
+    /// * `spacing` - The space in pixels between the repetitions of the watermark
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn diagonal_watermark(
+        &mut self,
+        text: String,
+        opacity: f32,
+        spacing: u32,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(DiagonalWatermarkOp::new(text, opacity, spacing)));
+        self
+    }
+
+    /// Representation of the smart-watermark operation
+    ///
+    /// This function adds `SmartWatermarkOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `SmartWatermarkOp` should be applied
+    /// * `logo` - The image that should be drawn on `self`
+    /// * `corner_candidates` - The positions `logo` may be placed at, the most visible one is used
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn smart_watermark(
+        &mut self,
+        logo: StaticThumbnail,
+        corner_candidates: &[BoxPosition],
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(SmartWatermarkOp::new(
+            logo,
+            corner_candidates.to_vec(),
+        )));
+        self
+    }
+
     /// Representation of the combine operation
     ///
     /// This function adds `CombineOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
@@ -574,6 +1560,104 @@ where
         self
     }
 
+    /// Representation of the combine operation with strict bounds
+    ///
+    /// This function adds `CombineOp` with strict bounds to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `CombineOp` should be applied
+    /// * `image` - The image that should be drawn on `self`
+    /// * `pos` - The position of `image` represented by the `BoxPosition` enum
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn combine_strict_bounds(
+        &mut self,
+        image: StaticThumbnail,
+        pos: BoxPosition,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(CombineOp::new_strict_bounds(image, pos)));
+        self
+    }
+
+    /// Representation of the combine-in-region operation
+    ///
+    /// This function adds `CombineInRegionOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `CombineInRegionOp` should be applied
+    /// * `image` - The image that should be drawn on `self`
+    /// * `region` - The region of `self` the image is drawn into, as (position_x, position_y, width, height)
+    /// * `fit` - How `image` is scaled to the region, represented by the `Fit` enum
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn combine_in_region(
+        &mut self,
+        image: StaticThumbnail,
+        region: (u32, u32, u32, u32),
+        fit: Fit,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(CombineInRegionOp::new(image, region, fit)));
+        self
+    }
+
+    /// Representation of the append operation
+    ///
+    /// This function adds `AppendOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `AppendOp` should be applied
+    /// * `image` - The image that should be appended to `self`
+    /// * `orientation` - Whether `image` is appended on the right side or at the bottom, represented by the `Orientation` enum
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn append(
+        &mut self,
+        image: StaticThumbnail,
+        orientation: Orientation,
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(AppendOp::new(image, orientation)));
+        self
+    }
+
+    /// Representation of the drop-shadow operation
+    ///
+    /// This function adds `ShadowOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `ShadowOp` should be applied
+    /// * `dx` - The horizontal offset of the shadow, positive values move it to the right
+    /// * `dy` - The vertical offset of the shadow, positive values move it down
+    /// * `blur` - The sigma of the gaussian blur, that softens the edges of the shadow
+    /// * `color` - The RGBA color of the shadow
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn drop_shadow(
+        &mut self,
+        dx: i32,
+        dy: i32,
+        blur: f32,
+        color: [u8; 4],
+    ) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(ShadowOp::new(dx, dy, blur, color)));
+        self
+    }
+
     /// Representation of the rotate operation
     ///
     /// This function adds `RotateOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
@@ -591,4 +1675,42 @@ where
         self.add_op(Box::new(RotateOp::new(rotation)));
         self
     }
+
+    /// Representation of the rotate operation with a fill for the exposed corners
+    ///
+    /// This function adds `RotateOp` with the given fill to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `RotateOp` should be applied
+    /// * `rotation` -  Options for the operation represented by the `Rotation` enum
+    /// * `fill` - How the corners exposed by `Rotation::Degrees` are filled, represented by the `RotationFill` enum
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn rotate_fill(&mut self, rotation: Rotation, fill: RotationFill) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(RotateOp::new_with_fill(rotation, fill)));
+        self
+    }
+
+    /// Representation of the distortion-correction operation
+    ///
+    /// This function adds `DistortionOp` to the queue of a `GenericThumbnail` represented by `&mut self`.
+    /// It returns itself after that.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - The object on which `DistortionOp` should be applied
+    /// * `k1` - The coefficient of the second power of the radius, negative values correct barrel distortion
+    /// * `k2` - The coefficient of the fourth power of the radius
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic
+    fn distortion(&mut self, k1: f32, k2: f32) -> &mut dyn GenericThumbnail {
+        self.add_op(Box::new(DistortionOp::new(k1, k2)));
+        self
+    }
 }
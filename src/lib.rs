@@ -7,8 +7,12 @@ mod tests {
 }
 
 pub use crate::generic::GenericThumbnail;
-pub use crate::generic::{BoxPosition, Crop, Exif, Orientation, ResampleFilter, Resize, Rotation};
+pub use crate::generic::{
+    BoxPosition, Channel, Crop, Exif, Fit, Gravity, Orientation, ResampleFilter, Resize, Rotation,
+    RotationFill,
+};
 pub use crate::target::Target;
+pub use crate::thumbnail::quick_thumbnail;
 pub use crate::thumbnail::StaticThumbnail;
 pub use crate::thumbnail::Thumbnail;
 pub use crate::thumbnail::ThumbnailCollection;
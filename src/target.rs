@@ -1,10 +1,59 @@
-use crate::errors::{FileError, FileNotSupportedError};
+use crate::errors::{FileError, FileNotSupportedError, PartialStoreError};
 use crate::thumbnail::data::ThumbnailData;
-use image::{DynamicImage, ImageFormat};
+use crate::thumbnail::AnimatedThumbnail;
+use image::codecs::jpeg::{JpegEncoder, PixelDensity};
+use image::codecs::png::{CompressionType, FilterType as PngFilterType, PngEncoder};
+use image::codecs::tiff::TiffEncoder;
+use image::imageops::FilterType;
+use image::{
+    ColorType, DynamicImage, GenericImageView, ImageEncoder, ImageError, ImageFormat,
+    ImageOutputFormat, ImageResult, Rgb, RgbImage,
+};
+use rayon::prelude::*;
 use std::ffi::OsStr;
-use std::fs::create_dir_all;
-use std::io;
+use std::fs::{create_dir_all, File};
+use std::io::{BufWriter, Seek, Write};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::{fmt, fs, io};
+
+/// The standard sRGB color profile, that is embedded if `Target::embed_srgb()` is set
+const SRGB_PROFILE: &[u8] = include_bytes!("../resources/icc/sRGB.icc");
+
+/// The JPEG quality used when writing to a writer, the same as the default of the JPEG encoder
+const JPEG_QUALITY: u8 = 75;
+
+/// The `OutputEncoder` trait. It encodes images in a file type that is not supported by this crate itself.
+///
+/// Implement it to store images e.g. as JPEG XL or in a proprietary format, and pass it with `TargetFormat::Custom`.
+/// The encoder is shared between the items of a `Target`, which may be stored in parallel.
+pub trait OutputEncoder: Send + Sync {
+    /// Gets the file extension of the file type, without the leading dot
+    ///
+    /// It is added to the destination path, if the path does not already have it.
+    fn extension(&self) -> &str;
+
+    /// Gets the MIME type of the file type, see `TargetFormat::mime_type()`
+    ///
+    /// Defaults to `application/octet-stream`.
+    fn mime_type(&self) -> &str {
+        "application/octet-stream"
+    }
+
+    /// Encodes the image and writes it to the writer
+    ///
+    /// The image is passed as it is, it may have an alpha channel or 16 bits per channel.
+    ///
+    /// * img: &DynamicImage - The image data
+    /// * w: &mut dyn Write - The writer the encoded image is written to
+    fn encode(&self, img: &DynamicImage, w: &mut dyn Write) -> Result<(), FileError>;
+}
+
+impl fmt::Debug for dyn OutputEncoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OutputEncoder {{ {:?} }}", self.extension())
+    }
+}
 
 /// The `TargetMethod` type. This sets the file type of the output file.
 #[derive(Debug)]
@@ -19,23 +68,312 @@ pub enum TargetFormat {
     Bmp,
     /// GIF file
     Gif,
+    /// ICO file
+    ///
+    /// Contains the list of sizes (in pixels) of the icons that are stored in the file.
+    /// Each size has to be between 1 and 256. The image is scaled down to fit into a square of that size.
+    /// If the list is empty, the image itself is stored as single icon,
+    /// scaled down to fit into 256x256 pixels if it is larger than that.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_ico/favicon.ico");
+    /// let target = Target::new(TargetFormat::Ico(vec![16, 32, 48]), dst);
+    /// let thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// let paths = thumb.store(&target).ok().unwrap();
+    /// let icon = image::open(&paths[0]).unwrap();
+    ///
+    /// assert_eq!(icon.width(), 48);
+    /// ```
+    Ico(Vec<u32>),
+    /// Jpeg file with a maximum file size
+    ///
+    /// Contains the maximum file size in bytes. The highest JPEG quality, at which the encoded image
+    /// still fits into that size, is determined with a binary search.
+    /// If the image does not fit even at the lowest quality, storing fails with `FileError::SizeLimitExceeded`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_jpeg_max_bytes");
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// let target = Target::new(TargetFormat::JpegMaxBytes(8 * 1024), dir.join("small.jpg"));
+    /// let path = thumb.store_keep(&target).ok().unwrap().remove(0);
+    /// assert!(std::fs::metadata(path).unwrap().len() <= 8 * 1024);
+    ///
+    /// let target = Target::new(TargetFormat::JpegMaxBytes(100), dir.join("tiny.jpg"));
+    /// assert!(thumb.store_keep(&target).is_err());
+    /// ```
+    JpegMaxBytes(usize),
+    /// The file type of the source image
+    ///
+    /// When storing, this is resolved to the format the image was loaded from, see `Thumbnail::source_format()`.
+    /// If the source format is unknown or can not be stored, e.g. for images created from a `DynamicImage`,
+    /// the image is stored as PNG file.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::ImageFormat;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_keep_original");
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// let png = dir.join("source.png");
+    /// image::open("resources/tests/test.jpg").unwrap().save(&png).unwrap();
+    /// let target = Target::new(TargetFormat::KeepOriginal, dir.join("out/"));
+    ///
+    /// let sources = vec![
+    ///     (Path::new("resources/tests/test.jpg").to_path_buf(), ImageFormat::Jpeg),
+    ///     (png, ImageFormat::Png),
+    /// ];
+    /// for (source, format) in sources {
+    ///     let thumb = Thumbnail::load(source).unwrap();
+    ///     let path = thumb.store(&target).ok().unwrap().remove(0);
+    ///     assert_eq!(image::guess_format(&std::fs::read(path).unwrap()).unwrap(), format);
+    /// }
+    /// ```
+    KeepOriginal,
+    /// File type of a custom encoder, see `OutputEncoder`
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::Write;
+    /// use std::path::Path;
+    /// use std::sync::Arc;
+    /// use image::DynamicImage;
+    /// use thumbnailer::errors::FileError;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::{OutputEncoder, TargetFormat};
+    /// use thumbnailer::{GenericThumbnail, Resize, Target, Thumbnail};
+    ///
+    /// /// Stores images as binary PPM
+    /// struct PpmEncoder;
+    ///
+    /// impl OutputEncoder for PpmEncoder {
+    ///     fn extension(&self) -> &str {
+    ///         "ppm"
+    ///     }
+    ///
+    ///     fn encode(&self, img: &DynamicImage, w: &mut dyn Write) -> Result<(), FileError> {
+    ///         let rgb = img.to_rgb8();
+    ///         write!(w, "P6\n{} {}\n255\n", rgb.width(), rgb.height())?;
+    ///         w.write_all(rgb.as_raw())?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_custom/image");
+    /// let target = Target::new(TargetFormat::Custom(Arc::new(PpmEncoder)), dst);
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::ExactBox(40, 30));
+    ///
+    /// let path = thumb.apply_store(&target).ok().unwrap().remove(0);
+    /// assert_eq!(path.extension().unwrap(), "ppm");
+    ///
+    /// let bytes = std::fs::read(path).unwrap();
+    /// assert!(bytes.starts_with(b"P6\n40 30\n255\n"));
+    /// assert_eq!(bytes.len(), "P6\n40 30\n255\n".len() + 40 * 30 * 3);
+    /// ```
+    Custom(Arc<dyn OutputEncoder>),
 }
+
+impl TargetFormat {
+    /// Determines the `TargetFormat` that matches the given file extension
+    ///
+    /// The comparison is case-insensitive. Returns `None` if the extension is not supported.
+    /// For `ico` files a single icon is stored, see `TargetFormat::Ico`.
+    ///
+    /// * ext: &str - The file extension, without the leading dot
+    pub fn from_extension(ext: &str) -> Option<TargetFormat> {
+        match ext.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(TargetFormat::Jpeg),
+            "png" => Some(TargetFormat::Png),
+            "tif" | "tiff" => Some(TargetFormat::Tiff),
+            "bmp" => Some(TargetFormat::Bmp),
+            "gif" => Some(TargetFormat::Gif),
+            "ico" => Some(TargetFormat::Ico(vec![])),
+            _ => None,
+        }
+    }
+
+    /// Resolves `TargetFormat::KeepOriginal` to the file type of the given source format
+    ///
+    /// Other file types are returned unchanged.
+    ///
+    /// * source: Option<ImageFormat> - The format the image was loaded from
+    pub(crate) fn resolve(self, source: Option<ImageFormat>) -> TargetFormat {
+        match self {
+            TargetFormat::KeepOriginal => TargetFormat::from_source(source),
+            format => format,
+        }
+    }
+
+    /// Gets the file type to store an image in the given source format with
+    ///
+    /// Falls back to PNG, if the source format is unknown or not supported as file type.
+    ///
+    /// * source: Option<ImageFormat> - The format the image was loaded from
+    fn from_source(source: Option<ImageFormat>) -> TargetFormat {
+        match source {
+            Some(ImageFormat::Jpeg) => TargetFormat::Jpeg,
+            Some(ImageFormat::Tiff) => TargetFormat::Tiff,
+            Some(ImageFormat::Bmp) => TargetFormat::Bmp,
+            Some(ImageFormat::Gif) => TargetFormat::Gif,
+            Some(ImageFormat::Ico) => TargetFormat::Ico(vec![]),
+            _ => TargetFormat::Png,
+        }
+    }
+
+    /// Gets the file extensions matching the file type
+    ///
+    /// The first extension is added to the destination path, if it has none of them.
+    fn extensions(&self) -> Vec<&str> {
+        match self {
+            TargetFormat::Jpeg => vec!["jpg", "jpeg"],
+            TargetFormat::Png | TargetFormat::KeepOriginal => vec!["png"],
+            TargetFormat::Tiff => vec!["tiff", "tif"],
+            TargetFormat::Bmp => vec!["bmp"],
+            TargetFormat::Gif => vec!["gif"],
+            TargetFormat::Ico(_) => vec!["ico"],
+            TargetFormat::JpegMaxBytes(_) => vec!["jpg", "jpeg"],
+            TargetFormat::Custom(encoder) => vec![encoder.extension()],
+        }
+    }
+
+    /// Checks whether the file type can store an alpha channel
+    ///
+    /// Custom encoders get the image as it is, so they decide themselves how to store the alpha channel.
+    fn has_alpha(&self) -> bool {
+        !matches!(
+            self,
+            TargetFormat::Jpeg | TargetFormat::JpegMaxBytes(_) | TargetFormat::Bmp
+        )
+    }
+
+    /// Checks whether the file type can store images with 16 bits per channel
+    fn supports_16_bit(&self) -> bool {
+        matches!(
+            self,
+            TargetFormat::Png
+                | TargetFormat::Tiff
+                | TargetFormat::KeepOriginal
+                | TargetFormat::Custom(_)
+        )
+    }
+
+    /// Gets the MIME type matching the file type, e.g. `image/png`
+    pub fn mime_type(&self) -> &str {
+        match self {
+            TargetFormat::Jpeg | TargetFormat::JpegMaxBytes(_) => "image/jpeg",
+            TargetFormat::Png | TargetFormat::KeepOriginal => "image/png",
+            TargetFormat::Tiff => "image/tiff",
+            TargetFormat::Bmp => "image/bmp",
+            TargetFormat::Gif => "image/gif",
+            TargetFormat::Ico(_) => "image/x-icon",
+            TargetFormat::Custom(encoder) => encoder.mime_type(),
+        }
+    }
+}
+
+/// The position of an image in a `ThumbnailCollection`, used to give the stored files distinct names
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct CollectionIndex {
+    /// The index of the image in the collection
+    pub(crate) index: u32,
+    /// Whether the source file of another image in the collection has the same file stem
+    pub(crate) stem_collision: bool,
+}
+
+/// The `PngCompression` type. This sets the compression level of PNG output files.
+#[derive(Debug, Copy, Clone)]
+pub enum PngCompression {
+    /// The default compression of the PNG encoder
+    Default,
+    /// Fast, minimal compression. Results in larger files.
+    Fast,
+    /// Best compression. Results in smaller files, but takes longer to encode.
+    Best,
+}
+
+/// The `OutputColor` type. This sets the color type the image is converted to, before it is stored.
+#[derive(Debug, Copy, Clone)]
+pub enum OutputColor {
+    /// 8-bit grayscale. Supported by JPEG, PNG, TIFF and BMP files.
+    Grayscale,
+    /// 8-bit grayscale with alpha channel. Supported by PNG files.
+    GrayscaleAlpha,
+}
+
+/// The `FileNaming` type. This sets how the file name is derived from the source file,
+/// for items that are stored into a folder, see `Target::naming()`.
+#[derive(Debug, Clone)]
+pub enum FileNaming {
+    /// The file stem of the source file. Within a collection the index of the image is added,
+    /// if another source file has the same file stem
+    Index,
+    /// The file stem of the source file, without an index
+    OriginalName,
+    /// The file stem of the source file, after the given prefix
+    Prefix(String),
+    /// The file stem of the source file, followed by the given suffix
+    Suffix(String),
+}
+
 /// The `TargetItem` type. This basically defines one single actual target.
 #[derive(Debug)]
 pub struct TargetItem {
     /// The file destination path
     path: PathBuf,
-    // flatten: bool,
+    /// The background color the image is composited over, if the target file type has no alpha channel
+    flatten: Option<[u8; 3]>,
     /// The file type of the target file
     method: TargetFormat,
+    /// The compression level, if the target file is a PNG file
+    png_compression: PngCompression,
+    /// Whether an already existing file at the destination path is overwritten
+    overwrite: bool,
+    /// The resolution in pixels per inch, that is written to the file, if the target file is a JPEG or PNG file
+    dpi: Option<u16>,
+    /// The template for the file name. If set, the path is the folder the file is saved to
+    name_template: Option<String>,
+    /// The color type the image is converted to before it is stored. If not set, the color type is kept
+    color: Option<OutputColor>,
 }
 /// The `Target` type. This defines a list of path and file type combinations, the given image will be stored to.
 #[derive(Debug)]
 pub struct Target {
     items: Vec<TargetItem>,
+    /// Whether the stored files carry no metadata of the source file, see `Target::strip_metadata()`
+    strip_metadata: bool,
+    /// Whether the sRGB color profile is embedded into JPEG and PNG files, see `Target::embed_srgb()`
+    embed_srgb: bool,
+    /// How the file names are derived from the source file, see `Target::naming()`
+    naming: FileNaming,
 }
 
 impl Target {
+    /// Constructs a `Target` without any entries and with the default settings
+    fn empty() -> Self {
+        Target {
+            items: vec![],
+            strip_metadata: true,
+            embed_srgb: false,
+            naming: FileNaming::Index,
+        }
+    }
+
     /// Constructs a new `Target with a first single entry.
     ///
     /// A single target or `TargetItem` is a tuple consisting of a file type/format and
@@ -59,11 +397,55 @@ impl Target {
     /// Target::new(TargetFormat::Jpeg, Path::new("image.jpg").to_path_buf());
     /// ```
     pub fn new(method: TargetFormat, dst: PathBuf) -> Self {
-        Target { items: vec![] }.add_target(method, dst)
+        Target::empty().add_target(method, dst)
+    }
+
+    /// Constructs a new `Target` with a first single entry, the file type is determined by the file extension of `dst`.
+    ///
+    /// The file extension is compared case-insensitive, see `TargetFormat::from_extension()` for the supported extensions.
+    ///
+    /// *  `dst: PathBuf` - The file path to save the file to.
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotSupported` if the file extension is missing or does not match a supported file type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use image::ImageFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_from_path");
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// let target = Target::from_path(dir.join("image.jpeg")).ok().unwrap();
+    /// let path = thumb.store_keep(&target).ok().unwrap().remove(0);
+    /// assert_eq!(image::guess_format(&std::fs::read(path).unwrap()).unwrap(), ImageFormat::Jpeg);
+    ///
+    /// let target = Target::from_path(dir.join("image.PNG")).ok().unwrap();
+    /// let path = thumb.store_keep(&target).ok().unwrap().remove(0);
+    /// assert_eq!(image::guess_format(&std::fs::read(path).unwrap()).unwrap(), ImageFormat::Png);
+    ///
+    /// assert!(Target::from_path(dir.join("image.xyz")).is_err());
+    /// ```
+    pub fn from_path(dst: PathBuf) -> Result<Self, FileError> {
+        let method = match dst.extension() {
+            Some(ext) => TargetFormat::from_extension(&ext.to_string_lossy()),
+            None => None,
+        };
+
+        match method {
+            Some(method) => Ok(Target::new(method, dst)),
+            None => Err(FileError::NotSupported(FileNotSupportedError::new(dst))),
+        }
     }
 
     /// Adds another actual target to the target set.
     ///
+    /// The new item can be configured by the setters called after it, e.g. `Target::dpi()` or `Target::color()`.
+    /// They only apply to the most recently added item and can be combined with each other.
+    ///
     /// Returns Self to allow method chaining.
     ///
     /// * `method: TargetMethod` - The target file type
@@ -79,44 +461,438 @@ impl Target {
     ///
     /// ```
     /// use std::path::Path;
-    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::target::{OutputColor, TargetFormat};
     /// use thumbnailer::Target;
-    /// Target::new(TargetFormat::Jpeg, Path::new("image.jpg").to_path_buf());
+    /// Target::new(TargetFormat::Jpeg, Path::new("image.jpg").to_path_buf())
+    ///     .add_target(TargetFormat::Png, Path::new("gray.png").to_path_buf())
+    ///     .color(OutputColor::Grayscale)
+    ///     .dpi(300);
     /// ```
     pub fn add_target(mut self, method: TargetFormat, dst: PathBuf) -> Self {
         self.items.push(TargetItem {
             path: dst,
-            // flatten: false,
+            flatten: None,
             method,
+            png_compression: PngCompression::Default,
+            overwrite: true,
+            dpi: None,
+            name_template: None,
+            color: None,
         });
 
         self
     }
 
-    // pub fn add_target_flatten(&mut self, method: TargetMethod, dst: PathBuf) -> &mut Self {
-    //     self.target.items.push(TargetItem {
-    //         path: dst,
-    //         flatten: true,
-    //         method,
-    //     });
-    //
-    //     self
-    // }
+    /// Sets the compression level of the most recently added item, if it is stored as PNG file
+    ///
+    /// For all other file types the compression level is ignored. The default is `PngCompression::Default`.
+    ///
+    /// Returns Self to allow method chaining.
+    ///
+    /// * `compression: PngCompression` - The compression level of the PNG file
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::target::{PngCompression, TargetFormat};
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_png_compression");
+    /// let fast = Target::new(TargetFormat::Png, dir.join("fast.png")).png_compression(PngCompression::Fast);
+    /// let best = Target::new(TargetFormat::Png, dir.join("best.png")).png_compression(PngCompression::Best);
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// let fast_path = thumb.store_keep(&fast).ok().unwrap().remove(0);
+    /// let best_path = thumb.store_keep(&best).ok().unwrap().remove(0);
+    ///
+    /// let fast_size = std::fs::metadata(fast_path).unwrap().len();
+    /// let best_size = std::fs::metadata(best_path).unwrap().len();
+    /// assert!(best_size <= fast_size);
+    /// ```
+    pub fn png_compression(mut self, compression: PngCompression) -> Self {
+        if let Some(item) = self.items.last_mut() {
+            item.png_compression = compression;
+        }
+        self
+    }
+
+    /// Sets whether an already existing file is overwritten by the most recently added item
+    ///
+    /// If set to `false` and a file already exists at the computed destination path,
+    /// the image is not stored for this item. The path of the existing file is still part of the returned paths
+    /// when storing. This allows to skip images that have already been processed in previous runs.
+    /// By default existing files are overwritten.
+    ///
+    /// Returns Self to allow method chaining.
+    ///
+    /// * `overwrite: bool` - Whether an existing file is overwritten
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use std::time::Duration;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_no_overwrite/image.png");
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// let path = thumb.store_keep(&Target::new(TargetFormat::Png, dst.clone())).ok().unwrap().remove(0);
+    /// let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+    /// std::thread::sleep(Duration::from_millis(20));
+    ///
+    /// let target = Target::new(TargetFormat::Png, dst).overwrite(false);
+    /// let paths = thumb.store_keep(&target).ok().unwrap();
+    ///
+    /// assert_eq!(paths, vec![path.clone()]);
+    /// assert_eq!(std::fs::metadata(&path).unwrap().modified().unwrap(), modified);
+    /// ```
+    pub fn overwrite(mut self, overwrite: bool) -> Self {
+        if let Some(item) = self.items.last_mut() {
+            item.overwrite = overwrite;
+        }
+        self
+    }
+
+    /// Sets the resolution, that is written to the file of the most recently added item
+    ///
+    /// The resolution is written to the JFIF density fields of JPEG files and to the pHYs chunk of PNG files.
+    /// For all other file types the resolution is ignored.
+    ///
+    /// Returns Self to allow method chaining.
+    ///
+    /// * `dpi: u16` - The resolution in pixels per inch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_dpi");
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// let target = Target::new(TargetFormat::Jpeg, dir.join("image.jpg"))
+    ///     .dpi(300)
+    ///     .add_target(TargetFormat::Png, dir.join("image.png"))
+    ///     .dpi(300);
+    /// let paths = thumb.store_keep(&target).ok().unwrap();
+    ///
+    /// // The JFIF APP0 segment directly follows the start of image marker
+    /// let bytes = std::fs::read(&paths[0]).unwrap();
+    /// assert_eq!(&bytes[2..4], &[0xFF, 0xE0]);
+    /// assert_eq!(&bytes[6..11], b"JFIF\0");
+    /// // density unit: dots per inch
+    /// assert_eq!(bytes[13], 1);
+    /// assert_eq!(u16::from_be_bytes([bytes[14], bytes[15]]), 300);
+    /// assert_eq!(u16::from_be_bytes([bytes[16], bytes[17]]), 300);
+    ///
+    /// let bytes = std::fs::read(&paths[1]).unwrap();
+    /// let index = bytes.windows(4).position(|chunk| chunk == b"pHYs").unwrap();
+    /// let pixels_per_meter = u32::from_be_bytes([bytes[index + 4], bytes[index + 5], bytes[index + 6], bytes[index + 7]]);
+    /// // 300 pixels per inch are 11811 pixels per meter
+    /// assert_eq!(pixels_per_meter, 11811);
+    /// // unit: meter
+    /// assert_eq!(bytes[index + 12], 1);
+    /// ```
+    pub fn dpi(mut self, dpi: u16) -> Self {
+        if let Some(item) = self.items.last_mut() {
+            item.dpi = Some(dpi);
+        }
+        self
+    }
+
+    /// Sets a template for the file name of the most recently added item
+    ///
+    /// The path of the item is the folder the file is saved to then, which is created if it does not exist yet.
+    /// The file name is generated from the template for every stored image, by replacing these placeholders:
+    /// * `{stem}` - The file name of the source image, without the extension
+    /// * `{width}` - The width of the stored image
+    /// * `{height}` - The height of the stored image
+    /// * `{format}` - The file extension of the target file type, e.g. `jpg`
+    /// * `{index}` - The index of the image when storing a `ThumbnailCollection`, otherwise `0`
+    ///
+    /// The placeholders are replaced in a single pass, so a source file named e.g. `{index}.jpg` keeps its name.
+    /// Storing fails with an `IoError` of kind `InvalidInput`, if the generated file name contains a path separator
+    /// or is `..`.
+    /// The matching file extension is added to the generated file name, if it has none of them.
+    /// When storing a `ThumbnailCollection` and the template contains no `{index}`,
+    /// the file names are made distinct like for other targets, see `ThumbnailCollection`.
+    ///
+    /// Returns Self to allow method chaining.
+    ///
+    /// * `template: &str` - The template for the file name
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{GenericThumbnail, Resize, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_templated");
+    /// let target = Target::new(TargetFormat::Png, dir.clone()).name_template("{stem}_thumb_{width}x{height}");
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::ExactBox(100, 50));
+    /// let paths = thumb.apply_store(&target).ok().unwrap();
+    ///
+    /// assert_eq!(paths, vec![dir.join("test_thumb_100x50.png")]);
+    /// assert!(paths[0].is_file());
+    /// ```
+    pub fn name_template(mut self, template: &str) -> Self {
+        if let Some(item) = self.items.last_mut() {
+            item.name_template = Some(template.to_string());
+        }
+        self
+    }
+
+    /// Sets the color type the image is converted to, before it is stored by the most recently added item
+    ///
+    /// Storing grayscale images as grayscale results in smaller files than storing them as RGB.
+    /// If the file type does not support the color type, storing fails with a `FileError::NotSupported`,
+    /// see `OutputColor` for the supported file types. By default the color type of the image is kept.
+    ///
+    /// Returns Self to allow method chaining.
+    ///
+    /// * `color: OutputColor` - The color type of the stored image
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::Path;
+    /// use image::ColorType;
+    /// use thumbnailer::target::{OutputColor, TargetFormat};
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_output_color");
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// let target = Target::new(TargetFormat::Png, dir.join("color.png"))
+    ///     .add_target(TargetFormat::Png, dir.join("gray.png"))
+    ///     .color(OutputColor::Grayscale)
+    ///     .add_target(TargetFormat::Png, dir.join("gray_alpha.png"))
+    ///     .color(OutputColor::GrayscaleAlpha);
+    /// let paths = thumb.store_keep(&target).ok().unwrap();
+    ///
+    /// assert_eq!(image::open(&paths[0]).unwrap().color(), ColorType::Rgb8);
+    /// assert_eq!(image::open(&paths[1]).unwrap().color(), ColorType::L8);
+    /// assert_eq!(image::open(&paths[2]).unwrap().color(), ColorType::La8);
+    ///
+    /// // GIF files can not be stored as grayscale with alpha channel
+    /// let target = Target::new(TargetFormat::Gif, dir.join("gray.gif")).color(OutputColor::GrayscaleAlpha);
+    /// assert!(thumb.store_keep(&target).is_err());
+    /// ```
+    pub fn color(mut self, color: OutputColor) -> Self {
+        if let Some(item) = self.items.last_mut() {
+            item.color = Some(color);
+        }
+        self
+    }
+
+    /// Sets the background color the image of the most recently added item is composited over
+    ///
+    /// If the file type has no alpha channel (JPEG and BMP), images with an alpha channel are composited
+    /// over the background color before they are stored. Otherwise transparent areas would have
+    /// an arbitrary color, usually black. For all other file types the background is ignored.
+    ///
+    /// Returns Self to allow method chaining.
+    ///
+    /// * `background: [u8; 3]` - The RGB background color
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// // A transparent image with an opaque red square in the center
+    /// let src = std::env::temp_dir().join("thumbnailer_doc_flatten/transparent.png");
+    /// std::fs::create_dir_all(src.parent().unwrap()).unwrap();
+    /// let transparent = RgbaImage::from_fn(40, 40, |x, y| {
+    ///     if (10..30).contains(&x) && (10..30).contains(&y) {
+    ///         Rgba([255, 0, 0, 255])
+    ///     } else {
+    ///         Rgba([0, 0, 0, 0])
+    ///     }
+    /// });
+    /// DynamicImage::ImageRgba8(transparent).save(&src).unwrap();
+    ///
+    /// let thumb = Thumbnail::load(src.clone()).unwrap();
+    /// let target = Target::new(TargetFormat::Jpeg, src.with_extension("jpg")).flatten([255, 255, 255]);
+    /// let paths = thumb.store(&target).ok().unwrap();
+    ///
+    /// let flattened = image::open(&paths[0]).unwrap();
+    /// let corner = flattened.get_pixel(2, 2);
+    /// let center = flattened.get_pixel(20, 20);
+    /// assert!(corner[0] > 245 && corner[1] > 245 && corner[2] > 245);
+    /// assert!(center[0] > 230 && center[1] < 25 && center[2] < 25);
+    /// ```
+    pub fn flatten(mut self, background: [u8; 3]) -> Self {
+        if let Some(item) = self.items.last_mut() {
+            item.flatten = Some(background);
+        }
+        self
+    }
+
+    /// Sets whether the metadata of the source file is stripped from the stored files
+    ///
+    /// By default the metadata is stripped: the stored files carry no EXIF, XMP or ICC metadata,
+    /// regardless of the metadata of the source file. This is important for privacy-sensitive uploads,
+    /// where e.g. GPS coordinates must not be published.
+    /// The encoders never write the metadata of the source file on their own, so stripping requires no extra work.
+    ///
+    /// If set to `false`, the EXIF metadata of the source file is copied to JPEG files.
+    /// It is copied unchanged, including the orientation, so it does not match images that have been rotated.
+    /// Other file types and XMP or ICC metadata are not supported, and are still stored without metadata.
+    /// For images decoded from encoded data, e.g. with `Thumbnail::from_bytes()`, the metadata of that data is copied.
+    /// Images created from a `DynamicImage` have no metadata to copy.
+    ///
+    /// * strip: bool - Whether the metadata is stripped
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_strip_metadata");
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/exif.jpg").to_path_buf()).unwrap();
+    /// let source_gps = thumb.read_exif().unwrap().gps;
+    /// assert!(source_gps.is_some());
+    ///
+    /// // by default the stored file carries no metadata
+    /// let target = Target::new(TargetFormat::Jpeg, dir.join("stripped.jpg"));
+    /// let path = thumb.store_keep(&target).ok().unwrap().remove(0);
+    /// let stored = Thumbnail::load(path).unwrap();
+    /// assert_eq!(stored.read_exif().unwrap(), Default::default());
+    ///
+    /// // the EXIF metadata of the source file can be kept
+    /// let target = Target::new(TargetFormat::Jpeg, dir.join("kept.jpg")).strip_metadata(false);
+    /// let path = thumb.store_keep(&target).ok().unwrap().remove(0);
+    /// let stored = Thumbnail::load(path).unwrap();
+    /// assert_eq!(stored.read_exif().unwrap().gps, source_gps);
+    /// ```
+    pub fn strip_metadata(mut self, strip: bool) -> Self {
+        self.strip_metadata = strip;
+        self
+    }
+
+    /// Sets whether the standard sRGB color profile is embedded into the stored files
+    ///
+    /// Some viewers render images without a color profile with wrong colors.
+    /// The embedded profile tags the image as sRGB, which is the color space the images are processed in.
+    /// JPEG files get the profile as APP2 segment, PNG files as iCCP chunk.
+    /// Other file types, and JPEG files with a maximum file size, are stored without a profile.
+    /// The profile is embedded regardless of `strip_metadata()`, it is no metadata of the source file.
+    ///
+    /// * embed: bool - Whether the profile is embedded
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::PathBuf;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{GenericThumbnail, Target, Thumbnail};
+    /// use image::DynamicImage;
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_embed_srgb");
+    /// let mut thumb = Thumbnail::from_dynamic_image("image.png", DynamicImage::new_rgb8(10, 10));
+    /// let contains = |path: PathBuf, pattern: &[u8]| {
+    ///     std::fs::read(path).unwrap().windows(pattern.len()).any(|window| window == pattern)
+    /// };
+    ///
+    /// let target = Target::new(TargetFormat::Png, dir.join("untagged.png"));
+    /// let path = thumb.store_keep(&target).ok().unwrap().remove(0);
+    /// assert!(!contains(path, b"iCCP"));
+    ///
+    /// let target = Target::new(TargetFormat::Png, dir.join("tagged.png"))
+    ///     .add_target(TargetFormat::Jpeg, dir.join("tagged.jpg"))
+    ///     .embed_srgb(true);
+    /// let paths = thumb.store_keep(&target).ok().unwrap();
+    /// assert!(contains(paths[0].clone(), b"iCCP"));
+    /// assert!(contains(paths[1].clone(), b"ICC_PROFILE"));
+    /// // the files can still be decoded
+    /// assert!(image::open(&paths[0]).is_ok());
+    /// assert!(image::open(&paths[1]).is_ok());
+    /// ```
+    pub fn embed_srgb(mut self, embed: bool) -> Self {
+        self.embed_srgb = embed;
+        self
+    }
+
+    /// Sets how the file names are derived from the source file
+    ///
+    /// This applies to items that are stored into a folder and take the file name from the source file.
+    /// Items with a file path or a file name template are not affected.
+    /// With `FileNaming::Index`, the default, images of a collection get their index added,
+    /// if the source files of several images have the same file stem, see `Target::store()`.
+    /// The other options keep the names stable across runs, regardless of the other images in the collection.
+    /// Images whose source files have the same file stem get the same file name then.
+    /// Storing fails with an `IoError` of kind `InvalidInput`, if a prefix or suffix contains a path separator.
+    ///
+    /// * naming: FileNaming - How the file names are derived
+    ///
+    /// # Examples
+    /// ```
+    /// use std::fs;
+    /// use thumbnailer::target::{FileNaming, TargetFormat};
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// use thumbnailer::{GenericThumbnail, Target};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_naming");
+    /// fs::create_dir_all(&dir).unwrap();
+    /// fs::copy("resources/tests/test.jpg", dir.join("original.jpg")).unwrap();
+    /// fs::copy("resources/tests/exif.jpg", dir.join("other.jpg")).unwrap();
+    ///
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// assert!(builder.add_path(dir.join("original.jpg").to_str().unwrap()).is_ok());
+    /// assert!(builder.add_path(dir.join("other.jpg").to_str().unwrap()).is_ok());
+    /// let collection = builder.finalize();
+    ///
+    /// let target = Target::new(TargetFormat::Jpeg, dir.join("out/"))
+    ///     .naming(FileNaming::Suffix("_thumb".to_string()));
+    /// let paths = collection.apply_store(&target).ok().unwrap();
+    ///
+    /// assert_eq!(paths, vec![dir.join("out/original_thumb.jpg"), dir.join("out/other_thumb.jpg")]);
+    /// ```
+    pub fn naming(mut self, naming: FileNaming) -> Self {
+        self.naming = naming;
+        self
+    }
 
     /// Stores the given image to the configured targets
     ///
     /// This takes the image data and saves it to the given path
     /// and type for all configures targets in this `Target` instance.
     ///
-    /// This can be based a `u32` number, which will be added to the end of the file name, before the extension.
+    /// If the image is part of a collection, the file names are made distinct:
+    /// * if the file name is taken from the source file, the index is only added, if the file stem
+    ///   of another image in the collection is the same
+    /// * otherwise the file stem of the source file is added, followed by the index,
+    ///   if the file stem of another image in the collection is the same
+    ///
+    /// The suffixes are added to the end of the file name, before the extension.
+    ///
+    /// The image data is decoded once, then the items are encoded in parallel.
+    /// The returned paths are in the order of the items.
+    /// Images with 16 bits per channel keep their bit depth in PNG and TIFF files,
+    /// for other file types they are reduced to 8 bits per channel.
+    /// Unless `strip_metadata` is disabled, the stored files carry no metadata.
+    ///
+    /// If an item can not be stored, the other items are stored anyway. With a single item its error is returned,
+    /// otherwise a `FileError::PartialStore` reports the stored paths along with the error of each failed item.
     ///
     /// * thumb: &mut ThumbnailData - The image data
-    /// * count: Option<u32> - If not None, the given number will be added to the end of the file name, before the extension.
+    /// * collection: Option<CollectionIndex> - The position of the image, if it is part of a collection
     ///
     pub(crate) fn store(
         &self,
         thumb: &mut ThumbnailData,
-        count: Option<u32>,
+        collection: Option<CollectionIndex>,
     ) -> Result<Vec<PathBuf>, FileError> {
         let orig_path = thumb.get_path();
         // let filename = match orig_path.file_stem() {
@@ -124,40 +900,325 @@ impl Target {
         //     Some(name) => name.clone(),
         // };
 
-        let mut result = vec![];
+        // The destination paths, and whether the file has to be stored at all
+        let mut paths = vec![];
+
+        // The dimensions are only needed for file name templates, the header is enough for that
+        let dimensions = if self.items.iter().any(|item| item.name_template.is_some()) {
+            Some(thumb.dimensions_without_decode()?)
+        } else {
+            None
+        };
+
+        let stem = orig_path
+            .file_stem()
+            .unwrap_or_else(|| OsStr::new("NAME_MISSING"))
+            .to_string_lossy()
+            .to_string();
+
+        // The file type used for items that keep the original file type
+        let original_method = TargetFormat::from_source(thumb.source_format());
 
         for item in &self.items {
-            let mut path = compute_and_create_path(&item.path, &orig_path)?;
-
-            if let Some(count) = count {
-                let filename = format!(
-                    "{}-{}.{}",
-                    path.file_stem()
-                        .unwrap_or_else(|| OsStr::new("NAME_MISSING"))
-                        .to_string_lossy(),
-                    count,
-                    path.extension()
-                        .unwrap_or_else(|| OsStr::new(""))
-                        .to_string_lossy()
-                );
-                path.set_file_name(filename);
+            let method = match item.method {
+                TargetFormat::KeepOriginal => &original_method,
+                _ => &item.method,
+            };
+
+            let path = item_path(
+                item,
+                method,
+                &orig_path,
+                &stem,
+                dimensions,
+                collection,
+                &self.naming,
+            );
+            let path = path.map(|path| {
+                let skip = !item.overwrite && path.exists();
+                (path, skip)
+            });
+            paths.push((item, method, path));
+        }
+
+        let encode = |path: &Result<(PathBuf, bool), FileError>| matches!(path, Ok((_, false)));
+        if !paths.iter().any(|(_, _, path)| encode(path)) {
+            let results = paths
+                .into_iter()
+                .map(|(item, _, path)| {
+                    path.map(|(path, _)| path)
+                        .map_err(|e| (item.path.clone(), e))
+                })
+                .collect();
+            return self.combine_results(results);
+        }
+
+        // The EXIF metadata is only read, if it is copied to any JPEG file
+        let source_exif = if !self.strip_metadata
+            && paths
+                .iter()
+                .any(|(_, method, path)| encode(path) && matches!(method, TargetFormat::Jpeg))
+        {
+            thumb.raw_exif()
+        } else {
+            None
+        };
+
+        // Decode the image data once, before it is encoded for each item
+        let dyn_image: &DynamicImage = thumb.get_dyn_image()?;
+
+        // The items are encoded in parallel, collecting keeps the order of the items
+        let results = paths
+            .into_par_iter()
+            .map(
+                |(item, method, path)| -> Result<PathBuf, (PathBuf, FileError)> {
+                    match path {
+                        Ok((path, false)) => self
+                            .store_item(item, method, path, dyn_image, source_exif.as_deref())
+                            .map_err(|e| (item.path.clone(), e)),
+                        Ok((path, true)) => Ok(path),
+                        Err(e) => Err((item.path.clone(), e)),
+                    }
+                },
+            )
+            .collect();
+        self.combine_results(results)
+    }
+
+    /// Encodes the image for a single item and stores it at the given path
+    ///
+    /// Returns the path of the stored file.
+    ///
+    /// * item: &TargetItem - The item
+    /// * method: &TargetFormat - The file type, with the original file type already resolved
+    /// * path: PathBuf - The destination path
+    /// * dyn_image: &DynamicImage - The decoded image data
+    /// * source_exif: Option<&[u8]> - The EXIF metadata of the source file, that is copied to JPEG files
+    fn store_item(
+        &self,
+        item: &TargetItem,
+        method: &TargetFormat,
+        path: PathBuf,
+        dyn_image: &DynamicImage,
+        source_exif: Option<&[u8]>,
+    ) -> Result<PathBuf, FileError> {
+        let flattened;
+        let dyn_image = match item.flatten {
+            Some(background) if !method.has_alpha() && dyn_image.color().has_alpha() => {
+                flattened = flatten(dyn_image, background);
+                &flattened
+            }
+            _ => dyn_image,
+        };
+
+        let converted;
+        let dyn_image = match item.color {
+            Some(color) => {
+                converted = convert_color(dyn_image, color, method, &path)?;
+                &converted
             }
+            None => dyn_image,
+        };
 
-            let dyn_image = thumb.get_dyn_image()?;
+        let reduced;
+        let dyn_image = match reduce_to_8_bit(dyn_image, method) {
+            Some(image) => {
+                reduced = image;
+                &reduced
+            }
+            None => dyn_image,
+        };
 
-            let new_path = match item.method {
-                TargetFormat::Jpeg => store_jpg(dyn_image, path)?,
-                TargetFormat::Png => store_png(dyn_image, path)?,
-                TargetFormat::Tiff => store_tiff(dyn_image, path)?,
-                TargetFormat::Bmp => store_bmp(dyn_image, path)?,
-                TargetFormat::Gif => store_gif(dyn_image, path)?,
-            };
+        let new_path = match method {
+            TargetFormat::Jpeg => store_jpg(dyn_image, path, item.dpi)?,
+            // the original file type is resolved above, PNG is the fallback
+            TargetFormat::Png | TargetFormat::KeepOriginal => match item.dpi {
+                Some(dpi) => store_png_dpi(dyn_image, path, item.png_compression, dpi)?,
+                None => store_png(dyn_image, path, item.png_compression)?,
+            },
+            TargetFormat::Tiff => store_tiff(dyn_image, path)?,
+            TargetFormat::Bmp => store_bmp(dyn_image, path)?,
+            TargetFormat::Gif => store_gif(dyn_image, path)?,
+            TargetFormat::Ico(sizes) => store_ico(dyn_image, sizes, path)?,
+            TargetFormat::JpegMaxBytes(max_bytes) => {
+                store_jpg_max_bytes(dyn_image, *max_bytes, path)?
+            }
+            TargetFormat::Custom(encoder) => store_custom(dyn_image, encoder.as_ref(), path)?,
+        };
+
+        // The profile is inserted first, so the EXIF segment ends up directly after the JFIF header
+        if self.embed_srgb {
+            embed_srgb_profile(&new_path, method)?;
+        }
+        if let (TargetFormat::Jpeg, Some(exif)) = (method, source_exif) {
+            insert_jpeg_segment(&new_path, 0xE1, b"Exif\0\0", exif)?;
+        }
+
+        Ok(new_path)
+    }
+
+    /// Stores the frames of an animation to all items of the `Target`
+    ///
+    /// GIF items, and items keeping the original file type, are stored as animated GIF, see `AnimatedThumbnail::store()`.
+    /// File types that can not hold an animation get the first frame, stored like a single image.
+    /// The paths are computed as with `store()`, without a collection.
+    ///
+    /// * animated: &AnimatedThumbnail - The animation, with its operations already applied
+    pub(crate) fn store_animated(
+        &self,
+        animated: &AnimatedThumbnail,
+    ) -> Result<Vec<PathBuf>, FileError> {
+        let orig_path = animated.get_path();
+        let stem = orig_path
+            .file_stem()
+            .unwrap_or_else(|| OsStr::new("NAME_MISSING"))
+            .to_string_lossy()
+            .to_string();
+        let dimensions = Some(animated.dimensions());
+        let first_frame = animated.first_frame();
+        let gif = TargetFormat::Gif;
+
+        let results = self
+            .items
+            .iter()
+            .map(|item| -> Result<PathBuf, (PathBuf, FileError)> {
+                let method = match item.method {
+                    TargetFormat::KeepOriginal => &gif,
+                    _ => &item.method,
+                };
+                let path = item_path(
+                    item,
+                    method,
+                    &orig_path,
+                    &stem,
+                    dimensions,
+                    None,
+                    &self.naming,
+                )
+                .map_err(|e| (item.path.clone(), e))?;
+                if !item.overwrite && path.exists() {
+                    return Ok(path);
+                }
+
+                let stored = match (method, &first_frame) {
+                    (TargetFormat::Gif, _) => animated.store(path),
+                    (_, Some(frame)) => self.store_item(item, method, path, frame, None),
+                    (_, None) => Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+                };
+                stored.map_err(|e| (item.path.clone(), e))
+            })
+            .collect();
+        self.combine_results(results)
+    }
+
+    /// Combines the results of storing each item
+    ///
+    /// Returns the paths of all stored files. If an item could not be stored, a `Target` with a single item
+    /// returns the error of the item, otherwise a `FileError::PartialStore` with the paths of the stored files is returned.
+    ///
+    /// * results: Vec<Result<PathBuf, (PathBuf, FileError)>> - The stored file or the error of each item, with its destination path
+    fn combine_results(
+        &self,
+        results: Vec<Result<PathBuf, (PathBuf, FileError)>>,
+    ) -> Result<Vec<PathBuf>, FileError> {
+        let mut paths = vec![];
+        let mut errors = vec![];
+        for result in results {
+            match result {
+                Ok(path) => paths.push(path),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(paths)
+        } else if self.items.len() == 1 {
+            Err(errors.remove(0).1)
+        } else {
+            Err(FileError::PartialStore(PartialStoreError::new(
+                paths, errors,
+            )))
+        }
+    }
+}
+
+/// Computes the destination path of a single item, and ensures that its folder exists
+///
+/// If the image is part of a collection, a suffix is added to the file name, see `Target::store()`.
+/// The extension is made to match the file type.
+///
+/// * item: &TargetItem - The item
+/// * method: &TargetFormat - The file type, with the original file type already resolved
+/// * orig_path: &PathBuf - The original path of the source image file
+/// * stem: &str - The file stem of the source image file
+/// * dimensions: Option<(u32, u32)> - The dimensions of the image, if the item has a file name template
+/// * collection: Option<CollectionIndex> - The position of the image, if it is part of a collection
+/// * naming: &FileNaming - How the file name is derived from the source file, if the item is a folder
+fn item_path(
+    item: &TargetItem,
+    method: &TargetFormat,
+    orig_path: &PathBuf,
+    stem: &str,
+    dimensions: Option<(u32, u32)>,
+    collection: Option<CollectionIndex>,
+    naming: &FileNaming,
+) -> Result<PathBuf, FileError> {
+    let (mut path, named_by_stem) = match (&item.name_template, dimensions) {
+        (Some(template), Some(dimensions)) => {
+            create_dir_all(&item.path)?;
+            let name = fill_name_template(
+                template,
+                orig_path,
+                dimensions,
+                method,
+                collection.map(|collection| collection.index),
+            );
+            check_file_name(&name)?;
+            (item.path.join(name), template.contains("{stem}"))
+        }
+        _ => compute_and_create_path(&item.path, orig_path)?,
+    };
 
-            result.push(new_path);
+    // Apart from the default, the naming replaces the index for file names taken from the source file
+    if named_by_stem && item.name_template.is_none() {
+        let name = match naming {
+            FileNaming::Index => None,
+            FileNaming::OriginalName => Some(stem.to_string()),
+            FileNaming::Prefix(prefix) => Some(format!("{}{}", prefix, stem)),
+            FileNaming::Suffix(suffix) => Some(format!("{}{}", stem, suffix)),
+        };
+        if let Some(name) = name {
+            check_file_name(&name)?;
+            path.set_file_name(name);
+            return Ok(with_matching_ext(path, method));
         }
+    }
 
-        Ok(result)
+    let suffix = match (collection, &item.name_template) {
+        (None, _) => None,
+        (Some(_), Some(template)) if template.contains("{index}") => None,
+        (Some(collection), _) => match (named_by_stem, collection.stem_collision) {
+            (true, false) => None,
+            (true, true) => Some(collection.index.to_string()),
+            (false, false) => Some(stem.to_string()),
+            (false, true) => Some(format!("{}-{}", stem, collection.index)),
+        },
+    };
+    if let Some(suffix) = suffix {
+        let filename = format!(
+            "{}-{}.{}",
+            path.file_stem()
+                .unwrap_or_else(|| OsStr::new("NAME_MISSING"))
+                .to_string_lossy(),
+            suffix,
+            path.extension()
+                .unwrap_or_else(|| OsStr::new(""))
+                .to_string_lossy()
+        );
+        path.set_file_name(filename);
     }
+
+    Ok(with_matching_ext(path, method))
 }
 
 /// Computes the target file path and ensures that the parent folder exists.
@@ -172,9 +1233,11 @@ impl Target {
 ///   * if dst end with / or \ -> dst is a folder, create that folder and save file in folder with the old filename
 ///   * else -> dst is a path to a filename, save to dst directly
 ///
+/// Returns the path, and whether the file name is taken from the original file path.
+///
 /// * dst: &PathBuf - The destination path
 /// * src: &PathBuf - The original path of the source image file
-fn compute_and_create_path(dst: &PathBuf, src: &PathBuf) -> Result<PathBuf, io::Error> {
+fn compute_and_create_path(dst: &PathBuf, src: &PathBuf) -> Result<(PathBuf, bool), io::Error> {
     let filename = match src.file_stem() {
         None => OsStr::new("NAME_MISSING"),
         Some(name) => name,
@@ -182,13 +1245,13 @@ fn compute_and_create_path(dst: &PathBuf, src: &PathBuf) -> Result<PathBuf, io::
 
     if dst.is_dir() {
         // dst is dir and exists
-        return Ok(dst.join(Path::new(filename)));
+        return Ok((dst.join(Path::new(filename)), true));
     }
 
     if let Some(dst_str) = dst.to_str() {
         if dst_str.ends_with('/') || dst_str.ends_with('\\') {
             create_dir_all(dst)?;
-            return Ok(dst.join(Path::new(filename)));
+            return Ok((dst.join(Path::new(filename)), true));
         }
     }
 
@@ -196,7 +1259,91 @@ fn compute_and_create_path(dst: &PathBuf, src: &PathBuf) -> Result<PathBuf, io::
         create_dir_all(parent)?;
     }
 
-    Ok(dst.clone())
+    Ok((dst.clone(), false))
+}
+
+/// Generates a file name from the given template, see `Target::name_template()`
+///
+/// * template: &str - The template for the file name
+/// * src: &Path - The original path of the source image file
+/// * dimensions: (u32, u32) - The dimensions of the stored image
+/// * method: &TargetFormat - The target file type
+/// * count: Option<u32> - The index of the image, if it is part of a collection
+fn fill_name_template(
+    template: &str,
+    src: &Path,
+    dimensions: (u32, u32),
+    method: &TargetFormat,
+    count: Option<u32>,
+) -> String {
+    let stem = match src.file_stem() {
+        None => OsStr::new("NAME_MISSING"),
+        Some(name) => name,
+    };
+
+    // The placeholders are replaced in a single pass, so the substituted values are never parsed again
+    let mut name = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let placeholder = rest.find('}').map(|end| (&rest[1..end], end));
+        let value = match placeholder {
+            Some(("stem", _)) => Some(stem.to_string_lossy().into_owned()),
+            Some(("width", _)) => Some(dimensions.0.to_string()),
+            Some(("height", _)) => Some(dimensions.1.to_string()),
+            Some(("format", _)) => Some(method.extensions()[0].to_string()),
+            Some(("index", _)) => Some(count.unwrap_or(0).to_string()),
+            _ => None,
+        };
+        match (value, placeholder) {
+            (Some(value), Some((_, end))) => {
+                name.push_str(&value);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                name.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    name.push_str(rest);
+    name
+}
+
+/// Checks that a generated file name is a plain file name, that stays inside the destination folder
+///
+/// Returns an `IoError` of kind `InvalidInput`, if the name is empty, `.` or `..`, or contains a path separator.
+///
+/// * name: &str - The generated file name
+fn check_file_name(name: &str) -> Result<(), FileError> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return Err(FileError::IoError(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "The generated file name {:?} is not a plain file name",
+                name
+            ),
+        )));
+    }
+    Ok(())
+}
+
+/// Ensures that the extension of the path matches the file type
+///
+/// If the extension of the path is not one of the extensions of the file type, the matching one is added.
+///
+/// * dst: PathBuf - The destination path
+/// * method: &TargetFormat - The target file type
+fn with_matching_ext(mut dst: PathBuf, method: &TargetFormat) -> PathBuf {
+    let extensions = method.extensions();
+    if !extensions
+        .iter()
+        .any(|ext| ensure_ext(dst.extension(), ext))
+    {
+        dst.set_extension(OsStr::new(extensions[0]));
+    }
+    dst
 }
 
 /// Check if ext matches the expected extension
@@ -210,39 +1357,392 @@ fn ensure_ext(ext: Option<&OsStr>, expected: &str) -> bool {
     }
 }
 
+/// Composites `DynamicImage` over the given background color
+///
+/// * image: &DynamicImage - The image data
+/// * background: [u8; 3] - The RGB background color
+fn flatten(image: &DynamicImage, background: [u8; 3]) -> DynamicImage {
+    let rgba = image.to_rgba8();
+    let flattened = RgbImage::from_fn(rgba.width(), rgba.height(), |x, y| {
+        let pixel = rgba.get_pixel(x, y);
+        let alpha = pixel[3] as u32;
+        let mut channels = [0u8; 3];
+        for (channel, value) in channels.iter_mut().enumerate() {
+            *value = ((pixel[channel] as u32 * alpha + background[channel] as u32 * (255 - alpha))
+                / 255) as u8;
+        }
+        Rgb(channels)
+    });
+
+    DynamicImage::ImageRgb8(flattened)
+}
+
+/// Converts `DynamicImage` to a color type the TIFF encoder supports
+///
+/// Returns `None` if the image can be stored as it is. Images in BGR order are converted to RGB,
+/// grayscale images with alpha channel to RGBA, keeping their bit depth.
+///
+/// * image: &DynamicImage - The image data
+fn tiff_compatible(image: &DynamicImage) -> Option<DynamicImage> {
+    match image {
+        DynamicImage::ImageBgr8(_) => Some(DynamicImage::ImageRgb8(image.to_rgb8())),
+        DynamicImage::ImageBgra8(_) | DynamicImage::ImageLumaA8(_) => {
+            Some(DynamicImage::ImageRgba8(image.to_rgba8()))
+        }
+        DynamicImage::ImageLumaA16(_) => Some(DynamicImage::ImageRgba16(image.to_rgba16())),
+        _ => None,
+    }
+}
+
+/// Converts `DynamicImage` with 16 bits per channel to 8 bits per channel, if the file type requires it
+///
+/// Returns `None` if the image can be stored as it is.
+///
+/// * image: &DynamicImage - The image data
+/// * method: &TargetFormat - The target file type
+fn reduce_to_8_bit(image: &DynamicImage, method: &TargetFormat) -> Option<DynamicImage> {
+    if method.supports_16_bit() {
+        return None;
+    }
+
+    match image {
+        DynamicImage::ImageLuma16(_) => Some(DynamicImage::ImageLuma8(image.to_luma8())),
+        DynamicImage::ImageLumaA16(_) => Some(DynamicImage::ImageLumaA8(image.to_luma_alpha8())),
+        DynamicImage::ImageRgb16(_) => Some(DynamicImage::ImageRgb8(image.to_rgb8())),
+        DynamicImage::ImageRgba16(_) => Some(DynamicImage::ImageRgba8(image.to_rgba8())),
+        _ => None,
+    }
+}
+
+/// Converts `DynamicImage` to the given color type
+///
+/// * image: &DynamicImage - The image data
+/// * color: OutputColor - The color type to convert to
+/// * method: &TargetFormat - The target file type, which has to support the color type
+/// * dst: &Path - The destination path, used for error reporting
+fn convert_color(
+    image: &DynamicImage,
+    color: OutputColor,
+    method: &TargetFormat,
+    dst: &Path,
+) -> Result<DynamicImage, FileError> {
+    let supported = match color {
+        OutputColor::Grayscale => matches!(
+            method,
+            TargetFormat::Jpeg
+                | TargetFormat::JpegMaxBytes(_)
+                | TargetFormat::Png
+                | TargetFormat::Tiff
+                | TargetFormat::Bmp
+                | TargetFormat::Custom(_)
+        ),
+        OutputColor::GrayscaleAlpha => {
+            matches!(method, TargetFormat::Png | TargetFormat::Custom(_))
+        }
+    };
+    if !supported {
+        return Err(FileError::NotSupported(FileNotSupportedError::new(
+            dst.to_path_buf(),
+        )));
+    }
+
+    Ok(match color {
+        OutputColor::Grayscale => DynamicImage::ImageLuma8(image.to_luma8()),
+        OutputColor::GrayscaleAlpha => DynamicImage::ImageLumaA8(image.to_luma_alpha8()),
+    })
+}
+
 /// Stores `DynamicImage` as JPEG to the given path.
 ///
-/// Returns the actual path the file has been saved to. (Path might be extended by the correct file extension.
+/// Returns the path the file has been saved to.
 ///
 /// * image: &DynamicImage - The image data
 /// * dst: PathBuf - The destination path
-fn store_jpg(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileError> {
-    if !ensure_ext(dst.extension(), "jpg") && !ensure_ext(dst.extension(), "jpeg") {
-        dst.set_extension(OsStr::new("jpg"));
-    }
+/// * dpi: Option<u16> - The resolution in pixels per inch, that is written to the JFIF density fields
+fn store_jpg(image: &DynamicImage, dst: PathBuf, dpi: Option<u16>) -> Result<PathBuf, FileError> {
+    let dpi = match dpi {
+        Some(dpi) => dpi,
+        None => {
+            if image
+                .save_with_format(dst.clone(), ImageFormat::Jpeg)
+                .is_err()
+            {
+                return Err(FileError::NotSupported(FileNotSupportedError::new(dst)));
+            }
+            return Ok(dst);
+        }
+    };
 
-    if image
-        .save_with_format(dst.clone(), ImageFormat::Jpeg)
-        .is_err()
-    {
+    let mut writer = BufWriter::new(File::create(dst.clone())?);
+    let mut encoder = JpegEncoder::new(&mut writer);
+    encoder.set_pixel_density(PixelDensity::dpi(dpi));
+    if encoder.encode_image(image).is_err() {
         return Err(FileError::NotSupported(FileNotSupportedError::new(dst)));
     }
 
     Ok(dst)
 }
-/// Stores `DynamicImage` as PNG to the given path.
+
+/// Inserts a segment into the JPEG file at the given path
+///
+/// The segment is placed directly after the JFIF header, which has to stay the first segment.
+/// Segments inserted later are placed before segments inserted earlier.
+/// Data that does not fit into a single segment is dropped.
+///
+/// * path: &Path - The path of the JPEG file
+/// * marker: u8 - The marker of the segment, e.g. `0xE1` for APP1
+/// * identifier: &[u8] - The identifier at the start of the segment, e.g. `Exif\0\0`
+/// * data: &[u8] - The data of the segment after the identifier
+fn insert_jpeg_segment(
+    path: &Path,
+    marker: u8,
+    identifier: &[u8],
+    data: &[u8],
+) -> Result<(), FileError> {
+    let length = 2 + identifier.len() + data.len();
+    if length > u16::MAX as usize {
+        return Ok(());
+    }
+
+    let jpeg = fs::read(path)?;
+    // Skip the start of image marker, and the JFIF segment if there is one
+    let mut position = 2;
+    if jpeg.len() >= 6 && jpeg[2..4] == [0xFF, 0xE0] {
+        position += 2 + u16::from_be_bytes([jpeg[4], jpeg[5]]) as usize;
+    }
+    let position = position.min(jpeg.len());
+
+    let mut output = Vec::with_capacity(jpeg.len() + 2 + length);
+    output.extend_from_slice(&jpeg[..position]);
+    output.extend_from_slice(&[0xFF, marker]);
+    output.extend_from_slice(&(length as u16).to_be_bytes());
+    output.extend_from_slice(identifier);
+    output.extend_from_slice(data);
+    output.extend_from_slice(&jpeg[position..]);
+    fs::write(path, output)?;
+
+    Ok(())
+}
+
+/// Inserts a chunk into the PNG file at the given path
+///
+/// The chunk is placed directly after the IHDR chunk, which has to stay the first chunk.
+/// This is valid for chunks that have to appear before the PLTE and IDAT chunks, e.g. iCCP.
 ///
-/// Returns the actual path the file has been saved to. (Path might be extended by the correct file extension.
+/// * path: &Path - The path of the PNG file
+/// * chunk_type: &[u8; 4] - The type of the chunk
+/// * data: &[u8] - The data of the chunk
+fn insert_png_chunk(path: &Path, chunk_type: &[u8; 4], data: &[u8]) -> Result<(), FileError> {
+    // The signature, followed by length, type, 13 bytes of data and CRC of the IHDR chunk
+    const IHDR_END: usize = 8 + 4 + 4 + 13 + 4;
+
+    let png = fs::read(path)?;
+    let position = IHDR_END.min(png.len());
+
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(chunk_type);
+    crc.update(data);
+
+    let mut output = Vec::with_capacity(png.len() + 12 + data.len());
+    output.extend_from_slice(&png[..position]);
+    output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    output.extend_from_slice(chunk_type);
+    output.extend_from_slice(data);
+    output.extend_from_slice(&crc.finalize().to_be_bytes());
+    output.extend_from_slice(&png[position..]);
+    fs::write(path, output)?;
+
+    Ok(())
+}
+
+/// Embeds the sRGB color profile into the JPEG or PNG file at the given path
+///
+/// JPEG files get the profile as APP2 segment, PNG files as iCCP chunk. Other file types are left unchanged.
+///
+/// * path: &Path - The path of the file
+/// * method: &TargetFormat - The file type of the file
+fn embed_srgb_profile(path: &Path, method: &TargetFormat) -> Result<(), FileError> {
+    match method {
+        // The profile fits into a single segment: sequence number 1 of 1
+        TargetFormat::Jpeg => {
+            insert_jpeg_segment(path, 0xE2, b"ICC_PROFILE\0\x01\x01", SRGB_PROFILE)
+        }
+        TargetFormat::Png | TargetFormat::KeepOriginal => {
+            // The profile name, followed by the compression method 0 (zlib)
+            let mut data = b"sRGB\0\0".to_vec();
+            data.extend_from_slice(&deflate::deflate_bytes_zlib(SRGB_PROFILE));
+            insert_png_chunk(path, b"iCCP", &data)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Stores `DynamicImage` as JPEG with a maximum file size to the given path.
+///
+/// Binary searches the highest quality, at which the encoded image fits into `max_bytes`.
+/// The image is encoded into memory and only the final result is written to the file.
+///
+/// Returns the path the file has been saved to.
 ///
 /// * image: &DynamicImage - The image data
+/// * max_bytes: usize - The maximum file size in bytes
 /// * dst: PathBuf - The destination path
-fn store_png(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileError> {
-    if !ensure_ext(dst.extension(), "png") {
-        dst.set_extension(OsStr::new("png"));
+fn store_jpg_max_bytes(
+    image: &DynamicImage,
+    max_bytes: usize,
+    dst: PathBuf,
+) -> Result<PathBuf, FileError> {
+    let buffer = encode_jpg_max_bytes(image, max_bytes, &dst)?;
+    BufWriter::new(File::create(dst.clone())?).write_all(&buffer)?;
+    Ok(dst)
+}
+
+/// Encodes `DynamicImage` as JPEG with a maximum size into memory.
+///
+/// Binary searches the highest quality, at which the encoded image fits into `max_bytes`.
+///
+/// * image: &DynamicImage - The image data
+/// * max_bytes: usize - The maximum size in bytes
+/// * path: &Path - The path used for error reporting
+fn encode_jpg_max_bytes(
+    image: &DynamicImage,
+    max_bytes: usize,
+    path: &Path,
+) -> Result<Vec<u8>, FileError> {
+    let encode = |quality: u8| -> Result<Vec<u8>, FileError> {
+        let mut buffer = vec![];
+        match JpegEncoder::new_with_quality(&mut buffer, quality).encode_image(image) {
+            Ok(_) => Ok(buffer),
+            Err(_) => Err(FileError::NotSupported(FileNotSupportedError::new(
+                path.to_path_buf(),
+            ))),
+        }
+    };
+
+    let mut best = None;
+    let (mut low, mut high) = (1u8, 100u8);
+    while low <= high {
+        let quality = low + (high - low) / 2;
+        let buffer = encode(quality)?;
+
+        if buffer.len() <= max_bytes {
+            best = Some(buffer);
+            low = quality + 1;
+        } else if quality == 1 {
+            break;
+        } else {
+            high = quality - 1;
+        }
     }
 
-    if image
-        .save_with_format(dst.clone(), ImageFormat::Png)
+    best.ok_or(FileError::SizeLimitExceeded(max_bytes))
+}
+
+/// Writes `DynamicImage` in the given file type to a writer.
+///
+/// JPEG, PNG, BMP and GIF are encoded directly into the writer, TIFF uses the `Seek` bound of the writer.
+/// ICO files and JPEG files with a maximum size are encoded into memory first and then written at once.
+/// Images with 16 bits per channel are reduced to 8 bits per channel, unless the file type is PNG or TIFF.
+///
+/// * image: &DynamicImage - The image data
+/// * format: &TargetFormat - The file type
+/// * writer: &mut W - The writer the encoded image is written to
+/// * path: &Path - The path used for error reporting
+pub(crate) fn write_image<W: Write + Seek>(
+    image: &DynamicImage,
+    format: &TargetFormat,
+    writer: &mut W,
+    path: &Path,
+) -> Result<(), FileError> {
+    let not_supported = || FileError::NotSupported(FileNotSupportedError::new(path.to_path_buf()));
+
+    let reduced;
+    let image = match reduce_to_8_bit(image, format) {
+        Some(image) => {
+            reduced = image;
+            &reduced
+        }
+        None => image,
+    };
+
+    let result = match format {
+        TargetFormat::Jpeg => image.write_to(writer, ImageOutputFormat::Jpeg(JPEG_QUALITY)),
+        TargetFormat::Png | TargetFormat::KeepOriginal => match image {
+            // write_image() stores 16-bit samples in the byte order required by PNG
+            DynamicImage::ImageLuma16(_)
+            | DynamicImage::ImageLumaA16(_)
+            | DynamicImage::ImageRgb16(_)
+            | DynamicImage::ImageRgba16(_) => {
+                let (width, height) = image.dimensions();
+                PngEncoder::new(writer).write_image(image.as_bytes(), width, height, image.color())
+            }
+            _ => image.write_to(writer, ImageOutputFormat::Png),
+        },
+        TargetFormat::Bmp => image.write_to(writer, ImageOutputFormat::Bmp),
+        TargetFormat::Gif => image.write_to(writer, ImageOutputFormat::Gif),
+        TargetFormat::Tiff => {
+            let converted = tiff_compatible(image);
+            let image = converted.as_ref().unwrap_or(image);
+            let (width, height) = image.dimensions();
+            TiffEncoder::new(writer).encode(image.as_bytes(), width, height, image.color())
+        }
+        TargetFormat::Ico(sizes) => {
+            if sizes.iter().any(|size| *size == 0 || *size > 256) {
+                return Err(not_supported());
+            }
+            let bytes = encode_ico(&ico_icons(image, sizes)).map_err(|_| not_supported())?;
+            writer.write_all(&bytes)?;
+            Ok(())
+        }
+        TargetFormat::JpegMaxBytes(max_bytes) => {
+            let bytes = encode_jpg_max_bytes(image, *max_bytes, path)?;
+            writer.write_all(&bytes)?;
+            Ok(())
+        }
+        TargetFormat::Custom(encoder) => {
+            encoder.encode(image, writer)?;
+            Ok(())
+        }
+    };
+
+    match result {
+        Ok(_) => Ok(()),
+        Err(ImageError::IoError(error)) => Err(FileError::IoError(error)),
+        Err(_) => Err(not_supported()),
+    }
+}
+
+/// Stores `DynamicImage` as PNG to the given path.
+///
+/// Returns the path the file has been saved to.
+///
+/// * image: &DynamicImage - The image data
+/// * dst: PathBuf - The destination path
+/// * compression: PngCompression - The compression level
+fn store_png(
+    image: &DynamicImage,
+    dst: PathBuf,
+    compression: PngCompression,
+) -> Result<PathBuf, FileError> {
+    let (compression_type, filter_type) = match compression {
+        PngCompression::Default => {
+            if image
+                .save_with_format(dst.clone(), ImageFormat::Png)
+                .is_err()
+            {
+                return Err(FileError::NotSupported(FileNotSupportedError::new(dst)));
+            }
+            return Ok(dst);
+        }
+        PngCompression::Fast => (CompressionType::Fast, PngFilterType::NoFilter),
+        PngCompression::Best => (CompressionType::Best, PngFilterType::Paeth),
+    };
+
+    let writer = BufWriter::new(File::create(dst.clone())?);
+    let (width, height) = image.dimensions();
+    // write_image() stores 16-bit samples in the byte order required by PNG
+    if PngEncoder::new_with_quality(writer, compression_type, filter_type)
+        .write_image(image.as_bytes(), width, height, image.color())
         .is_err()
     {
         return Err(FileError::NotSupported(FileNotSupportedError::new(dst)));
@@ -251,17 +1751,75 @@ fn store_png(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileErro
     Ok(dst)
 }
 
-/// Stores `DynamicImage` as TIFF to the given path.
+/// Stores `DynamicImage` as PNG with the given resolution to the given path.
+///
+/// The image encoder does not support writing the resolution, therefore the PNG encoder is used directly.
+/// The resolution is converted to pixels per meter and written to the pHYs chunk.
+/// The image is stored with 8 bits per channel, as RGBA if it has an alpha channel, otherwise as RGB.
 ///
-/// Returns the actual path the file has been saved to. (Path might be extended by the correct file extension.
+/// Returns the path the file has been saved to.
 ///
 /// * image: &DynamicImage - The image data
 /// * dst: PathBuf - The destination path
-fn store_tiff(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileError> {
-    if !ensure_ext(dst.extension(), "tif") && !ensure_ext(dst.extension(), "tiff") {
-        dst.set_extension(OsStr::new("tiff"));
+/// * compression: PngCompression - The compression level
+/// * dpi: u16 - The resolution in pixels per inch
+fn store_png_dpi(
+    image: &DynamicImage,
+    dst: PathBuf,
+    compression: PngCompression,
+    dpi: u16,
+) -> Result<PathBuf, FileError> {
+    let (width, height) = image.dimensions();
+    let (color_type, data) = match image {
+        DynamicImage::ImageLuma8(gray) => (png::ColorType::Grayscale, gray.as_raw().clone()),
+        DynamicImage::ImageLumaA8(gray) => (png::ColorType::GrayscaleAlpha, gray.as_raw().clone()),
+        _ if image.color().has_alpha() => (png::ColorType::RGBA, image.to_rgba8().into_raw()),
+        _ => (png::ColorType::RGB, image.to_rgb8().into_raw()),
+    };
+
+    let writer = BufWriter::new(File::create(dst.clone())?);
+    let mut encoder = png::Encoder::new(writer, width, height);
+    encoder.set_color(color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    match compression {
+        PngCompression::Default => encoder.set_compression(png::Compression::Default),
+        PngCompression::Fast => {
+            encoder.set_compression(png::Compression::Fast);
+            encoder.set_filter(png::FilterType::NoFilter);
+        }
+        PngCompression::Best => {
+            encoder.set_compression(png::Compression::Best);
+            encoder.set_filter(png::FilterType::Paeth);
+        }
+    }
+
+    let pixels_per_meter = (dpi as f64 / 0.0254).round() as u32;
+    let mut phys = Vec::with_capacity(9);
+    phys.extend_from_slice(&pixels_per_meter.to_be_bytes());
+    phys.extend_from_slice(&pixels_per_meter.to_be_bytes());
+    phys.push(png::Unit::Meter as u8);
+
+    let result = encoder.write_header().and_then(|mut writer| {
+        writer.write_chunk(*b"pHYs", &phys)?;
+        writer.write_image_data(&data)
+    });
+    if result.is_err() {
+        return Err(FileError::NotSupported(FileNotSupportedError::new(dst)));
     }
 
+    Ok(dst)
+}
+
+/// Stores `DynamicImage` as TIFF to the given path.
+///
+/// Color types the TIFF encoder does not support are converted first, see `tiff_compatible()`.
+/// Returns the path the file has been saved to.
+///
+/// * image: &DynamicImage - The image data
+/// * dst: PathBuf - The destination path
+fn store_tiff(image: &DynamicImage, dst: PathBuf) -> Result<PathBuf, FileError> {
+    let converted = tiff_compatible(image);
+    let image = converted.as_ref().unwrap_or(image);
     if image
         .save_with_format(dst.clone(), ImageFormat::Tiff)
         .is_err()
@@ -274,15 +1832,11 @@ fn store_tiff(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileErr
 
 /// Stores `DynamicImage` as BMP to the given path.
 ///
-/// Returns the actual path the file has been saved to. (Path might be extended by the correct file extension.
+/// Returns the path the file has been saved to.
 ///
 /// * image: &DynamicImage - The image data
 /// * dst: PathBuf - The destination path
-fn store_bmp(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileError> {
-    if !ensure_ext(dst.extension(), "bmp") {
-        dst.set_extension(OsStr::new("bmp"));
-    }
-
+fn store_bmp(image: &DynamicImage, dst: PathBuf) -> Result<PathBuf, FileError> {
     if image
         .save_with_format(dst.clone(), ImageFormat::Bmp)
         .is_err()
@@ -294,15 +1848,11 @@ fn store_bmp(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileErro
 }
 /// Stores `DynamicImage` as GIF to the given path.
 ///
-/// Returns the actual path the file has been saved to. (Path might be extended by the correct file extension.
+/// Returns the path the file has been saved to.
 ///
 /// * image: &DynamicImage - The image data
 /// * dst: PathBuf - The destination path
-fn store_gif(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileError> {
-    if !ensure_ext(dst.extension(), "gif") {
-        dst.set_extension(OsStr::new("gif"));
-    }
-
+fn store_gif(image: &DynamicImage, dst: PathBuf) -> Result<PathBuf, FileError> {
     if image
         .save_with_format(dst.clone(), ImageFormat::Gif)
         .is_err()
@@ -312,3 +1862,391 @@ fn store_gif(image: &DynamicImage, mut dst: PathBuf) -> Result<PathBuf, FileErro
 
     Ok(dst)
 }
+
+/// Stores `DynamicImage` as ICO to the given path.
+///
+/// For every given size a scaled down copy of the image is added to the file.
+/// If no sizes are given, the image itself is stored, scaled down to fit into 256x256 pixels if necessary.
+///
+/// Returns the path the file has been saved to.
+///
+/// * image: &DynamicImage - The image data
+/// * sizes: &[u32] - The sizes of the icons to store
+/// * dst: PathBuf - The destination path
+fn store_ico(image: &DynamicImage, sizes: &[u32], dst: PathBuf) -> Result<PathBuf, FileError> {
+    if sizes.iter().any(|size| *size == 0 || *size > 256) {
+        return Err(FileError::NotSupported(FileNotSupportedError::new(dst)));
+    }
+
+    let icons = ico_icons(image, sizes);
+
+    let bytes = match encode_ico(&icons) {
+        Ok(bytes) => bytes,
+        Err(_) => return Err(FileError::NotSupported(FileNotSupportedError::new(dst))),
+    };
+
+    fs::write(dst.clone(), bytes)?;
+
+    Ok(dst)
+}
+
+/// Stores `DynamicImage` with a custom encoder to the given path.
+///
+/// Returns the path the file has been saved to.
+///
+/// * image: &DynamicImage - The image data
+/// * encoder: &dyn OutputEncoder - The encoder of the file type
+/// * dst: PathBuf - The destination path
+fn store_custom(
+    image: &DynamicImage,
+    encoder: &dyn OutputEncoder,
+    dst: PathBuf,
+) -> Result<PathBuf, FileError> {
+    let mut writer = BufWriter::new(File::create(dst.clone())?);
+    encoder.encode(image, &mut writer)?;
+    writer.flush()?;
+    Ok(dst)
+}
+
+/// Scales the image to the given icon sizes.
+///
+/// If no sizes are given, the image itself is used, scaled down to fit into 256x256 pixels if necessary.
+///
+/// * image: &DynamicImage - The image data
+/// * sizes: &[u32] - The sizes of the icons
+fn ico_icons(image: &DynamicImage, sizes: &[u32]) -> Vec<DynamicImage> {
+    if sizes.is_empty() {
+        let (width, height) = image.dimensions();
+        if width > 256 || height > 256 {
+            vec![image.resize(256, 256, FilterType::Lanczos3)]
+        } else {
+            vec![image.clone()]
+        }
+    } else {
+        sizes
+            .iter()
+            .map(|size| image.resize(*size, *size, FilterType::Lanczos3))
+            .collect()
+    }
+}
+
+/// Encodes the given images as the entries of a single ICO file.
+///
+/// Every entry is stored as PNG, which is supported by all current ICO readers.
+///
+/// * icons: &[DynamicImage] - The icons, each between 1x1 and 256x256 pixels
+fn encode_ico(icons: &[DynamicImage]) -> ImageResult<Vec<u8>> {
+    let mut entries = vec![];
+    for icon in icons {
+        let rgba = icon.to_rgba8();
+        let mut data = vec![];
+        PngEncoder::new(&mut data).encode(&rgba, rgba.width(), rgba.height(), ColorType::Rgba8)?;
+        entries.push((rgba.width(), rgba.height(), data));
+    }
+
+    // ICONDIR: reserved, image type (1 = ICO), number of images
+    let mut bytes = vec![];
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&1u16.to_le_bytes());
+    bytes.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+
+    // ICONDIRENTRY for each image, the image data follows after all entries
+    let mut offset = 6 + 16 * entries.len() as u32;
+    for (width, height, data) in &entries {
+        // A size of 256 is stored as 0
+        bytes.push(if *width < 256 { *width as u8 } else { 0 });
+        bytes.push(if *height < 256 { *height as u8 } else { 0 });
+        // No palette, reserved
+        bytes.push(0);
+        bytes.push(0);
+        // Color planes, bits per pixel
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&32u16.to_le_bytes());
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&offset.to_le_bytes());
+        offset += data.len() as u32;
+    }
+
+    for (_, _, data) in &entries {
+        bytes.extend_from_slice(data);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fill_name_template, FileNaming, OutputColor, Target, TargetFormat};
+    use crate::errors::{ApplyError, FileError};
+    use crate::generic::GenericThumbnailOperations;
+    use crate::thumbnail::data::{ThumbnailData, DECODE_COUNT};
+    use crate::thumbnail::StaticThumbnail;
+    use crate::{BoxPosition, GenericThumbnail, Resize, Rotation, RotationFill, Thumbnail};
+    use image::{Bgra, ColorType, DynamicImage, GenericImageView, ImageBuffer, LumaA, Rgb};
+    use std::path::Path;
+
+    #[test]
+    fn store_multiple_formats_decodes_once() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_decode_once");
+        let target = Target::new(TargetFormat::Jpeg, dir.join("image.jpg"))
+            .add_target(TargetFormat::Png, dir.join("image.png"))
+            .add_target(TargetFormat::Bmp, dir.join("image.bmp"));
+
+        let mut data =
+            ThumbnailData::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+        DECODE_COUNT.with(|count| count.set(0));
+
+        let paths = target.store(&mut data, None).unwrap();
+
+        assert_eq!(paths.len(), 3);
+        assert_eq!(DECODE_COUNT.with(|count| count.get()), 1);
+    }
+
+    #[test]
+    fn store_copies_metadata_of_encoded_data() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_encoded_metadata");
+        let target = Target::new(TargetFormat::Jpeg, dir.join("image.jpg")).strip_metadata(false);
+
+        // The metadata comes from the data, even if the path names a file with metadata
+        let bytes = std::fs::read("resources/tests/test.jpg").unwrap();
+        let mut data = ThumbnailData::from_bytes("resources/tests/exif.jpg", &bytes).unwrap();
+        let path = target.store(&mut data, None).unwrap().remove(0);
+        let stored = Thumbnail::load(path).unwrap();
+        assert_eq!(stored.read_exif().unwrap(), Default::default());
+
+        let bytes = std::fs::read("resources/tests/exif.jpg").unwrap();
+        let mut data = ThumbnailData::from_bytes("resources/tests/test.jpg", &bytes).unwrap();
+        let path = target.store(&mut data, None).unwrap().remove(0);
+        let stored = Thumbnail::load(path).unwrap();
+        assert_eq!(stored.read_exif().unwrap().orientation, Some(6));
+    }
+
+    #[test]
+    fn apply_store_multiple_formats_decodes_once() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_apply_decode_once");
+        let target = Target::new(TargetFormat::Jpeg, dir.join("image.jpg"))
+            .add_target(TargetFormat::Png, dir.join("image.png"))
+            .add_target(TargetFormat::Gif, dir.clone())
+            .name_template("{stem}_{width}");
+
+        let mut thumb =
+            Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+        thumb.resize(Resize::Width(100)).blur(1.0);
+        DECODE_COUNT.with(|count| count.set(0));
+
+        // the operations decode the image, storing reuses the decoded image for every target
+        let paths = thumb.apply_store(&target).ok().unwrap();
+
+        assert_eq!(paths.len(), 3);
+        assert_eq!(DECODE_COUNT.with(|count| count.get()), 1);
+    }
+
+    #[test]
+    fn store_multiple_formats_keeps_order() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_store_order");
+        let _ = std::fs::remove_dir_all(&dir);
+        let target = Target::new(TargetFormat::Tiff, dir.join("image.tiff"))
+            .add_target(TargetFormat::Jpeg, dir.join("image.jpg"))
+            .add_target(TargetFormat::Gif, dir.join("image.gif"))
+            .add_target(TargetFormat::Png, dir.join("image.png"));
+
+        let mut data =
+            ThumbnailData::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+
+        for _ in 0..5 {
+            let paths = target.store(&mut data, None).unwrap();
+            assert_eq!(
+                paths,
+                vec![
+                    dir.join("image.tiff"),
+                    dir.join("image.jpg"),
+                    dir.join("image.gif"),
+                    dir.join("image.png"),
+                ]
+            );
+            assert!(paths.iter().all(|path| path.is_file()));
+        }
+    }
+
+    #[test]
+    fn store_16_bit_tiff_keeps_bit_depth() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_16_bit");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // The low bits of each sample are set, they are lost when reduced to 8 bits
+        let source = DynamicImage::ImageRgb16(ImageBuffer::from_fn(64, 32, |x, y| {
+            Rgb([x as u16 * 1000 + 1, y as u16 * 2000 + 3, 40_005])
+        }));
+        source.save(dir.join("source.tiff")).unwrap();
+
+        // An overlay, that is fully transparent, does not change the image
+        let overlay = StaticThumbnail::from_dynamic_image(DynamicImage::new_rgba8(16, 16));
+        let mut thumb = Thumbnail::load(dir.join("source.tiff")).unwrap();
+        thumb.combine(overlay, BoxPosition::TopLeft(8, 8));
+
+        let target = Target::new(TargetFormat::Tiff, dir.join("out/"))
+            .add_target(TargetFormat::Png, dir.join("out/"))
+            .add_target(TargetFormat::Jpeg, dir.join("out/"));
+        let paths = thumb.apply_store(&target).ok().unwrap();
+
+        for path in &paths[..2] {
+            let stored = image::open(path).unwrap();
+            assert_eq!(stored.color(), ColorType::Rgb16, "{}", path.display());
+            assert_eq!(stored.to_bytes(), source.to_bytes(), "{}", path.display());
+        }
+        assert_eq!(image::open(&paths[2]).unwrap().color(), ColorType::Rgb8);
+    }
+
+    #[test]
+    fn store_tiff_converts_unsupported_colors() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_tiff_colors");
+        let sources = vec![
+            (
+                DynamicImage::ImageBgra8(ImageBuffer::from_pixel(8, 4, Bgra([10, 20, 30, 40]))),
+                [30, 20, 10, 40],
+            ),
+            (
+                DynamicImage::ImageLumaA8(ImageBuffer::from_pixel(8, 4, LumaA([50, 60]))),
+                [50, 50, 50, 60],
+            ),
+        ];
+
+        for (index, (source, expected)) in sources.into_iter().enumerate() {
+            let path = dir.join(format!("image{}.tiff", index));
+            let thumb = Thumbnail::from_dynamic_image("image", source);
+            thumb
+                .store(&Target::new(TargetFormat::Tiff, path.clone()))
+                .ok()
+                .unwrap();
+
+            let stored = image::open(&path).unwrap();
+            assert_eq!(stored.color(), ColorType::Rgba8);
+            assert_eq!(stored.get_pixel(7, 3).0, expected);
+        }
+    }
+
+    #[test]
+    fn rotate_fill_color_is_kept_in_jpeg() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_rotate_fill_jpeg");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Without and with alpha channel, the rotated image is RGB or RGBA before it is stored
+        let source = image::open("resources/tests/test.jpg").unwrap();
+        source.save(dir.join("rgb.png")).unwrap();
+        DynamicImage::ImageRgba8(source.to_rgba8())
+            .save(dir.join("rgba.png"))
+            .unwrap();
+
+        for name in &["rgb.png", "rgba.png"] {
+            let mut thumb = Thumbnail::load(dir.join(name)).unwrap();
+            let white = RotationFill::Color([255, 255, 255, 255]);
+            thumb.rotate_fill(Rotation::Degrees(30.0), white);
+
+            let target = Target::new(TargetFormat::Jpeg, dir.join("out/"));
+            let paths = thumb.apply_store(&target).ok().unwrap();
+            let rotated = image::open(&paths[0]).unwrap();
+
+            let (width, height) = rotated.dimensions();
+            for &(x, y) in &[
+                (2, 2),
+                (width - 3, 2),
+                (2, height - 3),
+                (width - 3, height - 3),
+            ] {
+                let pixel = rotated.get_pixel(x, y);
+                assert!(
+                    pixel[0] > 245 && pixel[1] > 245 && pixel[2] > 245,
+                    "{}: corner ({}, {}) is {:?}",
+                    name,
+                    x,
+                    y,
+                    pixel
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn dimensions_before_store_decodes_once() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_dimensions_decode_once");
+        let target = Target::new(TargetFormat::Jpeg, dir.join("image.jpg"))
+            .add_target(TargetFormat::Png, dir.join("image.png"))
+            .add_target(TargetFormat::Bmp, dir.join("image.bmp"));
+
+        let mut thumb =
+            Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+        DECODE_COUNT.with(|count| count.set(0));
+
+        assert_eq!(thumb.dimensions_without_decode().unwrap(), (500, 138));
+        assert_eq!(DECODE_COUNT.with(|count| count.get()), 0);
+        assert_eq!(thumb.dimensions().unwrap(), (500, 138));
+        let paths = thumb.store_keep(&target).ok().unwrap();
+
+        assert_eq!(paths.len(), 3);
+        assert_eq!(DECODE_COUNT.with(|count| count.get()), 1);
+    }
+
+    #[test]
+    fn item_settings_are_combined() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_item_settings");
+        let _ = std::fs::remove_dir_all(&dir);
+        let target = Target::new(TargetFormat::Jpeg, dir.join("color.jpg"))
+            .add_target(TargetFormat::Png, dir.clone())
+            .name_template("{stem}_{width}")
+            .color(OutputColor::Grayscale)
+            .dpi(300)
+            .overwrite(false);
+
+        let mut thumb =
+            Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+        let paths = thumb.store_keep(&target).ok().unwrap();
+        assert_eq!(paths[1], dir.join("test_500.png"));
+        assert_eq!(image::open(&paths[0]).unwrap().color(), ColorType::Rgb8);
+        assert_eq!(image::open(&paths[1]).unwrap().color(), ColorType::L8);
+        let bytes = std::fs::read(&paths[1]).unwrap();
+        assert!(bytes.windows(4).any(|chunk| chunk == b"pHYs"));
+
+        // The existing file of the second item is kept, the first item is overwritten
+        let modified = |path: &Path| std::fs::metadata(path).unwrap().modified().unwrap();
+        let before = (modified(&paths[0]), modified(&paths[1]));
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert_eq!(thumb.store_keep(&target).ok().unwrap(), paths);
+        assert_ne!(modified(&paths[0]), before.0);
+        assert_eq!(modified(&paths[1]), before.1);
+    }
+
+    #[test]
+    fn name_template_does_not_substitute_values() {
+        let name = fill_name_template(
+            "{stem}_{width}{unknown}",
+            Path::new("images/{height}.jpg"),
+            (100, 50),
+            &TargetFormat::Png,
+            None,
+        );
+
+        assert_eq!(name, "{height}_100{unknown}");
+    }
+
+    #[test]
+    fn generated_names_stay_in_folder() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_names_stay_in_folder");
+        let thumb =
+            || Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+
+        let target = Target::new(TargetFormat::Png, dir.clone()).name_template("../{stem}");
+        assert!(matches!(
+            thumb().store(&target),
+            Err(ApplyError::StoreError(FileError::IoError(_)))
+        ));
+
+        let target = Target::new(TargetFormat::Png, dir.join("out/"))
+            .naming(FileNaming::Prefix("../".to_string()));
+        assert!(matches!(
+            thumb().store(&target),
+            Err(ApplyError::StoreError(FileError::IoError(_)))
+        ));
+        assert!(!std::env::temp_dir().join("test.png").exists());
+        assert!(!dir.join("test.png").exists());
+    }
+}
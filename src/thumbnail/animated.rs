@@ -0,0 +1,179 @@
+use crate::errors::{ApplyError, FileError, FileNotFoundError, FileNotSupportedError};
+use crate::generic::OperationContainer;
+use crate::thumbnail::operations::{Operation, ResizeOp};
+use crate::{ResampleFilter, Resize};
+use image::codecs::gif::{GifDecoder, GifEncoder, Repeat};
+use image::{AnimationDecoder, DynamicImage, Frame};
+use std::fmt;
+use std::fmt::Formatter;
+use std::fs::{create_dir_all, File};
+use std::io::{BufReader, BufWriter};
+use std::path::PathBuf;
+
+/// The `AnimatedThumbnail` type
+///
+/// Represents an animated GIF with all of its frames.
+/// Queued operations are applied to each frame on its own, the delays of the frames are preserved.
+pub struct AnimatedThumbnail {
+    /// Path from where the file was loaded
+    path: PathBuf,
+    /// The decoded frames, each holding the full canvas of the animation
+    frames: Vec<Frame>,
+    /// The queue of operations that will be applied to each frame
+    ops: Vec<Box<dyn Operation>>,
+}
+
+impl fmt::Debug for AnimatedThumbnail {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "AnimatedThumbnail: {} with {} frames, ops: {:?}",
+            self.path.display(),
+            self.frames.len(),
+            self.ops
+        )
+    }
+}
+
+impl OperationContainer for AnimatedThumbnail {
+    fn add_op(&mut self, op: Box<dyn Operation>) {
+        self.ops.push(op);
+    }
+}
+
+impl AnimatedThumbnail {
+    /// Creates a new `AnimatedThumbnail` from the animated GIF at the given file path
+    ///
+    /// All frames are decoded immediately.
+    ///
+    /// * path: PathBuf - The path to the GIF file
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotFound` if the file could not be found
+    /// Returns a `FileError::NotSupported` if the file is not a GIF file or could not be decoded
+    /// Returns a `FileError::IoError` if an error occurred while accessing the file
+    pub(crate) fn load(path: PathBuf) -> Result<AnimatedThumbnail, FileError> {
+        if !path.is_file() {
+            return Err(FileError::NotFound(FileNotFoundError { path }));
+        }
+
+        let decoder = match GifDecoder::new(BufReader::new(File::open(path.clone())?)) {
+            Ok(decoder) => decoder,
+            Err(_) => return Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+        };
+
+        let frames = match decoder.into_frames().collect_frames() {
+            Ok(frames) => frames,
+            Err(_) => return Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+        };
+
+        Ok(AnimatedThumbnail {
+            path,
+            frames,
+            ops: vec![],
+        })
+    }
+
+    /// Gets the path from which the animation was loaded
+    pub fn get_path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    /// Gets the number of frames of the animation
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Gets the dimensions of the animation as `(width, height)`
+    ///
+    /// Queued operations are not taken into account.
+    pub fn dimensions(&self) -> (u32, u32) {
+        match self.frames.first() {
+            Some(frame) => frame.buffer().dimensions(),
+            None => (0, 0),
+        }
+    }
+
+    /// Gets the first frame as `DynamicImage`, `None` if the animation has no frames
+    ///
+    /// Queued operations are not taken into account.
+    pub(crate) fn first_frame(&self) -> Option<DynamicImage> {
+        self.frames
+            .first()
+            .map(|frame| DynamicImage::ImageRgba8(frame.buffer().clone()))
+    }
+
+    /// Gets the number of operations that are queued and not applied yet
+    pub fn queued_ops(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Queues the resize operation, which is applied to each frame
+    ///
+    /// Returns itself to allow method chaining.
+    ///
+    /// * size: Resize - Options for the operation represented by the `Resize` enum
+    /// * filter: Option<ResampleFilter> - The filter used for resampling, see `ResizeOp`
+    pub fn resize(&mut self, size: Resize, filter: Option<ResampleFilter>) -> &mut Self {
+        self.add_op(Box::new(ResizeOp::new(size, filter)));
+        self
+    }
+
+    /// Applies the queued operations to each frame and clears the queue
+    ///
+    /// The position and the delay of each frame are kept.
+    ///
+    /// # Errors
+    /// Returns an `ApplyError::OperationError` if an operation fails on any of the frames.
+    /// In that case the frames are left untouched.
+    pub fn apply(&mut self) -> Result<&mut Self, ApplyError> {
+        let mut frames = Vec::with_capacity(self.frames.len());
+
+        for frame in &self.frames {
+            let mut image = DynamicImage::ImageRgba8(frame.buffer().clone());
+            for operation in &self.ops {
+                if let Err(error) = operation.apply(&mut image) {
+                    return Err(ApplyError::OperationError(error));
+                }
+            }
+            frames.push(Frame::from_parts(
+                image.to_rgba8(),
+                frame.left(),
+                frame.top(),
+                frame.delay(),
+            ));
+        }
+
+        self.frames = frames;
+        self.ops.clear();
+
+        Ok(self)
+    }
+
+    /// Stores the frames as animated GIF to the given path
+    ///
+    /// Queued operations are not applied, see `apply()`. The animation loops infinitely.
+    /// The parent folder of the path is created, if it does not exist yet.
+    ///
+    /// Returns the path the file has been saved to.
+    ///
+    /// * dst: PathBuf - The destination file path
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotSupported` if the frames could not be encoded
+    /// Returns a `FileError::IoError` if an error occurred while writing the file
+    pub fn store(&self, dst: PathBuf) -> Result<PathBuf, FileError> {
+        if let Some(parent) = dst.parent() {
+            create_dir_all(parent)?;
+        }
+
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(dst.clone())?));
+        if encoder.set_repeat(Repeat::Infinite).is_err()
+            || encoder.encode_frames(self.frames.clone()).is_err()
+        {
+            return Err(FileError::NotSupported(FileNotSupportedError::new(dst)));
+        }
+
+        Ok(dst)
+    }
+}
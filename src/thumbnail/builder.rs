@@ -0,0 +1,385 @@
+use crate::errors::{ApplyError, FileError};
+use crate::generic::GenericThumbnailOperations;
+use crate::{
+    BoxPosition, Channel, Crop, Exif, Fit, GenericThumbnail, Orientation, ResampleFilter, Resize,
+    Rotation, RotationFill, StaticThumbnail, Target, Thumbnail,
+};
+use image::DynamicImage;
+use std::path::PathBuf;
+
+/// The `ThumbnailBuilder` type. Allows to create a `Thumbnail` with queued operations.
+///
+/// Unlike the methods of `GenericThumbnailOperations`, which return a `&mut dyn GenericThumbnail`,
+/// the methods of the builder take and return the builder itself.
+/// This allows to chain the operations and still get the concrete `Thumbnail` type in the end.
+///
+/// The operations are only queued, not applied.
+#[derive(Debug)]
+pub struct ThumbnailBuilder {
+    /// The thumbnail being built
+    thumbnail: Thumbnail,
+}
+
+/// The `ThumbnailPipeline` type. An owned, fluent pipeline of operations on a `Thumbnail`.
+///
+/// This is the `ThumbnailBuilder`, used with one of its terminal methods, e.g. `store()`,
+/// instead of `build()`. See `Thumbnail::into_pipeline()`.
+pub type ThumbnailPipeline = ThumbnailBuilder;
+
+impl ThumbnailBuilder {
+    /// Creates a new `ThumbnailBuilder` from the image at the given path
+    ///
+    /// This internally calls `Thumbnail::load`.
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotFound` if the file could not be found
+    /// Can return a `FileError::NotSupported` if the file is of an unsupported type
+    /// Can return a `FileError::IoError` if an error occurred while accessing the file
+    pub fn from_path(path: PathBuf) -> Result<Self, FileError> {
+        Ok(ThumbnailBuilder {
+            thumbnail: Thumbnail::load(path)?,
+        })
+    }
+
+    /// Creates a new `ThumbnailBuilder` from an existing `DynamicImage`
+    ///
+    /// This internally calls `Thumbnail::from_dynamic_image`.
+    ///
+    /// * `path_name` - A custom path for the new `Thumbnail`
+    /// * `dynamic_image` - The `DynamicImage` that should be contained in the `Thumbnail`
+    pub fn from_dynamic_image(path_name: &str, dynamic_image: DynamicImage) -> Self {
+        ThumbnailBuilder {
+            thumbnail: Thumbnail::from_dynamic_image(path_name, dynamic_image),
+        }
+    }
+
+    /// Creates a new `ThumbnailBuilder` from an existing `Thumbnail`
+    ///
+    /// Operations already queued on the `Thumbnail` are kept.
+    pub fn from_thumbnail(thumbnail: Thumbnail) -> Self {
+        ThumbnailBuilder { thumbnail }
+    }
+
+    /// Queues the resize operation. See `GenericThumbnailOperations::resize()`
+    pub fn resize(mut self, size: Resize) -> Self {
+        self.thumbnail.resize(size);
+        self
+    }
+
+    /// Queues the resize operation with a custom filter. See `GenericThumbnailOperations::resize_filter()`
+    pub fn resize_filter(mut self, size: Resize, filter: ResampleFilter) -> Self {
+        self.thumbnail.resize_filter(size, filter);
+        self
+    }
+
+    /// Queues the blur operation. See `GenericThumbnailOperations::blur()`
+    pub fn blur(mut self, sigma: f32) -> Self {
+        self.thumbnail.blur(sigma);
+        self
+    }
+
+    /// Queues the blur operation for a region. See `GenericThumbnailOperations::blur_region()`
+    pub fn blur_region(mut self, x: u32, y: u32, width: u32, height: u32, sigma: f32) -> Self {
+        self.thumbnail.blur_region(x, y, width, height, sigma);
+        self
+    }
+
+    /// Queues the fast blur operation. See `GenericThumbnailOperations::fast_blur()`
+    pub fn fast_blur(mut self, radius: u32) -> Self {
+        self.thumbnail.fast_blur(radius);
+        self
+    }
+
+    /// Queues the brighten operation. See `GenericThumbnailOperations::brighten()`
+    pub fn brighten(mut self, value: i32) -> Self {
+        self.thumbnail.brighten(value);
+        self
+    }
+
+    /// Queues the hue rotate operation. See `GenericThumbnailOperations::huerotate()`
+    pub fn huerotate(mut self, degree: i32) -> Self {
+        self.thumbnail.huerotate(degree);
+        self
+    }
+
+    /// Queues the selective-desaturate operation. See `GenericThumbnailOperations::selective_desaturate()`
+    pub fn selective_desaturate(mut self, keep_hue: f32, range: f32) -> Self {
+        self.thumbnail.selective_desaturate(keep_hue, range);
+        self
+    }
+
+    /// Queues the tint operation. See `GenericThumbnailOperations::tint()`
+    pub fn tint(mut self, color: [u8; 3], strength: f32) -> Self {
+        self.thumbnail.tint(color, strength);
+        self
+    }
+
+    /// Queues the duotone operation. See `GenericThumbnailOperations::duotone()`
+    pub fn duotone(mut self, shadow: [u8; 3], highlight: [u8; 3]) -> Self {
+        self.thumbnail.duotone(shadow, highlight);
+        self
+    }
+
+    /// Queues the color-temperature operation. See `GenericThumbnailOperations::temperature()`
+    pub fn temperature(mut self, shift: i32) -> Self {
+        self.thumbnail.temperature(shift);
+        self
+    }
+
+    /// Queues the contrast operation. See `GenericThumbnailOperations::contrast()`
+    pub fn contrast(mut self, value: f32) -> Self {
+        self.thumbnail.contrast(value);
+        self
+    }
+
+    /// Queues the auto-contrast operation. See `GenericThumbnailOperations::auto_contrast()`
+    pub fn auto_contrast(mut self) -> Self {
+        self.thumbnail.auto_contrast();
+        self
+    }
+
+    /// Queues the auto-contrast operation with clipping. See `GenericThumbnailOperations::auto_contrast_clipped()`
+    pub fn auto_contrast_clipped(mut self, clip: f32) -> Self {
+        self.thumbnail.auto_contrast_clipped(clip);
+        self
+    }
+
+    /// Queues the levels operation. See `GenericThumbnailOperations::levels()`
+    pub fn levels(mut self, black: u8, white: u8, gamma: f32) -> Self {
+        self.thumbnail.levels(black, white, gamma);
+        self
+    }
+
+    /// Queues the levels operation for a single color channel. See `GenericThumbnailOperations::levels_channel()`
+    pub fn levels_channel(mut self, channel: Channel, black: u8, white: u8, gamma: f32) -> Self {
+        self.thumbnail.levels_channel(channel, black, white, gamma);
+        self
+    }
+
+    /// Queues the unsharpen operation. See `GenericThumbnailOperations::unsharpen()`
+    pub fn unsharpen(mut self, sigma: f32, threshold: i32) -> Self {
+        self.thumbnail.unsharpen(sigma, threshold);
+        self
+    }
+
+    /// Queues the median operation. See `GenericThumbnailOperations::median()`
+    pub fn median(mut self, radius: u32) -> Self {
+        self.thumbnail.median(radius);
+        self
+    }
+
+    /// Queues the quantize operation. See `GenericThumbnailOperations::quantize()`
+    pub fn quantize(mut self, max_colors: u16) -> Self {
+        self.thumbnail.quantize(max_colors);
+        self
+    }
+
+    /// Queues the opacity operation. See `GenericThumbnailOperations::opacity()`
+    pub fn opacity(mut self, factor: f32) -> Self {
+        self.thumbnail.opacity(factor);
+        self
+    }
+
+    /// Queues the crop operation. See `GenericThumbnailOperations::crop()`
+    pub fn crop(mut self, c: Crop) -> Self {
+        self.thumbnail.crop(c);
+        self
+    }
+
+    /// Queues the pad-to-ratio operation. See `GenericThumbnailOperations::pad_to_ratio()`
+    pub fn pad_to_ratio(mut self, ratio_width: f32, ratio_height: f32, fill: [u8; 4]) -> Self {
+        self.thumbnail.pad_to_ratio(ratio_width, ratio_height, fill);
+        self
+    }
+
+    /// Queues the face-crop operation. See `GenericThumbnailOperations::face_crop()`
+    #[cfg(feature = "face-detection")]
+    pub fn face_crop(mut self, model: PathBuf, padding: f32) -> Self {
+        self.thumbnail.face_crop(model, padding);
+        self
+    }
+
+    /// Queues the trim operation. See `GenericThumbnailOperations::trim()`
+    pub fn trim(mut self, tolerance: u8) -> Self {
+        self.thumbnail.trim(tolerance);
+        self
+    }
+
+    /// Queues the flip operation. See `GenericThumbnailOperations::flip()`
+    pub fn flip(mut self, orientation: Orientation) -> Self {
+        self.thumbnail.flip(orientation);
+        self
+    }
+
+    /// Queues the invert operation. See `GenericThumbnailOperations::invert()`
+    pub fn invert(mut self) -> Self {
+        self.thumbnail.invert();
+        self
+    }
+
+    /// Queues the invert operation for a region. See `GenericThumbnailOperations::invert_region()`
+    pub fn invert_region(mut self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        self.thumbnail.invert_region(x, y, width, height);
+        self
+    }
+
+    /// Queues the emboss operation. See `GenericThumbnailOperations::emboss()`
+    pub fn emboss(mut self) -> Self {
+        self.thumbnail.emboss();
+        self
+    }
+
+    /// Queues the edge-detection operation. See `GenericThumbnailOperations::detect_edges()`
+    pub fn detect_edges(mut self) -> Self {
+        self.thumbnail.detect_edges();
+        self
+    }
+
+    /// Queues the exif operation. See `GenericThumbnailOperations::exif()`
+    pub fn exif(mut self, metadata: Exif) -> Self {
+        self.thumbnail.exif(metadata);
+        self
+    }
+
+    /// Queues the draw-text operation. See `GenericThumbnailOperations::text()`
+    pub fn text(mut self, text: String, pos: BoxPosition) -> Self {
+        self.thumbnail.text(text, pos);
+        self
+    }
+
+    /// Queues the draw-text operation with a relative font height. See `GenericThumbnailOperations::text_relative()`
+    pub fn text_relative(mut self, text: String, pos: BoxPosition, fraction: f32) -> Self {
+        self.thumbnail.text_relative(text, pos, fraction);
+        self
+    }
+
+    /// Queues the caption operation. See `GenericThumbnailOperations::caption()`
+    pub fn caption(mut self, text: String) -> Self {
+        self.thumbnail.caption(text);
+        self
+    }
+
+    /// Queues the diagonal-watermark operation. See `GenericThumbnailOperations::diagonal_watermark()`
+    pub fn diagonal_watermark(mut self, text: String, opacity: f32, spacing: u32) -> Self {
+        self.thumbnail.diagonal_watermark(text, opacity, spacing);
+        self
+    }
+
+    /// Queues the smart-watermark operation. See `GenericThumbnailOperations::smart_watermark()`
+    pub fn smart_watermark(
+        mut self,
+        logo: StaticThumbnail,
+        corner_candidates: &[BoxPosition],
+    ) -> Self {
+        self.thumbnail.smart_watermark(logo, corner_candidates);
+        self
+    }
+
+    /// Queues the combine operation. See `GenericThumbnailOperations::combine()`
+    pub fn combine(mut self, image: StaticThumbnail, pos: BoxPosition) -> Self {
+        self.thumbnail.combine(image, pos);
+        self
+    }
+
+    /// Queues the combine operation with strict bounds. See `GenericThumbnailOperations::combine_strict_bounds()`
+    pub fn combine_strict_bounds(mut self, image: StaticThumbnail, pos: BoxPosition) -> Self {
+        self.thumbnail.combine_strict_bounds(image, pos);
+        self
+    }
+
+    /// Queues the combine-in-region operation. See `GenericThumbnailOperations::combine_in_region()`
+    pub fn combine_in_region(
+        mut self,
+        image: StaticThumbnail,
+        region: (u32, u32, u32, u32),
+        fit: Fit,
+    ) -> Self {
+        self.thumbnail.combine_in_region(image, region, fit);
+        self
+    }
+
+    /// Queues the append operation. See `GenericThumbnailOperations::append()`
+    pub fn append(mut self, image: StaticThumbnail, orientation: Orientation) -> Self {
+        self.thumbnail.append(image, orientation);
+        self
+    }
+
+    /// Queues the drop-shadow operation. See `GenericThumbnailOperations::drop_shadow()`
+    pub fn drop_shadow(mut self, dx: i32, dy: i32, blur: f32, color: [u8; 4]) -> Self {
+        self.thumbnail.drop_shadow(dx, dy, blur, color);
+        self
+    }
+
+    /// Queues the rotate operation. See `GenericThumbnailOperations::rotate()`
+    pub fn rotate(mut self, rotation: Rotation) -> Self {
+        self.thumbnail.rotate(rotation);
+        self
+    }
+
+    /// Queues the rotate operation with a fill for the exposed corners. See `GenericThumbnailOperations::rotate_fill()`
+    pub fn rotate_fill(mut self, rotation: Rotation, fill: RotationFill) -> Self {
+        self.thumbnail.rotate_fill(rotation, fill);
+        self
+    }
+
+    /// Queues the distortion-correction operation. See `GenericThumbnailOperations::distortion()`
+    pub fn distortion(mut self, k1: f32, k2: f32) -> Self {
+        self.thumbnail.distortion(k1, k2);
+        self
+    }
+
+    /// Consumes the `ThumbnailBuilder` and returns the constructed `Thumbnail`
+    ///
+    /// The queued operations are not applied yet.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::thumbnail::ThumbnailBuilder;
+    /// use thumbnailer::Resize;
+    ///
+    /// let mut thumb = ThumbnailBuilder::from_path(Path::new("resources/tests/test.jpg").to_path_buf())
+    ///     .unwrap()
+    ///     .resize(Resize::Width(100))
+    ///     .blur(2.0)
+    ///     .build();
+    ///
+    /// assert_eq!(thumb.queued_ops(), 2);
+    /// assert!(thumb.try_clone_and_load().is_ok());
+    /// ```
+    pub fn build(self) -> Thumbnail {
+        self.thumbnail
+    }
+
+    /// Consumes the `ThumbnailBuilder`, applies the queued operations and stores the result to the given `Target`
+    ///
+    /// See `GenericThumbnail::apply_store()`. Returns the paths the files have been saved to.
+    ///
+    /// # Errors
+    /// Returns an `ApplyError` if an operation fails or the image could not be stored
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{Resize, Target, Thumbnail};
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_pipeline/image.png");
+    /// let target = Target::new(TargetFormat::Png, dst);
+    ///
+    /// let paths = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf())
+    ///     .unwrap()
+    ///     .into_pipeline()
+    ///     .resize(Resize::Width(100))
+    ///     .blur(2.0)
+    ///     .store(&target)
+    ///     .ok()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(image::open(&paths[0]).unwrap().width(), 100);
+    /// ```
+    pub fn store(self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
+        self.thumbnail.apply_store(target)
+    }
+}
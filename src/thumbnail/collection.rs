@@ -1,10 +1,21 @@
 use crate::errors::{ApplyError, CollectionError, FileError};
 use crate::generic::OperationContainer;
+use crate::target::CollectionIndex;
 use crate::thumbnail::data::ThumbnailData;
 use crate::thumbnail::operations::Operation;
-use crate::{GenericThumbnail, Target, Thumbnail};
+use crate::{GenericThumbnail, ResampleFilter, Target, Thumbnail};
+use globwalk::{GlobWalker, GlobWalkerBuilder};
 use rayon::prelude::*;
-use std::path::{Path, PathBuf};
+#[cfg(feature = "serde")]
+use std::borrow::Cow;
+use std::collections::HashMap;
+#[cfg(feature = "serde")]
+use std::fs::{self, File, OpenOptions};
+#[cfg(feature = "serde")]
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+#[cfg(feature = "serde")]
+use std::sync::{Mutex, PoisonError};
 
 /// The `ThumbnailCollectionBuilder` type. Allows to create a `ThumbnailCollection`
 ///
@@ -22,6 +33,8 @@ impl ThumbnailCollectionBuilder {
             collection: ThumbnailCollection {
                 images: vec![],
                 ops: vec![],
+                auto_orient: false,
+                default_filter: None,
             },
         }
     }
@@ -36,9 +49,15 @@ impl ThumbnailCollectionBuilder {
     ///
     /// # Examples
     /// ```
+    /// use thumbnailer::errors::FileError;
     /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
     /// let mut builder = ThumbnailCollectionBuilder::new();
-    /// builder.add_path("path/to/file.jpg").is_ok();
+    /// assert!(builder.add_path("resources/tests/test.jpg").is_ok());
+    ///
+    /// match builder.add_path("path/to/missing.jpg") {
+    ///     Err(FileError::NotFound(error)) => assert!(error.path.ends_with("path/to/missing.jpg")),
+    ///     _ => panic!("a missing file has to result in FileError::NotFound"),
+    /// }
     /// ```
     pub fn add_path(&mut self, path: &str) -> Result<&mut Self, FileError> {
         let t = ThumbnailData::load(Path::new(path).to_path_buf())?;
@@ -49,6 +68,7 @@ impl ThumbnailCollectionBuilder {
     ///
     /// This uses the (globwalk)[https://docs.rs/globwalk/0.8.0/globwalk/] to parse the glob and find the files.
     /// See its documentation on how to construct globs.
+    /// Relative globs are matched in the current directory, absolute globs in the directory they start with.
     ///
     /// This internally calls the `ThumbnailData::load` method, and stores the result.
     ///
@@ -69,7 +89,7 @@ impl ThumbnailCollectionBuilder {
     /// builder.add_path("resources/tests/*.{png,jpg}").is_ok();
     /// ```
     pub fn add_glob(&mut self, glob: &str) -> Result<&mut Self, FileError> {
-        let files = globwalk::glob(glob)?;
+        let files = glob_walker(glob)?;
         let mut new_thumbs = vec![];
         for file in files {
             if let Ok(file) = file {
@@ -80,6 +100,117 @@ impl ThumbnailCollectionBuilder {
         Ok(self)
     }
 
+    /// Adds at most `max` images by (unix) glob to the collection
+    ///
+    /// Like `add_glob()`, but the matching files are found lazily and the search stops after `max` files.
+    /// This allows to e.g. preview a few images of a glob, that matches tens of thousands of files.
+    /// The files are added in the order globwalk finds them, see `add_glob_sampled()` for an even sample.
+    ///
+    /// * glob: &str - the glob to match files on the filesystem, see `add_glob()`
+    /// * max: usize - the maximum number of images to add
+    ///
+    /// # Attention
+    /// It stops parsing the found files on the first error loading a file
+    ///
+    /// # Errors
+    /// The same errors as `add_glob()`
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// builder.add_glob_limited("resources/tests/*.{png,jpg}", 2).is_ok();
+    /// ```
+    pub fn add_glob_limited(&mut self, glob: &str, max: usize) -> Result<&mut Self, FileError> {
+        let files = glob_walker(glob)?;
+        let mut new_thumbs = vec![];
+        for file in files.filter_map(Result::ok).take(max) {
+            new_thumbs.push(ThumbnailData::load(Path::new(file.path()).to_path_buf())?);
+        }
+        self.collection.images.append(new_thumbs.as_mut());
+        Ok(self)
+    }
+
+    /// Adds `count` images by (unix) glob to the collection, sampled evenly across all matching files
+    ///
+    /// The paths of all matching files are collected and sorted, but only the sampled files are loaded.
+    /// The sample starts with the first file and takes every n-th file, so that it spans the whole match set.
+    /// If the glob matches at most `count` files, all of them are added.
+    ///
+    /// * glob: &str - the glob to match files on the filesystem, see `add_glob()`
+    /// * count: usize - the number of images to add
+    ///
+    /// # Attention
+    /// It stops parsing the found files on the first error loading a file
+    ///
+    /// # Errors
+    /// The same errors as `add_glob()`
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// builder.add_glob_sampled("resources/tests/*.{png,jpg}", 2).is_ok();
+    /// ```
+    pub fn add_glob_sampled(&mut self, glob: &str, count: usize) -> Result<&mut Self, FileError> {
+        let mut paths: Vec<PathBuf> = glob_walker(glob)?
+            .filter_map(Result::ok)
+            .map(|file| file.path().to_path_buf())
+            .collect();
+        paths.sort();
+
+        let count = count.min(paths.len());
+        let mut new_thumbs = vec![];
+        for index in 0..count {
+            let path = paths[index * paths.len() / count].clone();
+            new_thumbs.push(ThumbnailData::load(path)?);
+        }
+        self.collection.images.append(new_thumbs.as_mut());
+        Ok(self)
+    }
+
+    /// Adds a single image from encoded image data to the collection, e.g. from an uploaded file
+    ///
+    /// This internally calls the `ThumbnailData::from_bytes` method, and stores the result.
+    /// As the image has no source file, it gets the synthetic path `bytes-<index>`,
+    /// where `<index>` is the position of the image in the collection.
+    /// The file stem of this path is used when storing the image, e.g. in file name templates.
+    ///
+    /// * bytes: &[u8] - The encoded image data
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotSupported` if the format is not supported or the data could not be decoded
+    ///
+    /// # Examples
+    /// ```
+    /// use image::GenericImageView;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// use thumbnailer::{GenericThumbnail, Resize, Target};
+    ///
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// assert!(builder.add_bytes(&std::fs::read("resources/tests/test.jpg").unwrap()).is_ok());
+    /// assert!(builder.add_bytes(&std::fs::read("resources/tests/exif.jpg").unwrap()).is_ok());
+    /// assert!(builder.add_bytes(b"not an image").is_err());
+    /// let mut collection = builder.finalize();
+    ///
+    /// collection.resize(Resize::Width(32));
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_add_bytes");
+    /// let target = Target::new(TargetFormat::Png, dst.clone()).name_template("{stem}_{width}");
+    /// let mut paths = collection.apply_store(&target).ok().unwrap();
+    /// paths.sort();
+    ///
+    /// assert_eq!(paths, vec![dst.join("bytes-0_32.png"), dst.join("bytes-1_32.png")]);
+    /// assert_eq!(image::open(&paths[0]).unwrap().width(), 32);
+    /// ```
+    pub fn add_bytes(&mut self, bytes: &[u8]) -> Result<&mut Self, FileError> {
+        let name = format!("bytes-{}", self.collection.images.len());
+        let t = ThumbnailData::from_bytes(&name, bytes)?;
+        self.collection.images.push(t);
+        Ok(self)
+    }
+
     /// Adds a single, already existing `Thumbnail` to the collection
     ///
     /// * thumb: Thumbnail - The image to add.
@@ -124,84 +255,422 @@ impl Default for ThumbnailCollectionBuilder {
     }
 }
 
+/// Finds the files matching the glob
+///
+/// globwalk matches the glob relative to the current directory. A glob that starts with an absolute path,
+/// e.g. `/tmp/images/*.png`, is split into its longest leading path without glob characters,
+/// which is searched instead, and the rest of the glob.
+///
+/// * glob: &str - the glob to match files on the filesystem
+///
+/// # Errors
+/// Returns a `FileError::GlobError` if parsing the glob fails
+fn glob_walker(glob: &str) -> Result<GlobWalker, FileError> {
+    let path = Path::new(glob);
+    if !path.is_absolute() {
+        return Ok(globwalk::glob(glob)?);
+    }
+
+    let is_pattern = |component: &Component| {
+        component
+            .as_os_str()
+            .to_string_lossy()
+            .contains(['*', '?', '[', ']', '{', '}'])
+    };
+    let components: Vec<Component> = path.components().collect();
+    // A glob without glob characters matches the file it names
+    let split = components
+        .iter()
+        .position(is_pattern)
+        .unwrap_or_else(|| components.len().saturating_sub(1));
+    let base: PathBuf = components[..split].iter().collect();
+    let pattern: Vec<String> = components[split..]
+        .iter()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    Ok(GlobWalkerBuilder::from_patterns(base, &[pattern.join("/")]).build()?)
+}
+
 /// The `ThumbnailCollection` type.
 ///
 /// This type represents a set of images.
+///
+/// When a collection is stored, the file names of the stored images are made distinct.
+/// If the file name is taken from the source file, the index of the image is only added,
+/// if another image of the collection has a source file with the same file stem.
+/// Otherwise the file stem of the source file is added to the file name, and the index as well on collisions.
+///
+/// # Examples
+/// ```
+/// use std::fs;
+/// use thumbnailer::target::TargetFormat;
+/// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+/// use thumbnailer::{GenericThumbnail, Target};
+///
+/// let dir = std::env::temp_dir().join("thumbnailer_doc_collection_names");
+/// fs::create_dir_all(dir.join("a")).unwrap();
+/// fs::create_dir_all(dir.join("b")).unwrap();
+/// fs::copy("resources/tests/test.jpg", dir.join("a/photo.jpg")).unwrap();
+/// fs::copy("resources/tests/test.jpg", dir.join("b/photo.jpg")).unwrap();
+/// fs::copy("resources/tests/test.jpg", dir.join("a/other.jpg")).unwrap();
+///
+/// let mut builder = ThumbnailCollectionBuilder::new();
+/// for name in &["a/photo.jpg", "b/photo.jpg", "a/other.jpg"] {
+///     assert!(builder.add_path(dir.join(name).to_str().unwrap()).is_ok());
+/// }
+/// let collection = builder.finalize();
+///
+/// let out = dir.join("out/");
+/// let mut paths = collection.store(&Target::new(TargetFormat::Png, out.clone())).ok().unwrap();
+/// paths.sort();
+///
+/// assert_eq!(
+///     paths,
+///     vec![out.join("other.png"), out.join("photo-0.png"), out.join("photo-1.png")]
+/// );
+/// ```
 #[derive(Debug)]
 pub struct ThumbnailCollection {
     /// List of the actual image data
     images: Vec<ThumbnailData>,
     /// List of operations to apply to all images in the collection
     ops: Vec<Box<dyn Operation>>,
+    /// Whether each image is turned upright according to its EXIF orientation, before the operations are applied
+    auto_orient: bool,
+    /// The filter used by resize operations, that are queued without a custom filter
+    default_filter: Option<ResampleFilter>,
 }
 
-impl OperationContainer for ThumbnailCollection {
-    fn add_op(&mut self, op: Box<dyn Operation>) {
-        self.ops.push(op);
+impl ThumbnailCollection {
+    /// Turns each image upright according to the EXIF orientation of its source file
+    ///
+    /// Every image can have a different orientation, e.g. when the images are taken from a camera.
+    /// Therefore the orientation is read per image, when the operations are applied.
+    /// The matching rotate and flip operations are applied before all queued operations.
+    /// The orientation is read from the EXIF metadata of JPEG (APP1 segment), PNG (eXIf chunk), TIFF, HEIF and WebP files.
+    /// Images without EXIF orientation are not changed.
+    ///
+    /// Like the queued operations, this only affects the next time the operations are applied.
+    ///
+    /// Returns itself to allow method chaining.
+    pub fn auto_orient_all(&mut self) -> &mut Self {
+        self.auto_orient = true;
+        self
     }
-}
 
-impl GenericThumbnail for ThumbnailCollection {
-    fn apply(&mut self) -> Result<&mut dyn GenericThumbnail, ApplyError> {
-        let ops = self.ops.clone();
+    /// Sets the filter used by subsequent `resize()` calls on the collection
+    ///
+    /// This allows to e.g. resize with a high-quality filter, without calling `resize_filter()` each time.
+    /// Resize operations that are already queued, or that are queued with `resize_filter()`, are not changed.
+    ///
+    /// Returns itself to allow method chaining.
+    pub fn set_default_filter(&mut self, filter: ResampleFilter) -> &mut Self {
+        self.default_filter = Some(filter);
+        self
+    }
+
+    /// Removes all queued operations without applying them
+    ///
+    /// Operations that have already been applied are not undone, and `auto_orient_all()` stays enabled.
+    ///
+    /// Returns itself to allow method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::GenericImageView;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// use thumbnailer::Resize;
+    ///
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// assert!(builder.add_path("resources/tests/test.jpg").is_ok());
+    /// let mut collection = builder.finalize();
+    ///
+    /// collection.resize(Resize::Width(100));
+    /// collection.clear_ops();
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_collection_clear_ops/");
+    /// let target = thumbnailer::Target::new(thumbnailer::target::TargetFormat::Png, dst);
+    /// let paths = collection.process_streaming(&target).ok().unwrap();
+    /// assert_eq!(image::open(&paths[0]).unwrap().dimensions(), (500, 138));
+    /// ```
+    pub fn clear_ops(&mut self) -> &mut Self {
         self.ops.clear();
+        self
+    }
+
+    /// Applies the queued operations to each image, stores the results, and releases each image right after storing it
+    ///
+    /// Unlike `apply_store()`, the decoded images are not kept in memory until all images are processed.
+    /// Each image is decoded, processed and stored, and its image data is dropped before the next image is decoded.
+    /// The images are still processed in parallel, so at most one image per thread of the rayon thread pool
+    /// is held in memory at the same time, regardless of the size of the collection.
+    /// This allows to process thousands of large images, which would not fit into memory at once.
+    ///
+    /// The returned paths are in the order of the images in the collection.
+    ///
+    /// * target: &Target - The definition of the target image files
+    ///
+    /// # Errors
+    /// Returns an `ApplyError::CollectionError`, if processing any of the images failed.
+    /// The other images are still processed and stored, their paths are contained in the error.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::GenericImageView;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// use thumbnailer::{Resize, Target};
+    ///
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// assert!(builder.add_path("resources/tests/test.jpg").is_ok());
+    /// assert!(builder.add_path("resources/tests/exif.jpg").is_ok());
+    /// let mut collection = builder.finalize();
+    ///
+    /// collection.resize(Resize::Width(32));
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_process_streaming/");
+    /// let paths = collection.process_streaming(&Target::new(TargetFormat::Png, dst)).ok().unwrap();
+    ///
+    /// assert_eq!(paths.len(), 2);
+    /// for path in paths {
+    ///     assert_eq!(image::open(path).unwrap().width(), 32);
+    /// }
+    /// ```
+    pub fn process_streaming(self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
+        let indices = self.collection_indices();
+        let ops = self.ops;
+        let auto_orient = self.auto_orient;
 
-        let results: Vec<Option<ApplyError>> = self
+        let results: Vec<Result<Vec<PathBuf>, (PathBuf, ApplyError)>> = self
             .images
-            .par_iter_mut()
-            .map(|data| -> Option<ApplyError> {
-                match data.apply_ops_list(&ops) {
-                    Ok(_) => None,
-                    Err(err) => Some(err),
-                }
-            })
-            .collect();
+            .into_par_iter()
+            .zip(indices)
+            .map(
+                |(mut data, index)| -> Result<Vec<PathBuf>, (PathBuf, ApplyError)> {
+                    let ops = ThumbnailCollection::ops_for_image(auto_orient, &data, &ops);
+                    let result =
+                        ThumbnailCollection::apply_store_image(&mut data, index, &ops, target)
+                            .map_err(|err| (data.get_path(), err));
+                    // the image data is released, before the thread continues with the next image
+                    drop(data);
 
-        let errors = results
-            .iter()
-            .filter_map(|r| match r {
-                None => None,
-                Some(apply_error) => match apply_error {
-                    ApplyError::OperationError(err) => Some(err.clone()),
-                    _ => None,
+                    result
                 },
-            })
+            )
             .collect();
 
-        if results.is_empty() {
-            Ok(self)
-        } else {
-            Err(ApplyError::CollectionError(CollectionError::new(
-                vec![],
-                vec![],
-                errors,
-            )))
-        }
+        ThumbnailCollection::collect_store_results(results)
     }
 
-    fn apply_store(mut self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
-        self.apply_store_keep(target)
+    /// Applies the queued operations, stores the results, and reports the outcome per source file
+    ///
+    /// Unlike `apply_store_keep()`, which returns the stored files of all images together,
+    /// this keeps the association between each source file and its stored files.
+    /// This allows to report success or failure for each file, e.g. to the user who uploaded it.
+    /// The map contains an entry for each image of the collection, keyed by its source path.
+    /// If several images have the same source path, only the result of the last of them is kept.
+    ///
+    /// * target: &Target - The definition of the target image files
+    ///
+    /// # Examples
+    /// ```
+    /// use std::fs;
+    /// use thumbnailer::errors::ApplyError;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// use thumbnailer::{Resize, Target};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_apply_store_mapped");
+    /// fs::create_dir_all(&dir).unwrap();
+    /// fs::copy("resources/tests/test.jpg", dir.join("first.jpg")).unwrap();
+    /// // The file starts like a JPEG, so it only fails when it is decoded
+    /// fs::write(dir.join("broken.jpg"), b"\xFF\xD8\xFF\xE0 not a jpeg at all").unwrap();
+    /// fs::copy("resources/tests/exif.jpg", dir.join("second.jpg")).unwrap();
+    ///
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// for name in &["first.jpg", "broken.jpg", "second.jpg"] {
+    ///     assert!(builder.add_path(dir.join(name).to_str().unwrap()).is_ok());
+    /// }
+    /// let mut collection = builder.finalize();
+    /// collection.resize(Resize::Width(32));
+    ///
+    /// let out = dir.join("out/");
+    /// let target = Target::new(TargetFormat::Png, out.clone()).add_target(TargetFormat::Bmp, out.clone());
+    /// let results = collection.apply_store_mapped(&target);
+    ///
+    /// assert_eq!(results.len(), 3);
+    /// assert_eq!(
+    ///     results[&dir.join("first.jpg")].as_ref().ok().unwrap(),
+    ///     &vec![out.join("first.png"), out.join("first.bmp")]
+    /// );
+    /// assert_eq!(results[&dir.join("second.jpg")].as_ref().ok().unwrap().len(), 2);
+    /// assert!(matches!(results[&dir.join("broken.jpg")], Err(ApplyError::LoadingImageError(_))));
+    /// ```
+    pub fn apply_store_mapped(
+        &mut self,
+        target: &Target,
+    ) -> HashMap<PathBuf, Result<Vec<PathBuf>, ApplyError>> {
+        let ops = self.ops.clone();
+        self.ops.clear();
+        let auto_orient = self.auto_orient;
+        self.auto_orient = false;
+
+        let indices = self.collection_indices();
+
+        let results: Vec<(PathBuf, Result<Vec<PathBuf>, ApplyError>)> = self
+            .images
+            .par_iter_mut()
+            .zip(indices)
+            .map(|(data, index)| {
+                let ops = ThumbnailCollection::ops_for_image(auto_orient, data, &ops);
+                let result = ThumbnailCollection::apply_store_image(data, index, &ops, target);
+                (data.get_path(), result)
+            })
+            .collect();
+
+        results.into_iter().collect()
     }
 
-    fn apply_store_keep(&mut self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
+    /// Applies the queued operations and stores the results, skipping the images stored by a previous run
+    ///
+    /// Each stored image is recorded in a manifest at `manifest_path`, as its source path and its stored files.
+    /// The manifest is a log with one JSON object per line, each image is appended to it as soon as it is stored.
+    /// This keeps the manifest up to date even if the process is interrupted, without rewriting the previous records.
+    /// When this function is called again with the same manifest, e.g. after a crash, the images that are recorded
+    /// in it are neither decoded nor processed again, as long as all of their stored files still exist.
+    /// Their stored files are still contained in the returned paths.
+    /// Images that failed are not recorded, so they are retried with the next run.
+    ///
+    /// The manifest does not record the operations. It should be deleted, when the operations or the target change.
+    ///
+    /// This method is only available with the `serde` feature.
+    ///
+    /// * target: &Target - The definition of the target image files
+    /// * manifest_path: &Path - The path of the manifest, it is created if it does not exist
+    ///
+    /// # Errors
+    /// Returns an `ApplyError::StoreError`, if the existing manifest cannot be read or opened for appending.
+    /// Otherwise returns an `ApplyError::CollectionError`, if processing any of the images,
+    /// or updating the manifest afterwards, failed. The other images are still processed and stored.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::fs;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::thumbnail::ThumbnailCollectionBuilder;
+    /// use thumbnailer::{Resize, Target};
+    ///
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_apply_store_resumable");
+    /// let _ = fs::remove_dir_all(&dir);
+    /// fs::create_dir_all(&dir).unwrap();
+    /// fs::copy("resources/tests/test.jpg", dir.join("first.jpg")).unwrap();
+    ///
+    /// let manifest = dir.join("manifest.jsonl");
+    /// let target = Target::new(TargetFormat::Png, dir.join("out/"));
+    ///
+    /// let mut builder = ThumbnailCollectionBuilder::new();
+    /// assert!(builder.add_path(dir.join("first.jpg").to_str().unwrap()).is_ok());
+    /// let mut collection = builder.finalize();
+    /// collection.resize(Resize::Width(32));
+    ///
+    /// let paths = collection.apply_store_resumable(&target, &manifest).ok().unwrap();
+    /// assert_eq!(paths, vec![dir.join("out/first.png")]);
+    /// assert!(fs::read_to_string(&manifest).unwrap().contains("first.png"));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn apply_store_resumable(
+        &mut self,
+        target: &Target,
+        manifest_path: &Path,
+    ) -> Result<Vec<PathBuf>, ApplyError> {
+        let manifest = StoreManifest::open(manifest_path).map_err(ApplyError::StoreError)?;
+
         let ops = self.ops.clone();
         self.ops.clear();
+        let auto_orient = self.auto_orient;
+        self.auto_orient = false;
+
+        let indices = self.collection_indices();
 
-        let results: Vec<Result<Vec<PathBuf>, ApplyError>> = self
+        let results: Vec<Result<Vec<PathBuf>, (PathBuf, ApplyError)>> = self
             .images
             .par_iter_mut()
-            .enumerate()
-            .map(|(n, data)| -> Result<Vec<PathBuf>, ApplyError> {
-                if let Err(err) = data.apply_ops_list(&ops) {
-                    return Err(err);
-                }
-                match target.store(data, Some(n as u32)) {
-                    Ok(paths) => Ok(paths),
-                    Err(err) => Err(ApplyError::StoreError(err)),
-                }
-            })
+            .zip(indices)
+            .map(
+                |(data, index)| -> Result<Vec<PathBuf>, (PathBuf, ApplyError)> {
+                    let source = data.get_path();
+                    if let Some(outputs) = manifest.completed_outputs(&source) {
+                        return Ok(outputs);
+                    }
+
+                    let ops = ThumbnailCollection::ops_for_image(auto_orient, data, &ops);
+                    let outputs = ThumbnailCollection::apply_store_image(data, index, &ops, target)
+                        .map_err(|err| (source.clone(), err))?;
+
+                    manifest
+                        .complete(&source, &outputs)
+                        .map_err(|err| (source, ApplyError::StoreError(err)))?;
+                    Ok(outputs)
+                },
+            )
             .collect();
 
+        ThumbnailCollection::collect_store_results(results)
+    }
+
+    /// Applies the operations to a single image of the collection and stores the result
+    ///
+    /// * data: &mut ThumbnailData - The image
+    /// * index: CollectionIndex - The position of the image in the collection
+    /// * ops: &[Box<dyn Operation>] - The operations to apply
+    /// * target: &Target - The definition of the target image files
+    fn apply_store_image(
+        data: &mut ThumbnailData,
+        index: CollectionIndex,
+        ops: &[Box<dyn Operation>],
+        target: &Target,
+    ) -> Result<Vec<PathBuf>, ApplyError> {
+        data.apply_ops_list(ops)?;
+        target
+            .store(data, Some(index))
+            .map_err(ApplyError::StoreError)
+    }
+
+    /// Gets the operations to apply to the given image
+    ///
+    /// If `auto_orient` is set, the operations that turn the image upright are prepended to the queued operations.
+    ///
+    /// * auto_orient: bool - Whether the image is turned upright according to its EXIF orientation
+    /// * data: &ThumbnailData - The image
+    /// * ops: &[Box<dyn Operation>] - The queued operations
+    fn ops_for_image(
+        auto_orient: bool,
+        data: &ThumbnailData,
+        ops: &[Box<dyn Operation>],
+    ) -> Vec<Box<dyn Operation>> {
+        let mut image_ops = match auto_orient {
+            true => match data.read_exif() {
+                Ok(exif) => exif.orientation_ops(),
+                Err(_) => vec![],
+            },
+            false => vec![],
+        };
+        image_ops.extend(ops.iter().cloned());
+        image_ops
+    }
+
+    /// Combines the results of applying the operations to and storing each image
+    ///
+    /// Returns the paths of all stored files, or an `ApplyError::CollectionError` if any image failed.
+    ///
+    /// * results: Vec<Result<Vec<PathBuf>, (PathBuf, ApplyError)>> - The stored files or the error of each image, with its source path
+    fn collect_store_results(
+        results: Vec<Result<Vec<PathBuf>, (PathBuf, ApplyError)>>,
+    ) -> Result<Vec<PathBuf>, ApplyError> {
         let mut paths = vec![];
         let mut store_errors = vec![];
         let mut operation_errors = vec![];
@@ -209,9 +678,10 @@ impl GenericThumbnail for ThumbnailCollection {
         for result in results {
             match result {
                 Ok(mut p) => paths.append(&mut p),
-                Err(err) => match err {
-                    ApplyError::OperationError(op_err) => operation_errors.push(op_err),
-                    ApplyError::StoreError(store_err) => store_errors.push(store_err),
+                Err((path, err)) => match err {
+                    ApplyError::OperationError(op_err) => operation_errors.push((path, op_err)),
+                    ApplyError::StoreError(store_err) => store_errors.push((path, store_err)),
+                    ApplyError::LoadingImageError(load_err) => store_errors.push((path, load_err)),
                     _ => {}
                 },
             }
@@ -228,16 +698,207 @@ impl GenericThumbnail for ThumbnailCollection {
         }
     }
 
+    /// Gets the position of each image in the collection, used to give the stored files distinct names
+    ///
+    /// An image has a stem collision, if the source file of another image has the same file stem.
+    fn collection_indices(&self) -> Vec<CollectionIndex> {
+        let stems: Vec<PathBuf> = self
+            .images
+            .iter()
+            .map(|data| PathBuf::from(data.get_path().file_stem().unwrap_or_default()))
+            .collect();
+
+        let mut stem_counts: HashMap<&PathBuf, usize> = HashMap::new();
+        for stem in &stems {
+            *stem_counts.entry(stem).or_insert(0) += 1;
+        }
+
+        stems
+            .iter()
+            .enumerate()
+            .map(|(n, stem)| CollectionIndex {
+                index: n as u32,
+                stem_collision: stem_counts[stem] > 1,
+            })
+            .collect()
+    }
+}
+
+/// The images stored by `ThumbnailCollection::apply_store_resumable()`, as they are recorded in the manifest
+///
+/// The manifest is a log in the JSON Lines format, with one `StoreManifestEntry` per line.
+/// Completed images are appended, so recording an image takes the same time regardless of the size of the manifest.
+#[cfg(feature = "serde")]
+struct StoreManifest {
+    /// The stored files of each image completed by a previous run, by the path of its source file
+    completed: HashMap<PathBuf, Vec<PathBuf>>,
+    /// The manifest, opened for appending the images completed by this run
+    log: Mutex<File>,
+}
+
+/// A single completed image of a `StoreManifest`
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct StoreManifestEntry<'a> {
+    /// The path of the source file
+    source: Cow<'a, Path>,
+    /// The paths of the stored files
+    outputs: Cow<'a, [PathBuf]>,
+}
+
+#[cfg(feature = "serde")]
+impl StoreManifest {
+    /// Reads the manifest at the given path and opens it for appending, the file is created if it does not exist
+    ///
+    /// A source file that is recorded more than once keeps its last record.
+    /// A line, that is not a complete record because the previous run was interrupted while writing it, is ignored.
+    ///
+    /// * path: &Path - The path of the manifest
+    fn open(path: &Path) -> Result<Self, FileError> {
+        let log = match fs::read_to_string(path) {
+            Ok(log) => log,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(FileError::IoError(err)),
+        };
+        let completed = log
+            .lines()
+            .filter_map(|line| serde_json::from_str::<StoreManifestEntry>(line).ok())
+            .map(|entry| (entry.source.into_owned(), entry.outputs.into_owned()))
+            .collect();
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        // An incomplete last line is terminated, so it does not corrupt the next record
+        if !log.is_empty() && !log.ends_with('\n') {
+            file.write_all(b"\n")?;
+        }
+        Ok(StoreManifest {
+            completed,
+            log: Mutex::new(file),
+        })
+    }
+
+    /// Gets the stored files of the given source file, if it is completed and all of its stored files exist
+    ///
+    /// * source: &Path - The path of the source file
+    fn completed_outputs(&self, source: &Path) -> Option<Vec<PathBuf>> {
+        self.completed
+            .get(source)
+            .filter(|outputs| outputs.iter().all(|output| output.is_file()))
+            .cloned()
+    }
+
+    /// Records the given source file as completed, by appending it to the manifest
+    ///
+    /// The manifest is locked while the record is written, so records of images completed at the same time do not interleave.
+    ///
+    /// * source: &Path - The path of the source file
+    /// * outputs: &[PathBuf] - The paths of the stored files
+    fn complete(&self, source: &Path, outputs: &[PathBuf]) -> Result<(), FileError> {
+        let entry = StoreManifestEntry {
+            source: Cow::Borrowed(source),
+            outputs: Cow::Borrowed(outputs),
+        };
+        let mut line = serde_json::to_string(&entry).map_err(io::Error::from)?;
+        line.push('\n');
+        let mut log = self.log.lock().unwrap_or_else(PoisonError::into_inner);
+        log.write_all(line.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl OperationContainer for ThumbnailCollection {
+    fn add_op(&mut self, op: Box<dyn Operation>) {
+        self.ops.push(op);
+    }
+
+    fn default_resize_filter(&self) -> Option<ResampleFilter> {
+        self.default_filter
+    }
+}
+
+impl GenericThumbnail for ThumbnailCollection {
+    fn apply(&mut self) -> Result<&mut dyn GenericThumbnail, ApplyError> {
+        let ops = self.ops.clone();
+        self.ops.clear();
+        let auto_orient = self.auto_orient;
+        self.auto_orient = false;
+
+        let results: Vec<Option<(PathBuf, ApplyError)>> = self
+            .images
+            .par_iter_mut()
+            .map(|data| -> Option<(PathBuf, ApplyError)> {
+                let ops = ThumbnailCollection::ops_for_image(auto_orient, data, &ops);
+                match data.apply_ops_list(&ops) {
+                    Ok(_) => None,
+                    Err(err) => Some((data.get_path(), err)),
+                }
+            })
+            .collect();
+
+        let mut load_errors = vec![];
+        let mut operation_errors = vec![];
+
+        for (path, err) in results.into_iter().flatten() {
+            match err {
+                ApplyError::OperationError(op_err) => operation_errors.push((path, op_err)),
+                ApplyError::LoadingImageError(load_err) => load_errors.push((path, load_err)),
+                _ => {}
+            }
+        }
+
+        if load_errors.is_empty() && operation_errors.is_empty() {
+            Ok(self)
+        } else {
+            Err(ApplyError::CollectionError(CollectionError::new(
+                vec![],
+                load_errors,
+                operation_errors,
+            )))
+        }
+    }
+
+    fn apply_store(mut self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
+        self.apply_store_keep(target)
+    }
+
+    fn apply_store_keep(&mut self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
+        let ops = self.ops.clone();
+        self.ops.clear();
+        let auto_orient = self.auto_orient;
+        self.auto_orient = false;
+
+        let indices = self.collection_indices();
+
+        let results: Vec<Result<Vec<PathBuf>, (PathBuf, ApplyError)>> = self
+            .images
+            .par_iter_mut()
+            .zip(indices)
+            .map(|(data, index)| {
+                let ops = ThumbnailCollection::ops_for_image(auto_orient, data, &ops);
+                ThumbnailCollection::apply_store_image(data, index, &ops, target)
+                    .map_err(|err| (data.get_path(), err))
+            })
+            .collect();
+
+        ThumbnailCollection::collect_store_results(results)
+    }
+
     fn store(mut self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
         self.store_keep(target)
     }
 
     fn store_keep(&mut self, target: &Target) -> Result<Vec<PathBuf>, ApplyError> {
-        let results: Vec<Result<Vec<PathBuf>, FileError>> = self
+        let indices = self.collection_indices();
+
+        let results: Vec<Result<Vec<PathBuf>, (PathBuf, FileError)>> = self
             .images
             .par_iter_mut()
-            .enumerate()
-            .map(|(n, data)| target.store(data, Some(n as u32)))
+            .zip(indices)
+            .map(|(data, index)| {
+                target
+                    .store(data, Some(index))
+                    .map_err(|err| (data.get_path(), err))
+            })
             .collect();
 
         let mut paths = vec![];
@@ -246,7 +907,7 @@ impl GenericThumbnail for ThumbnailCollection {
         for result in results {
             match result {
                 Ok(mut p) => paths.append(&mut p),
-                Err(err) => store_errors.push(err),
+                Err(error) => store_errors.push(error),
             }
         }
 
@@ -261,3 +922,341 @@ impl GenericThumbnail for ThumbnailCollection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ThumbnailCollection, ThumbnailCollectionBuilder};
+    use crate::errors::FileError;
+    use crate::generic::GenericThumbnailOperations;
+    use crate::target::{OutputEncoder, TargetFormat};
+    use crate::thumbnail::data::{ThumbnailData, DECODE_COUNT};
+    use crate::thumbnail::operations::{Operation, ResizeOp};
+    use crate::{GenericThumbnail, Target};
+    use crate::{ResampleFilter, Resize};
+    use image::{DynamicImage, GenericImageView, ImageOutputFormat, Rgb, RgbImage};
+    use std::cell::Cell;
+    use std::io::Write;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    thread_local! {
+        /// The number of images stored by `CountingEncoder` on the current thread
+        static STORED: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Stores images as PNG, and records the most images a thread has decoded but not stored yet
+    struct CountingEncoder {
+        max_held: AtomicUsize,
+    }
+
+    impl OutputEncoder for CountingEncoder {
+        fn extension(&self) -> &str {
+            "png"
+        }
+
+        fn encode(&self, img: &DynamicImage, w: &mut dyn Write) -> Result<(), FileError> {
+            let held = DECODE_COUNT
+                .with(Cell::get)
+                .saturating_sub(STORED.with(Cell::get));
+            self.max_held.fetch_max(held, Ordering::SeqCst);
+            STORED.with(|stored| stored.set(stored.get() + 1));
+
+            let mut png = vec![];
+            img.write_to(&mut png, ImageOutputFormat::Png)
+                .map_err(|_| FileError::UnknownError)?;
+            w.write_all(&png)?;
+            Ok(())
+        }
+    }
+
+    /// Writes the image as JPEG with the given EXIF orientation
+    fn write_oriented_jpeg(path: &Path, image: &DynamicImage, orientation: u16) {
+        let mut jpeg = vec![];
+        image
+            .write_to(&mut jpeg, ImageOutputFormat::Jpeg(90))
+            .unwrap();
+
+        // TIFF header, one IFD with the orientation as single entry, no next IFD
+        let mut tiff = b"MM\x00\x2a".to_vec();
+        tiff.extend_from_slice(&8u32.to_be_bytes());
+        tiff.extend_from_slice(&1u16.to_be_bytes());
+        tiff.extend_from_slice(&0x0112u16.to_be_bytes());
+        tiff.extend_from_slice(&3u16.to_be_bytes());
+        tiff.extend_from_slice(&1u32.to_be_bytes());
+        tiff.extend_from_slice(&orientation.to_be_bytes());
+        tiff.extend_from_slice(&[0, 0]);
+        tiff.extend_from_slice(&0u32.to_be_bytes());
+
+        // The APP1 segment directly follows the start of image marker
+        let mut bytes = jpeg[..2].to_vec();
+        bytes.extend_from_slice(&[0xFF, 0xE1]);
+        bytes.extend_from_slice(&(2 + 6 + tiff.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(b"Exif\0\0");
+        bytes.extend_from_slice(&tiff);
+        bytes.extend_from_slice(&jpeg[2..]);
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    /// Creates an upright image, red in the upper half and blue in the lower half
+    fn upright(width: u32, height: u32) -> DynamicImage {
+        DynamicImage::ImageRgb8(RgbImage::from_fn(width, height, |_, y| {
+            if y < height / 2 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 255])
+            }
+        }))
+    }
+
+    #[test]
+    fn auto_orient_all_turns_each_image_upright() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_auto_orient");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Landscape and portrait images, stored as a camera would store them
+        let sources = vec![
+            ("landscape.jpg", upright(60, 40), 1),
+            ("landscape_upside_down.jpg", upright(60, 40).rotate180(), 3),
+            ("portrait_cw.jpg", upright(40, 60).rotate270(), 6),
+            ("portrait_ccw.jpg", upright(40, 60).rotate90(), 8),
+        ];
+
+        let mut builder = ThumbnailCollectionBuilder::new();
+        for (name, image, orientation) in &sources {
+            write_oriented_jpeg(&dir.join(name), image, *orientation);
+            assert!(builder.add_path(dir.join(name).to_str().unwrap()).is_ok());
+        }
+        let mut collection = builder.finalize();
+        collection.auto_orient_all();
+
+        let target = Target::new(TargetFormat::Png, dir.join("out/"));
+        let paths = collection.apply_store_keep(&target).ok().unwrap();
+        assert_eq!(paths.len(), 4);
+
+        for path in paths {
+            let image = image::open(&path).unwrap();
+            let (width, height) = image.dimensions();
+            let expected = if path.to_string_lossy().contains("portrait") {
+                (40, 60)
+            } else {
+                (60, 40)
+            };
+            assert_eq!((width, height), expected, "{}", path.display());
+
+            let top = image.get_pixel(width / 2, 2);
+            let bottom = image.get_pixel(width / 2, height - 3);
+            assert!(top[0] > 200 && top[2] < 50, "{}", path.display());
+            assert!(bottom[0] < 50 && bottom[2] > 200, "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn auto_orient_all_reads_png_exif_chunk() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_auto_orient_png");
+
+        // 60x40 pixels as stored, with orientation 6 in an eXIf chunk
+        let mut builder = ThumbnailCollectionBuilder::new();
+        assert!(builder.add_path("resources/tests/exif.png").is_ok());
+        let mut collection = builder.finalize();
+        collection.auto_orient_all();
+
+        let target = Target::new(TargetFormat::Png, dir.join("out/"));
+        let paths = collection.apply_store(&target).ok().unwrap();
+
+        let image = image::open(&paths[0]).unwrap();
+        assert_eq!(image.dimensions(), (40, 60));
+        let top = image.get_pixel(20, 2);
+        let bottom = image.get_pixel(20, 57);
+        assert!(top[0] > 200 && top[2] < 50);
+        assert!(bottom[0] < 50 && bottom[2] > 200);
+    }
+
+    /// Writes the given number of small PNG files to the directory, named `0000.png`, `0001.png`, ...
+    fn write_numbered_pngs(dir: &Path, count: usize) {
+        std::fs::create_dir_all(dir).unwrap();
+        for index in 0..count {
+            let image =
+                DynamicImage::ImageRgb8(RgbImage::from_pixel(4, 4, Rgb([index as u8, 0, 0])));
+            image.save(dir.join(format!("{:04}.png", index))).unwrap();
+        }
+    }
+
+    #[test]
+    fn add_glob_limited_stops_after_max_files() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_glob_limited");
+        write_numbered_pngs(&dir, 40);
+        let glob = format!("{}/*.png", dir.display());
+
+        let mut builder = ThumbnailCollectionBuilder::new();
+        assert!(builder.add_glob_limited(&glob, 5).is_ok());
+        assert_eq!(builder.collection.images.len(), 5);
+
+        let mut builder = ThumbnailCollectionBuilder::new();
+        assert!(builder.add_glob_limited(&glob, 100).is_ok());
+        assert_eq!(builder.collection.images.len(), 40);
+    }
+
+    #[test]
+    fn add_glob_sampled_spans_all_files() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_glob_sampled");
+        write_numbered_pngs(&dir, 40);
+        let glob = format!("{}/*.png", dir.display());
+
+        let mut builder = ThumbnailCollectionBuilder::new();
+        assert!(builder.add_glob_sampled(&glob, 5).is_ok());
+        let names: Vec<String> = builder
+            .collection
+            .images
+            .iter()
+            .map(|data| {
+                data.get_path()
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(
+            names,
+            vec!["0000.png", "0008.png", "0016.png", "0024.png", "0032.png"]
+        );
+    }
+
+    /// Builds a collection of the given number of copies of a large image, to be resized to a width of 50
+    fn streaming_collection(dir: &Path, count: usize) -> ThumbnailCollection {
+        let mut builder = ThumbnailCollectionBuilder::new();
+        for n in 0..count {
+            let path = dir.join(format!("source_{}.png", n));
+            if !path.exists() {
+                upright(1000, 800).save(&path).unwrap();
+            }
+            assert!(builder.add_path(path.to_str().unwrap()).is_ok());
+        }
+        let mut collection = builder.finalize();
+        collection.resize(Resize::Width(50));
+        collection
+    }
+
+    #[test]
+    fn process_streaming_holds_one_image_per_thread() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_process_streaming");
+        std::fs::create_dir_all(&dir).unwrap();
+        let threads = 2;
+        let count = threads * 8;
+        // The decodes are counted per thread, the threads of this pool only decode images of this test
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .unwrap();
+        let encoder = Arc::new(CountingEncoder {
+            max_held: AtomicUsize::new(0),
+        });
+        let target = Target::new(TargetFormat::Custom(encoder.clone()), dir.join("out/"));
+
+        let collection = streaming_collection(&dir, count);
+        let paths = pool
+            .install(|| collection.process_streaming(&target))
+            .ok()
+            .unwrap();
+
+        assert_eq!(paths.len(), count);
+        for (n, path) in paths.iter().enumerate() {
+            assert!(path.ends_with(format!("source_{}.png", n)));
+            assert_eq!(image::open(path).unwrap().width(), 50);
+        }
+        // each thread stores an image before it decodes the next one,
+        // so the decoded images are bound by the threads, not by the size of the collection
+        assert_eq!(encoder.max_held.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn resize_uses_default_filter() {
+        let mut builder = ThumbnailCollectionBuilder::new();
+        assert!(builder.add_path("resources/tests/test.jpg").is_ok());
+        let mut collection = builder.finalize();
+
+        collection.resize(Resize::Width(100));
+        collection.set_default_filter(ResampleFilter::Lanczos3);
+        collection.resize(Resize::Width(50));
+        collection.resize_filter(Resize::Width(25), ResampleFilter::Nearest);
+
+        let filters: Vec<String> = collection
+            .ops
+            .iter()
+            .map(|op| format!("{:?}", op))
+            .collect();
+        assert!(filters[0].contains("filter: None"));
+        assert!(filters[1].contains("filter: Some(Lanczos3)"));
+        assert!(filters[2].contains("filter: Some(Nearest)"));
+
+        // The queued resize gives the same result as resizing with Lanczos3 directly.
+        // Both go through the same decode, as a JPEG may be decoded at a reduced scale for a small thumbnail.
+        collection.clear_ops();
+        collection.resize(Resize::Width(100));
+        let dir = std::env::temp_dir().join("thumbnailer_test_default_filter/");
+        let target = Target::new(TargetFormat::Png, dir);
+        let paths = collection.apply_store_keep(&target).ok().unwrap();
+
+        let mut expected = ThumbnailData::load("resources/tests/test.jpg".into()).unwrap();
+        let lanczos: Vec<Box<dyn Operation>> = vec![Box::new(ResizeOp::new(
+            Resize::Width(100),
+            Some(ResampleFilter::Lanczos3),
+        ))];
+        assert!(expected.apply_ops_list(&lanczos).is_ok());
+        let expected = expected.get_dyn_image().unwrap();
+        let stored = image::open(&paths[0]).unwrap();
+        assert_eq!(stored.dimensions(), expected.dimensions());
+        assert_eq!(stored.to_bytes(), expected.to_bytes());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn apply_store_resumable_skips_completed_images() {
+        let dir = std::env::temp_dir().join("thumbnailer_test_resumable");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::copy("resources/tests/test.jpg", dir.join("first.jpg")).unwrap();
+        // The second image cannot be processed yet, as if the run was interrupted after the first image
+        std::fs::write(
+            dir.join("second.jpg"),
+            b"\xFF\xD8\xFF\xE0 not written completely",
+        )
+        .unwrap();
+
+        let manifest = dir.join("manifest.jsonl");
+        let out = dir.join("out/");
+        let target = Target::new(TargetFormat::Png, out.clone());
+        let run = || {
+            let mut builder = ThumbnailCollectionBuilder::new();
+            for name in &["first.jpg", "second.jpg"] {
+                assert!(builder.add_path(dir.join(name).to_str().unwrap()).is_ok());
+            }
+            let mut collection = builder.finalize();
+            collection.resize(Resize::Width(32));
+            collection.apply_store_resumable(&target, &manifest)
+        };
+
+        assert!(run().is_err());
+        assert!(out.join("first.png").is_file());
+        assert!(!out.join("second.png").exists());
+
+        // Marks the stored file of the first image, processing it again would overwrite the mark
+        std::fs::write(out.join("first.png"), b"completed before").unwrap();
+        std::fs::copy("resources/tests/exif.jpg", dir.join("second.jpg")).unwrap();
+
+        let mut paths = run().ok().unwrap();
+        paths.sort();
+        assert_eq!(paths, vec![out.join("first.png"), out.join("second.png")]);
+        assert_eq!(
+            std::fs::read(out.join("first.png")).unwrap(),
+            b"completed before"
+        );
+        assert_eq!(image::open(out.join("second.png")).unwrap().width(), 32);
+
+        // Once the stored file is gone, the image is processed again
+        std::fs::remove_file(out.join("first.png")).unwrap();
+        assert!(run().is_ok());
+        assert_eq!(image::open(out.join("first.png")).unwrap().width(), 32);
+    }
+}
@@ -1,22 +1,43 @@
-use crate::errors::{ApplyError, FileError, FileNotFoundError, FileNotSupportedError};
+use crate::errors::{
+    ApplyError, FileError, FileNotFoundError, FileNotSupportedError, OperationError,
+};
+use crate::thumbnail::decoder::{find_decoder, has_decoders, DECODER_HEADER_LEN};
+use crate::thumbnail::metadata::ExifData;
 use crate::thumbnail::operations::Operation;
+use image::codecs::gif::GifDecoder;
+use image::codecs::jpeg::JpegDecoder;
+use image::codecs::png::PngDecoder;
 use image::io::Reader;
-use image::{DynamicImage, ImageError, ImageFormat};
+use image::{
+    AnimationDecoder, ColorType, DynamicImage, Frames, GenericImageView, GrayAlphaImage, GrayImage,
+    ImageError, ImageFormat, ImageResult, RgbImage, RgbaImage,
+};
 use std::fmt;
 use std::fmt::Formatter;
 use std::fs::File;
-use std::io::BufReader;
-use std::path::PathBuf;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use tiff::decoder::{Decoder as TiffDecoder, DecodingResult};
+use tiff::ColorType as TiffColorType;
+
+#[cfg(test)]
+thread_local! {
+    /// Counts how often image data has been decoded from a file on the current thread
+    pub(crate) static DECODE_COUNT: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    /// The dimensions of the image data that has been decoded last on the current thread
+    pub(crate) static DECODED_DIMENSIONS: std::cell::Cell<(u32, u32)> = const { std::cell::Cell::new((0, 0)) };
+}
 
 /// The `ImageData` type
 ///
-/// This type either holds a file handle with a format, the file has been determined to be,
+/// This type either holds the format, the file at the path of the `ThumbnailData` has been determined to be,
 /// or the actual image data in memory.
 /// This allows to dynamically load the data only then when it's being used.
-/// Before that only a reference to the image is store, from which the data will be read.
+/// Before that the file is not even kept open, so many queued images do not hold many open files.
 pub(crate) enum ImageData {
-    /// File which holds a file handle and the files image format information
-    File(File, ImageFormat),
+    /// File at the path of the `ThumbnailData`, with the files image format information
+    File(ImageFormat),
     /// Image data in memory
     Image(DynamicImage),
 }
@@ -24,7 +45,7 @@ pub(crate) enum ImageData {
 impl fmt::Debug for ImageData {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            ImageData::File(file, format) => write!(f, "ImageData::File( {:?}, {:?}", file, format),
+            ImageData::File(format) => write!(f, "ImageData::File({:?})", format),
             ImageData::Image(_) => write!(f, "ImageData::Image(DynamicImage)"),
         }
     }
@@ -39,6 +60,16 @@ pub struct ThumbnailData {
     path: PathBuf,
     /// The image data
     image: ImageData,
+    /// The format of the image, as detected when loading the file
+    format: Option<ImageFormat>,
+    /// Whether the image was decoded from a file or from encoded data, rather than created from a `DynamicImage`
+    from_file: bool,
+    /// Whether the image was loaded from the file at `path`, rather than from encoded data or a `DynamicImage`
+    loaded_from_path: bool,
+    /// The raw EXIF metadata of the encoded data the image was decoded from, as there is no file to read it from
+    exif: Option<Vec<u8>>,
+    /// The color type the image is converted to, before operations are applied to it
+    working_color: Option<ColorType>,
 }
 
 impl ThumbnailData {
@@ -53,11 +84,24 @@ impl ThumbnailData {
             return Err(FileError::NotFound(FileNotFoundError { path }));
         }
 
-        let file = match File::open(path.clone()) {
+        let mut file = match File::open(path.clone()) {
             Ok(f) => f,
             Err(e) => return Err(FileError::IoError(e)),
         };
 
+        // Registered decoders are asked first, these images are decoded immediately
+        if let Some(image) = decode_registered(&mut file, &path)? {
+            return Ok(ThumbnailData {
+                path,
+                image: ImageData::Image(image),
+                format: None,
+                from_file: true,
+                loaded_from_path: true,
+                exif: None,
+                working_color: None,
+            });
+        }
+
         let buffer = BufReader::new(file);
 
         // This unfortunately needs to be mutable, because we may need to overwrite it with itself,
@@ -76,14 +120,119 @@ impl ThumbnailData {
 
                 match reader.format() {
                     Some(f) => f,
-                    None => return Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+                    None => {
+                        // The image crate does not support HEIF, these images are decoded immediately
+                        #[cfg(feature = "heif")]
+                        {
+                            let mut bytes = vec![];
+                            reader.into_inner().read_to_end(&mut bytes)?;
+                            if let Some(image) =
+                                catch_decoder_panic(|| decode_heif(&bytes)).flatten()
+                            {
+                                return Ok(ThumbnailData {
+                                    path,
+                                    image: ImageData::Image(image),
+                                    format: None,
+                                    from_file: true,
+                                    loaded_from_path: true,
+                                    exif: None,
+                                    working_color: None,
+                                });
+                            }
+                        }
+                        return Err(FileError::NotSupported(FileNotSupportedError::new(path)));
+                    }
                 }
             }
         };
 
         Ok(ThumbnailData {
             path,
-            image: ImageData::File(reader.into_inner().into_inner(), format),
+            image: ImageData::File(format),
+            format: Some(format),
+            from_file: true,
+            loaded_from_path: true,
+            exif: None,
+            working_color: None,
+        })
+    }
+
+    /// Creates a new `ThumbnailData` from the given file path, using the given format to decode the image
+    ///
+    /// Unlike `load()` the format is not guessed from the extension or the content of the file.
+    ///
+    /// * path: PathBuf - The path to the image file
+    /// * format: ImageFormat - The format of the image file
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotFound` if the file could not be found.
+    pub(crate) fn load_with_format(
+        path: PathBuf,
+        format: ImageFormat,
+    ) -> Result<ThumbnailData, FileError> {
+        if !path.is_file() {
+            return Err(FileError::NotFound(FileNotFoundError { path }));
+        }
+
+        Ok(ThumbnailData {
+            path,
+            image: ImageData::File(format),
+            format: Some(format),
+            from_file: true,
+            loaded_from_path: true,
+            exif: None,
+            working_color: None,
+        })
+    }
+
+    /// Creates a new `ThumbnailData` from a single frame of the animated image at the given file path
+    ///
+    /// Unlike `load()` this decodes the image data immediately.
+    /// Supported are animated GIF and PNG (APNG) files.
+    ///
+    /// * path: PathBuf - The path to the image file
+    /// * index: usize - The index of the frame, starting at 0
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotFound` if the file could not be found
+    /// Returns a `FileError::NotSupported` if the file is not an animated image of a supported format
+    /// Returns a `FileError::FrameNotFound` if the image has no frame with the given index
+    /// Returns a `FileError::IoError` if an error occurred while accessing the file
+    pub(crate) fn load_frame(path: PathBuf, index: usize) -> Result<ThumbnailData, FileError> {
+        if !path.is_file() {
+            return Err(FileError::NotFound(FileNotFoundError { path }));
+        }
+
+        let reader =
+            Reader::new(BufReader::new(File::open(path.clone())?)).with_guessed_format()?;
+
+        let format = reader.format();
+        let mut frames: Frames = match format {
+            Some(ImageFormat::Gif) => match GifDecoder::new(reader.into_inner()) {
+                Ok(decoder) => decoder.into_frames(),
+                Err(_) => return Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+            },
+            Some(ImageFormat::Png) => match PngDecoder::new(reader.into_inner()) {
+                Ok(decoder) => decoder.apng().into_frames(),
+                Err(_) => return Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+            },
+            _ => return Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+        };
+
+        let image = match frames.nth(index) {
+            Some(Ok(frame)) => DynamicImage::ImageRgba8(frame.into_buffer()),
+            Some(Err(_)) => return Err(FileError::NotSupported(FileNotSupportedError::new(path))),
+            None => return Err(FileError::FrameNotFound(index)),
+        };
+
+        Ok(ThumbnailData {
+            path,
+            image: ImageData::Image(image),
+            format,
+            from_file: true,
+            loaded_from_path: true,
+            exif: None,
+            working_color: None,
         })
     }
 
@@ -96,6 +245,8 @@ impl ThumbnailData {
 
     /// This function creates and returns a new `ThumbnailData` from an existing DynamicImage.
     ///
+    /// The format of the image is inferred from the extension of `path_name`, if possible.
+    ///
     /// # Arguments
     ///
     /// * `path_name` - A custom path for the new `ThumbnailData`
@@ -107,47 +258,277 @@ impl ThumbnailData {
     pub(crate) fn from_dynamic_image(path_name: &str, dynamic_image: DynamicImage) -> Self {
         let path = PathBuf::from(path_name);
         let image = ImageData::Image(dynamic_image);
+        let format = ImageFormat::from_path(&path).ok();
+
+        ThumbnailData {
+            path,
+            image,
+            format,
+            from_file: false,
+            loaded_from_path: false,
+            exif: None,
+            working_color: None,
+        }
+    }
+
+    /// Creates a new `ThumbnailData` by decoding the given encoded image data, e.g. an uploaded file
+    ///
+    /// The format is guessed from the content of the data. Unlike loading a file, the data is decoded immediately.
+    /// Decoders registered with `register_decoder()` are asked before the built-in ones.
+    ///
+    /// * path_name: &str - A custom path for the new `ThumbnailData`, nothing is read from that path
+    /// * bytes: &[u8] - The encoded image data
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotSupported` if the format is not supported or the data could not be decoded
+    /// Returns the error of a registered decoder, if it failed to decode the data
+    pub(crate) fn from_bytes(path_name: &str, bytes: &[u8]) -> Result<Self, FileError> {
+        let path = PathBuf::from(path_name);
+        let exif = ExifData::read_raw_bytes(bytes);
+        let not_supported = |path| FileError::NotSupported(FileNotSupportedError::new(path));
+
+        if let Some(decoder) = find_decoder(bytes) {
+            return match catch_decoder_panic(|| decoder.decode(bytes)) {
+                Some(Ok(image)) => Ok(ThumbnailData {
+                    path,
+                    image: ImageData::Image(image),
+                    format: None,
+                    from_file: true,
+                    loaded_from_path: false,
+                    exif,
+                    working_color: None,
+                }),
+                Some(Err(error)) => Err(error),
+                None => Err(not_supported(path)),
+            };
+        }
+
+        let format = match image::guess_format(bytes) {
+            Ok(format) => format,
+            #[cfg(feature = "heif")]
+            Err(_) => {
+                return match catch_decoder_panic(|| decode_heif(bytes)).flatten() {
+                    Some(image) => Ok(ThumbnailData {
+                        path,
+                        image: ImageData::Image(image),
+                        format: None,
+                        from_file: true,
+                        loaded_from_path: false,
+                        exif,
+                        working_color: None,
+                    }),
+                    None => Err(not_supported(path)),
+                }
+            }
+            #[cfg(not(feature = "heif"))]
+            Err(_) => return Err(not_supported(path)),
+        };
+        let decoded = catch_decoder_panic(|| image::load_from_memory_with_format(bytes, format));
+        #[cfg(feature = "webp")]
+        let decoded = match (decoded, format) {
+            (Some(Err(error)), ImageFormat::WebP) => Some(
+                catch_decoder_panic(|| decode_webp(bytes))
+                    .flatten()
+                    .ok_or(error),
+            ),
+            (decoded, _) => decoded,
+        };
+        let image = match decoded {
+            Some(Ok(image)) => image,
+            _ => return Err(not_supported(path)),
+        };
 
-        ThumbnailData { path, image }
+        Ok(ThumbnailData {
+            path,
+            image: ImageData::Image(image),
+            format: Some(format),
+            from_file: true,
+            loaded_from_path: false,
+            exif,
+            working_color: None,
+        })
     }
 
     /// Gets the `DynamicImage` stored inside a `ImageData` instance.
     ///
     /// If the dynamic image has not yet been loaded,
-    /// and the `ImageData` instance still contains the file handle,
-    /// the data will be loaded and the `ImageData` instance will be converted, if possible.
+    /// and the `ImageData` instance still refers to the file,
+    /// the file is opened, the data will be loaded and the `ImageData` instance will be converted, if possible.
     ///
     /// # Errors
     /// Returns an InternalError of there was a problem loading the image data from the file system
     /// or accessing the `DynamicImage` instance
     pub(crate) fn get_dyn_image(&mut self) -> Result<&mut image::DynamicImage, FileError> {
-        if let ImageData::File(file, format) = &self.image {
-            let mut reader = Reader::new(BufReader::new(file));
-            reader.set_format(*format);
-            let dyn_image = match reader.decode() {
+        self.get_dyn_image_scaled(None)
+    }
+
+    /// Gets the `DynamicImage` stored inside a `ImageData` instance, decoded at a reduced scale if possible.
+    ///
+    /// Same as `get_dyn_image()`, but JPEG images are decoded at the smallest scale (1/8, 1/4, 1/2 or full),
+    /// that is at least as large as `min_dimensions` in both directions. If no scale reaches `min_dimensions`,
+    /// the image is decoded in its full size.
+    /// Decoding at a reduced scale is considerably faster and needs less memory.
+    /// All other formats, and images that are already in memory, are not affected.
+    ///
+    /// * min_dimensions: Option<(u32, u32)> - The minimum dimensions, or `None` to decode the full image
+    ///
+    /// # Errors
+    /// Returns an InternalError of there was a problem loading the image data from the file system
+    /// or accessing the `DynamicImage` instance
+    fn get_dyn_image_scaled(
+        &mut self,
+        min_dimensions: Option<(u32, u32)>,
+    ) -> Result<&mut image::DynamicImage, FileError> {
+        if let ImageData::File(format) = self.image {
+            let file = &File::open(&self.path)?;
+            let mut handle = file;
+
+            #[cfg(test)]
+            DECODE_COUNT.with(|count| count.set(count.get() + 1));
+
+            let decoded = catch_decoder_panic(|| match (format, min_dimensions) {
+                (ImageFormat::Jpeg, Some((width, height)))
+                    if width <= u16::MAX as u32 && height <= u16::MAX as u32 =>
+                {
+                    JpegDecoder::new(BufReader::new(file)).and_then(|mut decoder| {
+                        let (scaled_width, scaled_height) =
+                            decoder.scale(width as u16, height as u16)?;
+                        // The decoder picks the scale by either side, the other side may end up too small
+                        if (scaled_width as u32) < width || (scaled_height as u32) < height {
+                            handle
+                                .seek(SeekFrom::Start(0))
+                                .map_err(ImageError::IoError)?;
+                            return decode_with_format(file, format);
+                        }
+                        DynamicImage::from_decoder(decoder)
+                    })
+                }
+                // Striped TIFF images are reduced strip by strip, without holding the full size image in memory
+                (ImageFormat::Tiff, Some((width, height))) => {
+                    match decode_tiff_scaled(file, width, height) {
+                        Some(image) => Ok(image),
+                        None => handle
+                            .seek(SeekFrom::Start(0))
+                            .map_err(ImageError::IoError)
+                            .and_then(|_| decode_with_format(file, format)),
+                    }
+                }
+                _ => decode_with_format(file, format),
+            });
+            let decoded = match decoded {
+                Some(decoded) => decoded,
+                None => {
+                    return Err(FileError::NotSupported(FileNotSupportedError::new(
+                        self.path.clone(),
+                    )))
+                }
+            };
+            // The image crate only decodes lossy WebP images without alpha channel
+            #[cfg(feature = "webp")]
+            let decoded = match (decoded, format) {
+                (Err(error), ImageFormat::WebP) => {
+                    handle.seek(SeekFrom::Start(0))?;
+                    let mut bytes = vec![];
+                    handle.read_to_end(&mut bytes)?;
+                    catch_decoder_panic(|| decode_webp(&bytes))
+                        .flatten()
+                        .ok_or(error)
+                }
+                (decoded, _) => decoded,
+            };
+
+            let dyn_image = match decoded {
                 Ok(i) => i,
                 Err(error) => {
                     return match error {
-                        ImageError::Unsupported(_) => Err(FileError::NotSupported(
-                            FileNotSupportedError::new(self.path.clone()),
-                        )),
+                        ImageError::Unsupported(_) | ImageError::Decoding(_) => Err(
+                            FileError::NotSupported(FileNotSupportedError::new(self.path.clone())),
+                        ),
+                        // Truncated files end before the decoder has read all of the image data
+                        ImageError::IoError(error)
+                            if error.kind() == io::ErrorKind::UnexpectedEof =>
+                        {
+                            Err(FileError::NotSupported(FileNotSupportedError::new(
+                                self.path.clone(),
+                            )))
+                        }
                         _ => Err(FileError::UnknownError),
-                    }
+                    };
                 }
             };
+
+            #[cfg(test)]
+            DECODED_DIMENSIONS.with(|dimensions| dimensions.set(dyn_image.dimensions()));
+
             self.image = ImageData::Image(dyn_image);
         }
 
         match &mut self.image {
             ImageData::Image(image) => Ok(image),
-            ImageData::File(_, _) => Err(FileError::UnknownError),
+            ImageData::File(_) => Err(FileError::UnknownError),
+        }
+    }
+
+    /// Gets the dimensions of the image as `(width, height)`
+    ///
+    /// This loads the image data into memory, if that has not happened yet.
+    ///
+    /// # Errors
+    /// Returns a `FileError` if an error occurs while loading the data from the disk
+    pub(crate) fn dimensions(&mut self) -> Result<(u32, u32), FileError> {
+        Ok(self.get_dyn_image()?.dimensions())
+    }
+
+    /// Gets the dimensions of the image as `(width, height)` without decoding the image data
+    ///
+    /// If the `ImageData` instance still refers to the file, only the header of the file is read.
+    /// Otherwise the dimensions of the image in memory are returned.
+    ///
+    /// # Errors
+    /// Returns a `FileError` if the header could not be read or is not supported
+    pub(crate) fn dimensions_without_decode(&self) -> Result<(u32, u32), FileError> {
+        match &self.image {
+            ImageData::File(format) => {
+                let file = File::open(&self.path)?;
+                let reader = Reader::with_format(BufReader::new(file), *format);
+                let dimensions = match reader.into_dimensions() {
+                    Ok(d) => d,
+                    Err(error) => {
+                        return match error {
+                            ImageError::Unsupported(_) => Err(FileError::NotSupported(
+                                FileNotSupportedError::new(self.path.clone()),
+                            )),
+                            ImageError::IoError(error) => Err(FileError::IoError(error)),
+                            _ => Err(FileError::UnknownError),
+                        }
+                    }
+                };
+
+                Ok(dimensions)
+            }
+            ImageData::Image(image) => Ok(image.dimensions()),
+        }
+    }
+
+    /// Turns into the `DynamicImage` stored inside the `ImageData` instance.
+    ///
+    /// If the dynamic image has not yet been loaded, it will be loaded first.
+    ///
+    /// # Errors
+    /// Returns a `FileError` if an error occurs while loading the data from the disk
+    pub(crate) fn into_dyn_image(mut self) -> Result<DynamicImage, FileError> {
+        self.get_dyn_image()?;
+
+        match self.image {
+            ImageData::Image(image) => Ok(image),
+            ImageData::File(_) => Err(FileError::UnknownError),
         }
     }
 
     /// Ensures the image data is in memory then clones the `ThumbnailData` instance
     ///
-    /// As `ImageData` initially only holds a file handle, cloning would be tricky,
-    /// as multiple instances of the same file handle could lead to weird situations.
+    /// As `ImageData` initially only refers to the file, cloning would decode the file once for every instance,
+    /// and the instances could see different files, if the file is changed in between.
     /// Therefore ThumbnailData only allows cloning if the image data is already in memory,
     /// to ensure that, this methods loads the data into memory before it clones.
     ///
@@ -155,10 +536,20 @@ impl ThumbnailData {
     /// Returns a `FileError` if an error occurs while loading the data from the disk
     pub fn try_clone_and_load(&mut self) -> Result<ThumbnailData, FileError> {
         let path = self.path.clone();
+        let format = self.format;
+        let from_file = self.from_file;
+        let loaded_from_path = self.loaded_from_path;
+        let exif = self.exif.clone();
+        let working_color = self.working_color;
         let image_data = self.get_dyn_image()?;
         Ok(ThumbnailData {
             path,
             image: ImageData::Image(image_data.clone()),
+            format,
+            from_file,
+            loaded_from_path,
+            exif,
+            working_color,
         })
     }
     /// Ensures that the image data is loaded into memory.
@@ -169,34 +560,605 @@ impl ThumbnailData {
     //     self.get_dyn_image().is_ok()
     // }
 
+    /// Gets the format of the image
+    ///
+    /// This is the format detected when the file was loaded, which is kept after decoding the image data.
+    /// For images created from a `DynamicImage` the format is inferred from the extension of the path.
+    pub(crate) fn format(&self) -> Option<ImageFormat> {
+        self.format
+    }
+
+    /// Gets the format of the file or the encoded data the image was loaded from
+    ///
+    /// Unlike `format()` this is never inferred from the path.
+    /// Returns `None` if the image was created from a `DynamicImage`.
+    pub fn source_format(&self) -> Option<ImageFormat> {
+        match self.from_file {
+            true => self.format,
+            false => None,
+        }
+    }
+
     /// Gets the original path of the image (from where it has been loaded)
     pub fn get_path(&self) -> PathBuf {
         self.path.clone()
     }
 
+    /// Reads the EXIF metadata of the file or the encoded data the image was loaded from
+    ///
+    /// Images decoded from encoded data have no file to read from, their metadata is kept in memory instead.
+    /// The path is only read from, if the image was loaded from that path.
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotFound` if the image was created from a `DynamicImage` or the file could not be found
+    /// Returns a `FileError::IoError` if an error occurred while reading the file
+    pub(crate) fn read_exif(&self) -> Result<ExifData, FileError> {
+        if self.loaded_from_path {
+            ExifData::read(&self.path)
+        } else if self.from_file {
+            Ok(self
+                .exif
+                .as_deref()
+                .map(ExifData::from_raw)
+                .unwrap_or_default())
+        } else {
+            Err(FileError::NotFound(FileNotFoundError {
+                path: self.path.clone(),
+            }))
+        }
+    }
+
+    /// Reads the raw EXIF metadata of the file or the encoded data the image was loaded from
+    ///
+    /// Returns `None`, if the image was created from a `DynamicImage`, or the source contains no EXIF metadata.
+    pub(crate) fn raw_exif(&self) -> Option<Vec<u8>> {
+        if self.loaded_from_path {
+            ExifData::read_raw(&self.path)
+        } else {
+            self.exif.clone()
+        }
+    }
+
     /// Takes a vector of `Operation` objects and applies each to the image.
     ///
     /// This passes the underlying `DynamicImage` to the `Operation::apply`
     /// method of each given `Operation` object.
     ///
+    /// If the image data has not been loaded yet, and the first operation does not need the image
+    /// in its full size (e.g. when scaling it down), JPEG images are decoded at a reduced scale.
+    /// See `Operation::min_source_dimensions()`.
+    ///
     /// # Errors
     /// Returns a `ApplyError` if a operation fails.
     pub(crate) fn apply_ops_list(
         &mut self,
         ops: &[Box<dyn Operation>],
     ) -> Result<&mut Self, ApplyError> {
-        if let Err(err) = self.get_dyn_image() {
-            return Err(ApplyError::LoadingImageError(err));
+        let image = self.get_dyn_image_for_ops(ops)?;
+        for operation in ops {
+            match operation.apply(image) {
+                Ok(_) => (),
+                Err(error) => return Err(ApplyError::OperationError(error)),
+            }
         }
+        Ok(self)
+    }
+
+    /// Sets the color type the image is converted to, before operations are applied to it
+    ///
+    /// * color: ColorType - The color type of the image while the operations are applied
+    pub(crate) fn set_working_color(&mut self, color: ColorType) {
+        self.working_color = Some(color);
+    }
+
+    /// Takes a vector of `Operation` objects and applies each to the image, continuing after failed operations
+    ///
+    /// Unlike `apply_ops_list()` a failing operation does not stop the remaining operations.
+    /// The image keeps the changes of all operations that succeeded.
+    ///
+    /// Returns the errors of all failed operations, in the order of the operations.
+    ///
+    /// # Errors
+    /// Returns a `ApplyError::LoadingImageError` if the image data could not be loaded.
+    pub(crate) fn apply_ops_list_lenient(
+        &mut self,
+        ops: &[Box<dyn Operation>],
+    ) -> Result<Vec<OperationError>, ApplyError> {
+        let image = self.get_dyn_image_for_ops(ops)?;
+        Ok(ops
+            .iter()
+            .filter_map(|operation| operation.apply(image).err())
+            .collect())
+    }
+
+    /// Loads the image data, before the given operations are applied
+    ///
+    /// If the image data has not been loaded yet, and the first operation does not need the image
+    /// in its full size, JPEG images are decoded at a reduced scale, and striped TIFF images are reduced while decoding.
+    /// If a working color type is set, the image is converted to it, unless it already has that color type.
+    ///
+    /// # Errors
+    /// Returns a `ApplyError::LoadingImageError` if the image data could not be loaded.
+    fn get_dyn_image_for_ops(
+        &mut self,
+        ops: &[Box<dyn Operation>],
+    ) -> Result<&mut DynamicImage, ApplyError> {
+        let min_dimensions = match (&self.image, ops.first()) {
+            (ImageData::File(ImageFormat::Jpeg), Some(op))
+            | (ImageData::File(ImageFormat::Tiff), Some(op)) => self
+                .dimensions_without_decode()
+                .ok()
+                .and_then(|dimensions| op.min_source_dimensions(dimensions)),
+            _ => None,
+        };
+
+        let working_color = self.working_color;
+        let image = self
+            .get_dyn_image_scaled(min_dimensions)
+            .map_err(ApplyError::LoadingImageError)?;
+
+        if let Some(color) = working_color.filter(|&color| color != image.color()) {
+            if let Some(converted) = convert_to_color_type(image, color) {
+                *image = converted;
+            }
+        }
+        Ok(image)
+    }
+}
+
+/// Converts the image to the given color type
+///
+/// Returns `None` for color types, that a `DynamicImage` cannot hold.
+///
+/// * image: &DynamicImage - The image to convert
+/// * color: ColorType - The color type to convert to
+fn convert_to_color_type(image: &DynamicImage, color: ColorType) -> Option<DynamicImage> {
+    match color {
+        ColorType::L8 => Some(DynamicImage::ImageLuma8(image.to_luma8())),
+        ColorType::La8 => Some(DynamicImage::ImageLumaA8(image.to_luma_alpha8())),
+        ColorType::Rgb8 => Some(DynamicImage::ImageRgb8(image.to_rgb8())),
+        ColorType::Rgba8 => Some(DynamicImage::ImageRgba8(image.to_rgba8())),
+        ColorType::Bgr8 => Some(DynamicImage::ImageBgr8(image.to_bgr8())),
+        ColorType::Bgra8 => Some(DynamicImage::ImageBgra8(image.to_bgra8())),
+        ColorType::L16 => Some(DynamicImage::ImageLuma16(image.to_luma16())),
+        ColorType::La16 => Some(DynamicImage::ImageLumaA16(image.to_luma_alpha16())),
+        ColorType::Rgb16 => Some(DynamicImage::ImageRgb16(image.to_rgb16())),
+        ColorType::Rgba16 => Some(DynamicImage::ImageRgba16(image.to_rgba16())),
+        _ => None,
+    }
+}
+
+/// Runs the given decoding function, catching a panic of the decoder
+///
+/// Decoders may panic on malformed or truncated image data, instead of returning an error.
+/// Catching the panic at the decode boundary keeps a single corrupt file from crashing the thread,
+/// e.g. the thread of a server handling an upload. The panic message is still printed by the panic hook.
+/// Returns `None` if the decoder panicked.
+///
+/// * decode: impl FnOnce() -> T - The decoding function
+pub(crate) fn catch_decoder_panic<T>(decode: impl FnOnce() -> T) -> Option<T> {
+    panic::catch_unwind(AssertUnwindSafe(decode)).ok()
+}
+
+/// Decodes a file with the first registered decoder, that can decode it
+///
+/// The decoders are asked with the first `DECODER_HEADER_LEN` bytes of the file.
+/// Returns `None` if no registered decoder can decode the file, the file is rewound to its start in that case.
+///
+/// * file: &mut File - The image file, at its start
+/// * path: &Path - The path of the file, used for error reporting
+fn decode_registered(file: &mut File, path: &Path) -> Result<Option<DynamicImage>, FileError> {
+    if !has_decoders() {
+        return Ok(None);
+    }
+
+    let mut bytes = vec![];
+    Read::by_ref(file)
+        .take(DECODER_HEADER_LEN as u64)
+        .read_to_end(&mut bytes)?;
+    let decoder = match find_decoder(&bytes) {
+        Some(decoder) => decoder,
+        None => {
+            file.seek(SeekFrom::Start(0))?;
+            return Ok(None);
+        }
+    };
+
+    file.read_to_end(&mut bytes)?;
+    match catch_decoder_panic(|| decoder.decode(&bytes)) {
+        Some(Ok(image)) => Ok(Some(image)),
+        Some(Err(error)) => Err(error),
+        None => Err(FileError::NotSupported(FileNotSupportedError::new(
+            path.to_path_buf(),
+        ))),
+    }
+}
+
+/// Decodes the image data of a file with the given format
+///
+/// * file: &File - The file, positioned at the start of the image data
+/// * format: ImageFormat - The format of the image
+fn decode_with_format(file: &File, format: ImageFormat) -> ImageResult<DynamicImage> {
+    let mut reader = Reader::new(BufReader::new(file));
+    reader.set_format(format);
+    reader.decode()
+}
+
+/// Decodes a striped TIFF image at a reduced size, reading one strip at a time
+///
+/// The image is reduced by the largest integer factor, that keeps it at least as large as the given dimensions.
+/// Each pixel of the result is the average of a block of pixels of the full size image,
+/// so only a single strip and the reduced image are held in memory.
+/// Returns `None` if the image can not be reduced this way, e.g. if it is not at least twice as large as the given
+/// dimensions, or its pixels do not have 8 bits per channel. The image has to be decoded in full size then.
+///
+/// * file: &File - The TIFF file, positioned at the start of the image data
+/// * min_width: u32 - The minimum width of the reduced image
+/// * min_height: u32 - The minimum height of the reduced image
+fn decode_tiff_scaled(file: &File, min_width: u32, min_height: u32) -> Option<DynamicImage> {
+    let mut decoder = TiffDecoder::new(BufReader::new(file)).ok()?;
+    let (width, height) = decoder.dimensions().ok()?;
+    let channels = match decoder.colortype().ok()? {
+        TiffColorType::Gray(8) => 1,
+        TiffColorType::GrayA(8) => 2,
+        TiffColorType::RGB(8) => 3,
+        TiffColorType::RGBA(8) => 4,
+        _ => return None,
+    };
+
+    let factor = (width / min_width.max(1)).min(height / min_height.max(1));
+    if factor < 2 {
+        return None;
+    }
+    #[allow(clippy::manual_div_ceil)] // div_ceil() needs Rust 1.73
+    let (reduced_width, reduced_height) = (
+        (width + factor - 1) / factor,
+        (height + factor - 1) / factor,
+    );
+
+    let row_length = width as usize * channels;
+    let mut sums = vec![0u64; reduced_width as usize * channels];
+    let mut buffer = Vec::with_capacity(sums.len() * reduced_height as usize);
+    let mut y = 0;
+    while y < height {
+        let strip = match decoder.read_strip().ok()? {
+            DecodingResult::U8(strip) => strip,
+            _ => return None,
+        };
+        if strip.len() < row_length {
+            return None;
+        }
+
+        for row in strip.chunks_exact(row_length).take((height - y) as usize) {
+            for (index, &value) in row.iter().enumerate() {
+                let (x, channel) = (index / channels, index % channels);
+                sums[(x / factor as usize) * channels + channel] += value as u64;
+            }
+            y += 1;
 
-        if let Ok(image) = &mut self.get_dyn_image() {
-            for operation in ops {
-                match operation.apply(image) {
-                    Ok(_) => (),
-                    Err(error) => return Err(ApplyError::OperationError(error)),
+            // The last row of a block, the blocks at the right and bottom edges may be smaller
+            if y % factor == 0 || y == height {
+                let block_height = ((y - 1) % factor + 1) as u64;
+                for (index, sum) in sums.iter_mut().enumerate() {
+                    let block_x = (index / channels) as u32 * factor;
+                    let count = factor.min(width - block_x) as u64 * block_height;
+                    buffer.push(((*sum + count / 2) / count) as u8);
+                    *sum = 0;
                 }
             }
         }
-        Ok(self)
+    }
+
+    match channels {
+        1 => {
+            GrayImage::from_raw(reduced_width, reduced_height, buffer).map(DynamicImage::ImageLuma8)
+        }
+        2 => GrayAlphaImage::from_raw(reduced_width, reduced_height, buffer)
+            .map(DynamicImage::ImageLumaA8),
+        3 => RgbImage::from_raw(reduced_width, reduced_height, buffer).map(DynamicImage::ImageRgb8),
+        _ => {
+            RgbaImage::from_raw(reduced_width, reduced_height, buffer).map(DynamicImage::ImageRgba8)
+        }
+    }
+}
+
+/// Decodes a WebP image with libwebp
+///
+/// Unlike the decoder of the image crate, this supports lossless WebP images and images with alpha channel.
+/// Returns `None` if the data could not be decoded.
+///
+/// * bytes: &[u8] - The encoded WebP image
+#[cfg(feature = "webp")]
+fn decode_webp(bytes: &[u8]) -> Option<DynamicImage> {
+    webp::Decoder::new(bytes)
+        .decode()
+        .map(|image| image.to_image())
+}
+
+/// The brands of the `ftyp` box, that identify HEIF images
+#[cfg(feature = "heif")]
+const HEIF_BRANDS: [&[u8; 4]; 8] = [
+    b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"mif1", b"msf1",
+];
+
+/// Decodes a HEIF image, e.g. a HEIC photo taken with an iPhone, with libheif
+///
+/// The image is detected by the major brand of its `ftyp` box. Only the primary image is decoded.
+/// Returns `None` if the data is not a HEIF image or could not be decoded.
+///
+/// * bytes: &[u8] - The encoded HEIF image
+#[cfg(feature = "heif")]
+fn decode_heif(bytes: &[u8]) -> Option<DynamicImage> {
+    use libheif_rs::{ColorSpace, HeifContext, LibHeif, RgbChroma};
+
+    if bytes.len() < 12
+        || &bytes[4..8] != b"ftyp"
+        || !HEIF_BRANDS.iter().any(|brand| &bytes[8..12] == *brand)
+    {
+        return None;
+    }
+
+    let context = HeifContext::read_from_bytes(bytes).ok()?;
+    let handle = context.primary_image_handle().ok()?;
+    let has_alpha = handle.has_alpha_channel();
+    let chroma = if has_alpha {
+        RgbChroma::Rgba
+    } else {
+        RgbChroma::Rgb
+    };
+    let image = LibHeif::new()
+        .decode(&handle, ColorSpace::Rgb(chroma), None)
+        .ok()?;
+
+    let plane = image.planes().interleaved?;
+    let (width, height) = (plane.width, plane.height);
+    let row_length = width as usize * if has_alpha { 4 } else { 3 };
+    // The rows of the plane may be padded, so they are copied one by one
+    let mut buffer = Vec::with_capacity(row_length * height as usize);
+    for row in plane.data.chunks(plane.stride).take(height as usize) {
+        buffer.extend_from_slice(&row[..row_length]);
+    }
+
+    if has_alpha {
+        image::RgbaImage::from_raw(width, height, buffer).map(DynamicImage::ImageRgba8)
+    } else {
+        image::RgbImage::from_raw(width, height, buffer).map(DynamicImage::ImageRgb8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{catch_decoder_panic, ThumbnailData, DECODED_DIMENSIONS};
+    use crate::errors::{ApplyError, FileError, OperationErrorInfo};
+    use crate::generic::{BoxPosition, Resize};
+    use crate::thumbnail::operations::{CombineOp, InvertOp, Operation, ResizeOp};
+    use crate::StaticThumbnail;
+    use image::{DynamicImage, GenericImageView, ImageFormat, ImageOutputFormat, Rgb, RgbImage};
+    use std::time::Instant;
+
+    #[test]
+    fn large_jpeg_is_decoded_scaled_for_small_thumbnail() {
+        let path = std::env::temp_dir().join("thumbnailer_test_scaled_decode/large.jpg");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let large = RgbImage::from_fn(3200, 2400, |x, y| {
+            Rgb([(x % 256) as u8, (y % 256) as u8, ((x + y) % 256) as u8])
+        });
+        DynamicImage::ImageRgb8(large)
+            .save_with_format(&path, ImageFormat::Jpeg)
+            .unwrap();
+
+        let ops: Vec<Box<dyn Operation>> =
+            vec![Box::new(ResizeOp::new(Resize::BoundingBox(200, 200), None))];
+
+        // decoding the full image, as it happens without a queued resize
+        let start = Instant::now();
+        let mut full = ThumbnailData::load(path.clone()).unwrap();
+        full.get_dyn_image().unwrap();
+        let full_duration = start.elapsed();
+        assert_eq!(DECODED_DIMENSIONS.with(|d| d.get()), (3200, 2400));
+
+        let start = Instant::now();
+        let mut scaled = ThumbnailData::load(path).unwrap();
+        scaled.apply_ops_list(&ops).ok().unwrap();
+        let scaled_duration = start.elapsed();
+
+        // 1/8 of the size is still larger than the thumbnail, so only 1/64 of the pixels are held in memory
+        assert_eq!(DECODED_DIMENSIONS.with(|d| d.get()), (400, 300));
+        assert!(scaled_duration < full_duration);
+
+        full.apply_ops_list(&ops).ok().unwrap();
+        assert_eq!(
+            scaled.get_dyn_image().unwrap().dimensions(),
+            full.get_dyn_image().unwrap().dimensions()
+        );
+    }
+
+    #[test]
+    fn jpeg_is_decoded_in_full_size_if_scale_is_too_small() {
+        let path = std::env::temp_dir().join("thumbnailer_test_scaled_decode/wide_box.jpg");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        DynamicImage::ImageRgb8(RgbImage::new(3200, 2400))
+            .save_with_format(&path, ImageFormat::Jpeg)
+            .unwrap();
+
+        // The decoder picks 1/8 as the height reaches 100, but 400 pixels are less than the width of 500
+        let ops: Vec<Box<dyn Operation>> =
+            vec![Box::new(ResizeOp::new(Resize::ExactBox(500, 100), None))];
+        let mut data = ThumbnailData::load(path).unwrap();
+        data.apply_ops_list(&ops).ok().unwrap();
+
+        assert_eq!(DECODED_DIMENSIONS.with(|d| d.get()), (3200, 2400));
+        assert_eq!(data.get_dyn_image().unwrap().dimensions(), (500, 100));
+    }
+
+    #[test]
+    fn failing_operation_surfaces_as_apply_error() {
+        let overlay = StaticThumbnail::from_dynamic_image(DynamicImage::new_rgb8(50, 50));
+        let ops: Vec<Box<dyn Operation>> = vec![
+            Box::new(InvertOp::new()),
+            Box::new(CombineOp::new_strict_bounds(
+                overlay,
+                BoxPosition::TopLeft(80, 80),
+            )),
+            Box::new(ResizeOp::new(Resize::ExactBox(10, 10), None)),
+        ];
+
+        let mut data =
+            ThumbnailData::from_dynamic_image("image.png", DynamicImage::new_rgb8(100, 100));
+        let error = match data.apply_ops_list(&ops) {
+            Err(ApplyError::OperationError(error)) => error,
+            _ => panic!("the failing operation was not reported"),
+        };
+
+        assert!(matches!(
+            error.get_info(),
+            OperationErrorInfo::CoordinatesOutOfRange
+        ));
+        let operation = format!("{:?}", error.get_operation());
+        assert!(operation.starts_with("CombineOp"));
+        assert!(operation.contains("strict_bounds: true"));
+
+        // the operations after the failing one are not applied
+        let image = data.get_dyn_image().unwrap();
+        assert_eq!(image.dimensions(), (100, 100));
+        assert_eq!(image.get_pixel(0, 0)[0], 255);
+    }
+
+    #[test]
+    fn striped_tiff_is_reduced_while_decoding() {
+        let path = std::env::temp_dir().join("thumbnailer_test_striped_tiff/large.tiff");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let large = RgbImage::from_fn(2000, 1500, |x, y| {
+            Rgb([(x / 8) as u8, (y / 6) as u8, ((x + y) % 256) as u8])
+        });
+        DynamicImage::ImageRgb8(large.clone())
+            .save_with_format(&path, ImageFormat::Tiff)
+            .unwrap();
+
+        let mut reduced = ThumbnailData::load(path.clone()).unwrap();
+        let dimensions = reduced
+            .get_dyn_image_scaled(Some((100, 75)))
+            .unwrap()
+            .dimensions();
+
+        // Reduced by a factor of 20
+        assert_eq!(dimensions, (100, 75));
+
+        // Each pixel is the average of a block of 20x20 pixels
+        let image = reduced.get_dyn_image().unwrap();
+        for &(x, y) in &[(0, 0), (50, 30), (99, 74)] {
+            let mut sums = [0u32; 3];
+            for block_y in y * 20..(y + 1) * 20 {
+                for block_x in x * 20..(x + 1) * 20 {
+                    for (sum, value) in sums
+                        .iter_mut()
+                        .zip(large.get_pixel(block_x, block_y).0.iter())
+                    {
+                        *sum += *value as u32;
+                    }
+                }
+            }
+            let expected: Vec<u8> = sums.iter().map(|sum| ((sum + 200) / 400) as u8).collect();
+            assert_eq!(image.get_pixel(x, y).0[..3], expected[..]);
+        }
+
+        // The first queued resize reduces the image, before it is resized to its final size
+        let ops: Vec<Box<dyn Operation>> = vec![Box::new(ResizeOp::new(Resize::Width(100), None))];
+        let mut data = ThumbnailData::load(path).unwrap();
+        data.apply_ops_list(&ops).ok().unwrap();
+        let (decoded_width, decoded_height) = DECODED_DIMENSIONS.with(|d| d.get());
+        assert!(decoded_width < 200 && decoded_height < 150);
+        assert!(decoded_width >= 100 && decoded_height >= 75);
+        assert_eq!(data.get_dyn_image().unwrap().width(), 100);
+    }
+
+    #[test]
+    fn lossy_webp_is_loaded_and_resized() {
+        let mut data = ThumbnailData::load("resources/tests/lossy.webp".into()).unwrap();
+        assert_eq!(data.format(), Some(ImageFormat::WebP));
+
+        let ops: Vec<Box<dyn Operation>> = vec![Box::new(ResizeOp::new(Resize::Width(30), None))];
+        assert!(data.apply_ops_list(&ops).is_ok());
+        assert_eq!(data.get_dyn_image().unwrap().width(), 30);
+    }
+
+    #[test]
+    #[cfg(feature = "webp")]
+    fn lossless_webp_with_alpha_is_loaded() {
+        let mut data = ThumbnailData::load("resources/tests/lossless.webp".into()).unwrap();
+        let image = data.get_dyn_image().unwrap();
+
+        assert_eq!(image.dimensions(), (4, 4));
+        assert_eq!(image.get_pixel(0, 0), image::Rgba([255, 0, 0, 255]));
+        assert_eq!(image.get_pixel(3, 3), image::Rgba([0, 0, 255, 128]));
+    }
+
+    #[test]
+    #[cfg(feature = "heif")]
+    fn heic_is_loaded_and_resized() {
+        let mut data = ThumbnailData::load("resources/tests/sample.heic".into()).unwrap();
+        assert_eq!(data.get_dyn_image().unwrap().dimensions(), (64, 64));
+
+        let ops: Vec<Box<dyn Operation>> = vec![Box::new(ResizeOp::new(Resize::Width(32), None))];
+        assert!(data.apply_ops_list(&ops).is_ok());
+        assert_eq!(data.get_dyn_image().unwrap().dimensions(), (32, 32));
+    }
+
+    #[test]
+    fn malformed_data_is_not_supported() {
+        let jpeg = std::fs::read("resources/tests/test.jpg").unwrap();
+        let gif = std::fs::read("resources/tests/animated.gif").unwrap();
+        let mut png = vec![];
+        DynamicImage::new_rgb8(50, 50)
+            .write_to(&mut png, ImageOutputFormat::Png)
+            .unwrap();
+        let mut gif_garbage = gif[..8].to_vec();
+        gif_garbage.extend((0..500u32).map(|n| (n * 7919 % 251) as u8));
+
+        let cases: Vec<(&str, &[u8])> = vec![
+            ("header.jpg", &jpeg[..8]),
+            ("truncated.jpg", &jpeg[..300]),
+            ("half.jpg", &jpeg[..jpeg.len() / 2]),
+            ("truncated.gif", &gif[..100]),
+            ("garbage.gif", &gif_garbage),
+            ("truncated.png", &png[..png.len() - 10]),
+            ("garbage.png", &b"\x89PNG\r\n\x1a\nnot really a png"[..]),
+            ("garbage.bin", &b"neither an image nor anything else"[..]),
+        ];
+
+        let dir = std::env::temp_dir().join("thumbnailer_test_malformed");
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, bytes) in cases {
+            let res = ThumbnailData::from_bytes(name, bytes);
+            assert!(matches!(res, Err(FileError::NotSupported(_))), "{}", name);
+
+            // Files are decoded, when the image data is needed
+            std::fs::write(dir.join(name), bytes).unwrap();
+            let res = ThumbnailData::load(dir.join(name)).and_then(|mut data| {
+                data.get_dyn_image()?;
+                Ok(data)
+            });
+            assert!(matches!(res, Err(FileError::NotSupported(_))), "{}", name);
+        }
+    }
+
+    #[test]
+    fn decoder_panic_is_caught() {
+        assert_eq!(catch_decoder_panic(|| 42), Some(42));
+        assert_eq!(
+            catch_decoder_panic(|| -> u32 { panic!("corrupt image data") }),
+            None
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "heif"))]
+    fn heic_is_not_supported_without_heif_feature() {
+        let res = ThumbnailData::load("resources/tests/sample.heic".into());
+        assert!(matches!(res, Err(FileError::NotSupported(_))));
+
+        let bytes = std::fs::read("resources/tests/sample.heic").unwrap();
+        let res = ThumbnailData::from_bytes("sample.heic", &bytes);
+        assert!(matches!(res, Err(FileError::NotSupported(_))));
     }
 }
@@ -0,0 +1,124 @@
+use crate::errors::FileError;
+use crate::thumbnail::data::catch_decoder_panic;
+use image::DynamicImage;
+use std::sync::{Arc, RwLock};
+
+/// The number of bytes at the start of a file, that decoders get to decide whether they can decode the file
+pub const DECODER_HEADER_LEN: usize = 64;
+
+/// The decoders registered with `register_decoder()`, in the order they were registered
+static DECODERS: RwLock<Vec<Arc<dyn InputDecoder>>> = RwLock::new(Vec::new());
+
+/// The `InputDecoder` trait. It decodes images in a file type that is not supported by this crate itself.
+///
+/// Implement it to load images e.g. as JPEG XL or HEIC without the crate depending on those libraries,
+/// and add it with `register_decoder()`. Registered decoders are asked before the built-in ones,
+/// so they can also replace the decoder of a supported file type.
+pub trait InputDecoder: Send + Sync {
+    /// Checks whether the decoder can decode the given data, usually by its magic bytes
+    ///
+    /// When a file is loaded, only its first bytes are given, up to `DECODER_HEADER_LEN` bytes.
+    /// If this panics, the decoder is skipped, as if it could not decode the data.
+    ///
+    /// * bytes: &[u8] - The start of the encoded image data
+    fn can_decode(&self, bytes: &[u8]) -> bool;
+
+    /// Decodes the given data
+    ///
+    /// * bytes: &[u8] - The complete encoded image data
+    fn decode(&self, bytes: &[u8]) -> Result<DynamicImage, FileError>;
+}
+
+/// Registers a decoder, that is used when loading images from now on
+///
+/// This affects `Thumbnail::load()` and `Thumbnail::from_bytes()`, as well as everything based on them,
+/// e.g. the images of a `ThumbnailCollection`. The first registered decoder, that can decode an image, is used.
+///
+/// * decoder: Arc<dyn InputDecoder> - The decoder
+///
+/// # Examples
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use image::{DynamicImage, GenericImageView, RgbImage};
+/// use thumbnailer::errors::{FileError, FileNotSupportedError};
+/// use thumbnailer::thumbnail::{register_decoder, InputDecoder};
+/// use thumbnailer::{GenericThumbnail, Thumbnail};
+///
+/// static DECODED: AtomicUsize = AtomicUsize::new(0);
+///
+/// /// Decodes binary PPM images with a maximum value of 255
+/// struct PpmDecoder;
+///
+/// impl InputDecoder for PpmDecoder {
+///     fn can_decode(&self, bytes: &[u8]) -> bool {
+///         bytes.starts_with(b"P6")
+///     }
+///
+///     fn decode(&self, bytes: &[u8]) -> Result<DynamicImage, FileError> {
+///         let not_supported = || FileError::NotSupported(FileNotSupportedError::new("image.ppm".into()));
+///         // The magic bytes, width, height and maximum value are followed by the pixels
+///         let fields: Vec<&[u8]> = bytes.splitn(5, |b| b.is_ascii_whitespace()).collect();
+///         if fields.len() < 5 {
+///             return Err(not_supported());
+///         }
+///         let number = |field: &[u8]| -> Result<u32, FileError> {
+///             std::str::from_utf8(field).ok().and_then(|s| s.parse().ok()).ok_or_else(not_supported)
+///         };
+///         let (width, height) = (number(fields[1])?, number(fields[2])?);
+///         let pixels = fields[4].to_vec();
+///
+///         DECODED.fetch_add(1, Ordering::SeqCst);
+///         let image = RgbImage::from_raw(width, height, pixels).ok_or_else(not_supported)?;
+///         Ok(DynamicImage::ImageRgb8(image))
+///     }
+/// }
+///
+/// register_decoder(Arc::new(PpmDecoder));
+///
+/// let mut ppm = b"P6\n3 2\n255\n".to_vec();
+/// ppm.extend_from_slice(&[255, 0, 0].repeat(6));
+///
+/// let thumb = Thumbnail::from_bytes("image.ppm", &ppm).unwrap();
+/// let image = thumb.into_dynamic_image().ok().unwrap();
+/// assert_eq!(image.dimensions(), (3, 2));
+/// assert_eq!(image.get_pixel(2, 1).0, [255, 0, 0, 255]);
+/// assert_eq!(DECODED.load(Ordering::SeqCst), 1);
+///
+/// // Files are dispatched by their first bytes as well
+/// let path = std::env::temp_dir().join("thumbnailer_doc_decoder.ppm");
+/// std::fs::write(&path, &ppm).unwrap();
+/// let thumb = Thumbnail::load(path).unwrap();
+/// assert_eq!(thumb.into_dynamic_image().ok().unwrap().dimensions(), (3, 2));
+/// assert_eq!(DECODED.load(Ordering::SeqCst), 2);
+/// ```
+pub fn register_decoder(decoder: Arc<dyn InputDecoder>) {
+    match DECODERS.write() {
+        Ok(mut decoders) => decoders.push(decoder),
+        Err(poisoned) => poisoned.into_inner().push(decoder),
+    }
+}
+
+/// Checks whether any decoder has been registered
+pub(crate) fn has_decoders() -> bool {
+    match DECODERS.read() {
+        Ok(decoders) => !decoders.is_empty(),
+        Err(poisoned) => !poisoned.into_inner().is_empty(),
+    }
+}
+
+/// Gets the first registered decoder, that can decode the given data
+///
+/// A decoder, whose `can_decode()` panics on the data, is skipped.
+///
+/// * bytes: &[u8] - The start of the encoded image data
+pub(crate) fn find_decoder(bytes: &[u8]) -> Option<Arc<dyn InputDecoder>> {
+    let decoders = match DECODERS.read() {
+        Ok(decoders) => decoders,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    decoders
+        .iter()
+        .find(|decoder| catch_decoder_panic(|| decoder.can_decode(bytes)).unwrap_or(false))
+        .cloned()
+}
@@ -0,0 +1,267 @@
+use crate::errors::{FileError, FileNotFoundError};
+use crate::thumbnail::operations::{FlipOp, Operation, RotateOp};
+use crate::{Orientation, Rotation};
+use exif::{In, Reader, Tag, Value};
+use image::DynamicImage;
+use std::fs::File;
+use std::io::{BufReader, Cursor};
+use std::path::Path;
+
+/// The `ExifData` type. Holds the EXIF metadata read from an image file.
+///
+/// Every field is `None`, if the file does not contain the corresponding EXIF field,
+/// or if the file contains no EXIF metadata at all.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExifData {
+    /// The orientation of the image, as defined by the EXIF standard (1 to 8)
+    pub orientation: Option<u16>,
+    /// The date and time the image was captured, in the EXIF format `YYYY:MM:DD HH:MM:SS`.
+    /// The format sorts chronologically, when compared as string.
+    pub date_time: Option<String>,
+    /// The manufacturer of the camera
+    pub make: Option<String>,
+    /// The model of the camera
+    pub model: Option<String>,
+    /// The GPS coordinates as `(latitude, longitude)` in degrees.
+    /// Southern latitudes and western longitudes are negative.
+    pub gps: Option<(f64, f64)>,
+}
+
+impl ExifData {
+    /// Reads the EXIF metadata from the image file at the given path
+    ///
+    /// The metadata is read from the APP1 segment of JPEG files, the eXIf chunk of PNG files,
+    /// and the EXIF metadata of TIFF, HEIF and WebP files.
+    /// Returns an empty `ExifData`, if the file type does not support EXIF or the file contains no EXIF metadata.
+    ///
+    /// * path: &Path - The path to the image file
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotFound` if the file could not be found
+    /// Returns a `FileError::IoError` if an error occurred while reading the file
+    pub(crate) fn read(path: &Path) -> Result<ExifData, FileError> {
+        if !path.is_file() {
+            return Err(FileError::NotFound(FileNotFoundError {
+                path: path.to_path_buf(),
+            }));
+        }
+
+        let mut reader = BufReader::new(File::open(path)?);
+        match Reader::new().read_from_container(&mut reader) {
+            Ok(exif) => Ok(ExifData::from_exif(&exif)),
+            Err(exif::Error::Io(error)) => Err(FileError::IoError(error)),
+            Err(_) => Ok(ExifData::default()),
+        }
+    }
+
+    /// Parses raw EXIF metadata, as returned by `read_raw()` and `read_raw_bytes()`
+    ///
+    /// Returns an empty `ExifData`, if the metadata can not be parsed.
+    ///
+    /// * raw: &[u8] - The TIFF structure of the metadata
+    pub(crate) fn from_raw(raw: &[u8]) -> ExifData {
+        match Reader::new().read_raw(raw.to_vec()) {
+            Ok(exif) => ExifData::from_exif(&exif),
+            Err(_) => ExifData::default(),
+        }
+    }
+
+    /// Gets the supported fields of the parsed EXIF metadata
+    fn from_exif(exif: &exif::Exif) -> ExifData {
+        let field = |tag: Tag| exif.get_field(tag, In::PRIMARY).map(|field| &field.value);
+
+        let date_time = field(Tag::DateTimeOriginal)
+            .or_else(|| field(Tag::DateTime))
+            .and_then(ascii_value);
+
+        let gps = match (
+            field(Tag::GPSLatitude).and_then(degrees_value),
+            field(Tag::GPSLongitude).and_then(degrees_value),
+        ) {
+            (Some(latitude), Some(longitude)) => {
+                let is_ref = |tag: Tag, negative: &str| {
+                    field(tag).and_then(ascii_value).as_deref() == Some(negative)
+                };
+                Some((
+                    if is_ref(Tag::GPSLatitudeRef, "S") {
+                        -latitude
+                    } else {
+                        latitude
+                    },
+                    if is_ref(Tag::GPSLongitudeRef, "W") {
+                        -longitude
+                    } else {
+                        longitude
+                    },
+                ))
+            }
+            _ => None,
+        };
+
+        ExifData {
+            orientation: field(Tag::Orientation)
+                .and_then(|value| value.get_uint(0))
+                .map(|orientation| orientation as u16),
+            date_time,
+            make: field(Tag::Make).and_then(ascii_value),
+            model: field(Tag::Model).and_then(ascii_value),
+            gps,
+        }
+    }
+
+    /// Reads the raw EXIF metadata from the image file at the given path
+    ///
+    /// Returns the TIFF structure of the metadata, as it is stored in the APP1 segment of a JPEG file.
+    /// Returns `None`, if the file can not be read or contains no EXIF metadata.
+    ///
+    /// * path: &Path - The path to the image file
+    pub(crate) fn read_raw(path: &Path) -> Option<Vec<u8>> {
+        let mut reader = BufReader::new(File::open(path).ok()?);
+        let exif = Reader::new().read_from_container(&mut reader).ok()?;
+        Some(exif.buf().to_vec())
+    }
+
+    /// Reads the raw EXIF metadata from encoded image data, like `read_raw()` does from a file
+    ///
+    /// Returns `None`, if the data contains no EXIF metadata.
+    ///
+    /// * bytes: &[u8] - The encoded image data
+    pub(crate) fn read_raw_bytes(bytes: &[u8]) -> Option<Vec<u8>> {
+        let exif = Reader::new()
+            .read_from_container(&mut Cursor::new(bytes))
+            .ok()?;
+        Some(exif.buf().to_vec())
+    }
+
+    /// Gets the operations that turn the image upright, according to the EXIF orientation
+    ///
+    /// Returns no operations, if the orientation is missing, invalid, or the image already is upright.
+    pub(crate) fn orientation_ops(&self) -> Vec<Box<dyn Operation>> {
+        self.orientation.map(orientation_ops).unwrap_or_default()
+    }
+}
+
+/// Turns the image upright, according to the given EXIF orientation
+///
+/// The eight orientations of the EXIF standard are mapped to the matching flips and rotations.
+/// This is the same mapping `ThumbnailCollection::auto_orient_all()` uses,
+/// for images whose EXIF metadata has been read elsewhere.
+/// The orientation 1 and invalid values (0 or larger than 8) leave the image unchanged.
+///
+/// * image: &mut DynamicImage - The image, as it is stored in the file
+/// * orientation: u8 - The EXIF orientation of the image
+///
+/// # Examples
+/// ```
+/// use thumbnailer::thumbnail::apply_exif_orientation;
+/// use image::{DynamicImage, GenericImageView};
+///
+/// // orientation 6: the camera was turned clockwise, the image needs to be turned clockwise as well
+/// let mut image = DynamicImage::new_rgb8(48, 64);
+/// apply_exif_orientation(&mut image, 6);
+/// assert_eq!(image.dimensions(), (64, 48));
+/// ```
+pub fn apply_exif_orientation(image: &mut DynamicImage, orientation: u8) {
+    for operation in orientation_ops(orientation as u16) {
+        // flipping and rotating never fails
+        let _ = operation.apply(image);
+    }
+}
+
+/// Gets the operations that turn the image upright, according to the EXIF orientation
+///
+/// Returns no operations, if the orientation is invalid, or the image already is upright.
+fn orientation_ops(orientation: u16) -> Vec<Box<dyn Operation>> {
+    let rotate = |rotation: Rotation| -> Box<dyn Operation> { Box::new(RotateOp::new(rotation)) };
+    let flip = || -> Box<dyn Operation> { Box::new(FlipOp::new(Orientation::Horizontal)) };
+
+    match orientation {
+        2 => vec![flip()],
+        3 => vec![rotate(Rotation::Rotate180)],
+        4 => vec![Box::new(FlipOp::new(Orientation::Vertical))],
+        // transposed: mirrored along the diagonal from top-left to bottom-right
+        5 => vec![rotate(Rotation::Rotate90), flip()],
+        6 => vec![rotate(Rotation::Rotate90)],
+        // transversed: mirrored along the diagonal from top-right to bottom-left
+        7 => vec![rotate(Rotation::Rotate270), flip()],
+        8 => vec![rotate(Rotation::Rotate270)],
+        _ => vec![],
+    }
+}
+
+/// Gets the first string of an ASCII value, without trailing spaces
+fn ascii_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Ascii(strings) => strings
+            .first()
+            .map(|string| String::from_utf8_lossy(string).trim_end().to_string()),
+        _ => None,
+    }
+}
+
+/// Converts a value of degrees, minutes and seconds to degrees
+fn degrees_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::Rational(parts) if parts.len() == 3 => {
+            Some(parts[0].to_f64() + parts[1].to_f64() / 60.0 + parts[2].to_f64() / 3600.0)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::apply_exif_orientation;
+    use image::{DynamicImage, GrayImage, Luma};
+
+    const WIDTH: u32 = 3;
+    const HEIGHT: u32 = 2;
+
+    /// An upright image, in which every pixel has a distinct value
+    fn upright() -> GrayImage {
+        GrayImage::from_fn(WIDTH, HEIGHT, |x, y| Luma([(x + WIDTH * y) as u8]))
+    }
+
+    /// Creates the image as it is stored with the given orientation, following the EXIF definitions
+    ///
+    /// The mapping returns the position in the upright image, of each position in the stored image.
+    fn stored(orientation: u8) -> GrayImage {
+        let (w, h) = (WIDTH - 1, HEIGHT - 1);
+        let transposed = (5..=8).contains(&orientation);
+        let (width, height) = if transposed {
+            (HEIGHT, WIDTH)
+        } else {
+            (WIDTH, HEIGHT)
+        };
+        let upright = upright();
+        GrayImage::from_fn(width, height, |x, y| {
+            let (ux, uy) = match orientation {
+                // the 0th row is at the top, the 0th column on the right
+                2 => (w - x, y),
+                // the 0th row is at the bottom, the 0th column on the right
+                3 => (w - x, h - y),
+                // the 0th row is at the bottom, the 0th column on the left
+                4 => (x, h - y),
+                // the 0th row is on the left, the 0th column at the top
+                5 => (y, x),
+                // the 0th row is on the right, the 0th column at the top
+                6 => (w - y, x),
+                // the 0th row is on the right, the 0th column at the bottom
+                7 => (w - y, h - x),
+                // the 0th row is on the left, the 0th column at the bottom
+                8 => (y, h - x),
+                _ => (x, y),
+            };
+            *upright.get_pixel(ux, uy)
+        })
+    }
+
+    #[test]
+    fn apply_exif_orientation_turns_all_orientations_upright() {
+        for orientation in 0..=9 {
+            let mut image = DynamicImage::ImageLuma8(stored(orientation));
+            apply_exif_orientation(&mut image, orientation);
+            assert_eq!(image.to_luma8(), upright(), "orientation {}", orientation);
+        }
+    }
+}
@@ -1,22 +1,43 @@
-use crate::errors::ApplyError;
+#[cfg(feature = "remote")]
+use crate::errors::FileNotFoundError;
+use crate::errors::{ApplyError, OperationError};
 use crate::generic::OperationContainer;
+use crate::target::{self, TargetFormat};
 use crate::thumbnail::data::ThumbnailData;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::ResizeOp;
 use crate::{
-    errors::FileError, generic::GenericThumbnail, thumbnail::operations::Operation, Target,
+    errors::FileError, generic::GenericThumbnail, thumbnail::operations::Operation, Resize, Target,
 };
 use image::io::Reader;
-use image::DynamicImage;
+use image::{ColorType, DynamicImage, GenericImageView, ImageFormat};
+#[cfg(feature = "remote")]
+use std::io::{self, Read};
+use std::io::{Cursor, Seek, Write};
 use std::path::Path;
 use std::path::PathBuf;
 
+pub mod animated;
+pub mod builder;
 pub mod collection;
 pub mod data;
+pub mod decoder;
+pub mod metadata;
 pub mod operations;
 pub mod static_thumb;
 
+pub use animated::AnimatedThumbnail;
+pub use builder::{ThumbnailBuilder, ThumbnailPipeline};
 pub use collection::ThumbnailCollection;
 pub use collection::ThumbnailCollectionBuilder;
-pub use static_thumb::StaticThumbnail;
+pub use decoder::{register_decoder, InputDecoder};
+pub use metadata::{apply_exif_orientation, ExifData};
+pub use static_thumb::{hamming_distance, StaticThumbnail};
+
+/// The largest image in bytes, that `Thumbnail::load_url()` downloads
+#[cfg(feature = "remote")]
+pub const MAX_URL_BYTES: u64 = 100 * 1024 * 1024;
 
 /// The `Thumbnail` type
 ///
@@ -42,8 +63,10 @@ impl Thumbnail {
     /// It it is then tried to determine the the format of the file, first by using the file extension
     /// or if that fails by actually looking into the file.
     ///
-    /// If the file is found, a file handle is opened and store in the `Thumbnail` instance.
-    /// The actual binary data is not yet loaded into memory. This happens when the operations are applied to the image.
+    /// If the file is found, its format is stored in the `Thumbnail` instance, the file is not kept open.
+    /// The actual binary data is not yet loaded into memory. This happens when the operations are applied to the image,
+    /// the file is opened again then.
+    /// Files that a decoder registered with `register_decoder()` can decode are decoded immediately instead.
     ///
     /// # Errors
     /// Can return a `FileError::NotFound` if the file could not be found
@@ -67,6 +90,210 @@ impl Thumbnail {
         })
     }
 
+    /// Creates a new `Thumbnail` from the image at the given path, with a resize operation already queued
+    ///
+    /// This is the same as calling `load()` followed by `resize()`.
+    /// Further operations can be queued, they are applied after the resize operation.
+    ///
+    /// * path: PathBuf - The path to the image file
+    /// * size: Resize - The size of the thumbnail
+    ///
+    /// # Errors
+    /// The same errors as `load()`
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let thumb = Thumbnail::thumbnail(Path::new("resources/tests/test.jpg").to_path_buf(), Resize::Width(100)).unwrap();
+    /// assert_eq!(thumb.queued_ops(), 1);
+    ///
+    /// let image = thumb.into_dynamic_image().ok().unwrap();
+    /// assert_eq!(image.width(), 100);
+    /// ```
+    #[allow(clippy::self_named_constructors)]
+    pub fn thumbnail(path: PathBuf, size: Resize) -> Result<Thumbnail, FileError> {
+        let mut thumb = Thumbnail::load(path)?;
+        thumb.add_op(Box::new(ResizeOp::new(size, None)));
+        Ok(thumb)
+    }
+
+    /// Creates a new `Thumbnail` from the given path, using the given format to decode the image
+    ///
+    /// Unlike `load()` the format is not guessed from the extension or the content of the file.
+    /// This allows to load files with a wrong or missing extension.
+    /// As with `load()` the image data is only decoded when it's being used.
+    ///
+    /// * path: PathBuf - The path to the image file
+    /// * format: ImageFormat - The format that is used to decode the image
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotFound` if the file could not be found
+    /// Can return a `FileError::IoError` if an error occurred while accessing the file
+    ///
+    /// Decoding the image data later on returns a `FileError::NotSupported`,
+    /// if the file could not be decoded with the given format.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::ImageFormat;
+    /// use thumbnailer::errors::FileError;
+    /// use thumbnailer::Thumbnail;
+    ///
+    /// // A PNG file with a misleading extension
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_load_with_format/image.dat");
+    /// std::fs::create_dir_all(dst.parent().unwrap()).unwrap();
+    /// image::open("resources/tests/test.jpg").unwrap().save_with_format(&dst, ImageFormat::Png).unwrap();
+    ///
+    /// let mut thumb = Thumbnail::load_with_format(dst.clone(), ImageFormat::Png).unwrap();
+    /// assert_eq!(thumb.format(), Some(ImageFormat::Png));
+    /// assert_eq!(thumb.dimensions().unwrap(), (500, 138));
+    ///
+    /// let thumb = Thumbnail::load_with_format(dst, ImageFormat::Gif).unwrap();
+    /// assert!(matches!(thumb.to_dynamic_image(), Err(FileError::NotSupported(_))));
+    /// ```
+    pub fn load_with_format(path: PathBuf, format: ImageFormat) -> Result<Thumbnail, FileError> {
+        Ok(Thumbnail {
+            data: ThumbnailData::load_with_format(path, format)?,
+            ops: vec![],
+        })
+    }
+
+    /// Creates a new `Thumbnail` from a single frame of the animated image at the given path
+    ///
+    /// Supported are animated GIF and PNG (APNG) files. The frame is decoded immediately,
+    /// and is used as the image of the `Thumbnail`. All other frames are discarded.
+    ///
+    /// * path: PathBuf - The path to the image file
+    /// * frame_index: usize - The index of the frame, starting at 0
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotFound` if the file could not be found
+    /// Can return a `FileError::NotSupported` if the file is not an animated image of a supported type
+    /// Can return a `FileError::FrameNotFound` if the image has no frame with the given index
+    /// Can return a `FileError::IoError` if an error occurred while accessing the file
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::Thumbnail;
+    ///
+    /// let path = Path::new("resources/tests/animated.gif").to_path_buf();
+    /// let mut first = Thumbnail::load_frame(path.clone(), 0).unwrap();
+    /// let mut third = Thumbnail::load_frame(path.clone(), 2).unwrap();
+    ///
+    /// let first_pixel = first.clone_static_copy().unwrap().as_dyn().get_pixel(0, 0);
+    /// let third_pixel = third.clone_static_copy().unwrap().as_dyn().get_pixel(0, 0);
+    /// assert_ne!(first_pixel, third_pixel);
+    ///
+    /// assert!(Thumbnail::load_frame(path, 3).is_err());
+    /// ```
+    pub fn load_frame(path: PathBuf, frame_index: usize) -> Result<Thumbnail, FileError> {
+        Ok(Thumbnail {
+            data: ThumbnailData::load_frame(path, frame_index)?,
+            ops: vec![],
+        })
+    }
+
+    /// Creates a new `AnimatedThumbnail` from the animated GIF at the given path
+    ///
+    /// Unlike a `Thumbnail`, which only holds a single image, an `AnimatedThumbnail` keeps all frames of the animation.
+    /// Operations are applied to each frame, and the frames are stored as animated GIF with their original delays.
+    ///
+    /// * path: PathBuf - The path to the GIF file
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotFound` if the file could not be found
+    /// Can return a `FileError::NotSupported` if the file is not a GIF file or could not be decoded
+    /// Can return a `FileError::IoError` if an error occurred while accessing the file
+    ///
+    /// # Examples
+    /// ```
+    /// use std::fs::File;
+    /// use std::path::Path;
+    /// use image::codecs::gif::GifDecoder;
+    /// use image::AnimationDecoder;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let path = Path::new("resources/tests/animated.gif").to_path_buf();
+    /// let mut animated = Thumbnail::load_animated(path).unwrap();
+    /// assert_eq!(animated.frame_count(), 3);
+    ///
+    /// animated.resize(Resize::ExactBox(32, 24), None);
+    /// animated.apply().ok().unwrap();
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_animated/resized.gif");
+    /// let dst = animated.store(dst).unwrap();
+    ///
+    /// let resized = Thumbnail::load_animated(dst.clone()).unwrap();
+    /// assert_eq!(resized.frame_count(), 3);
+    /// assert_eq!(resized.dimensions(), (32, 24));
+    ///
+    /// // the delays of the frames are preserved
+    /// let frames = GifDecoder::new(File::open(dst).unwrap()).unwrap().into_frames().collect_frames().unwrap();
+    /// let delays: Vec<(u32, u32)> = frames.iter().map(|f| f.delay().numer_denom_ms()).collect();
+    /// assert_eq!(delays, vec![(100, 1), (200, 1), (300, 1)]);
+    /// ```
+    pub fn load_animated(path: PathBuf) -> Result<AnimatedThumbnail, FileError> {
+        AnimatedThumbnail::load(path)
+    }
+
+    /// Resizes each frame of the animated GIF at the given path, and stores the result to the target
+    ///
+    /// This is the same as calling `load_animated()`, followed by `resize()` and `apply()` on the `AnimatedThumbnail`.
+    /// GIF items of the target, and items keeping the original file type, are stored as animated GIF
+    /// with the original delays of the frames. Other file types can not hold an animation, they get the first frame.
+    ///
+    /// The frames are decoded onto the full canvas of the animation, so how a frame was disposed of
+    /// is already part of the following frames, and the result looks the same as the original.
+    ///
+    /// * path: PathBuf - The path to the GIF file
+    /// * size: Resize - The size of the frames
+    /// * target: &Target - The target the animation is stored to
+    ///
+    /// Returns the paths of the stored files.
+    ///
+    /// # Errors
+    /// Can return a `ApplyError::LoadingImageError` if the file could not be found or is not a GIF file
+    /// Can return a `ApplyError::OperationError` if resizing a frame failed
+    /// Can return a `ApplyError::StoreError` if the animation could not be stored
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{Resize, Target, Thumbnail};
+    ///
+    /// let path = Path::new("resources/tests/animated.gif").to_path_buf();
+    /// let dir = std::env::temp_dir().join("thumbnailer_doc_resize_animated");
+    /// let target = Target::new(TargetFormat::Gif, dir.join("small.gif"))
+    ///     .add_target(TargetFormat::Png, dir.join("poster.png"));
+    ///
+    /// let paths = Thumbnail::resize_animated(path, Resize::BoundingBox(32, 32), &target).ok().unwrap();
+    ///
+    /// let resized = Thumbnail::load_animated(paths[0].clone()).unwrap();
+    /// assert_eq!(resized.frame_count(), 3);
+    /// assert_eq!(resized.dimensions(), (32, 24));
+    ///
+    /// assert_eq!(image::open(&paths[1]).unwrap().dimensions(), (32, 24));
+    /// ```
+    pub fn resize_animated(
+        path: PathBuf,
+        size: Resize,
+        target: &Target,
+    ) -> Result<Vec<PathBuf>, ApplyError> {
+        let mut animated = AnimatedThumbnail::load(path).map_err(ApplyError::LoadingImageError)?;
+        animated.resize(size, None).apply()?;
+        target
+            .store_animated(&animated)
+            .map_err(ApplyError::StoreError)
+    }
+
     /// This function creates and returns a new `Thumbnail` from an existing DynamicImage.
     ///
     /// # Arguments
@@ -84,16 +311,623 @@ impl Thumbnail {
         }
     }
 
+    /// Creates a new `Thumbnail` by decoding the given encoded image data
+    ///
+    /// This allows to create thumbnails e.g. from uploaded files, without storing them to disk first.
+    /// The format is guessed from the content of the data, see `source_format()`.
+    /// Unlike `load()`, the image data is decoded immediately.
+    /// Decoders registered with `register_decoder()` are asked first, see `InputDecoder`.
+    ///
+    /// * path_name: &str - A custom path for the new `Thumbnail`, used to name the stored files.
+    ///   Nothing is read from that path.
+    /// * bytes: &[u8] - The encoded image data
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotSupported` if the format is not supported or the data could not be decoded
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{GenericImageView, ImageFormat};
+    /// use thumbnailer::Thumbnail;
+    ///
+    /// let bytes = std::fs::read("resources/tests/test.jpg").unwrap();
+    /// let mut thumb = Thumbnail::from_bytes("upload", &bytes).unwrap();
+    ///
+    /// assert_eq!(thumb.source_format(), Some(ImageFormat::Jpeg));
+    /// assert_eq!(thumb.dimensions().unwrap(), (500, 138));
+    ///
+    /// assert!(Thumbnail::from_bytes("upload", b"not an image").is_err());
+    /// ```
+    pub fn from_bytes(path_name: &str, bytes: &[u8]) -> Result<Thumbnail, FileError> {
+        Ok(Thumbnail {
+            data: ThumbnailData::from_bytes(path_name, bytes)?,
+            ops: vec![],
+        })
+    }
+
+    /// Creates a new `Thumbnail` from an image at a HTTP(S) URL
+    ///
+    /// The image is downloaded to memory and decoded like with `from_bytes()`.
+    /// The last segment of the URL is used as the path of the new `Thumbnail`, to name the stored files.
+    /// Only available with the `remote` feature.
+    ///
+    /// * url: &str - The URL of the image
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotFound` if the server responded with the status 404 or 410
+    /// Can return a `FileError::NotSupported` if the format is not supported or the data could not be decoded
+    /// Can return a `FileError::IoError` if the request failed, e.g. because the server could not be reached,
+    /// if the server responded with another error status, or if the image is larger than `MAX_URL_BYTES`
+    #[cfg(feature = "remote")]
+    pub fn load_url(url: &str) -> Result<Thumbnail, FileError> {
+        let bytes = download(url, MAX_URL_BYTES)?;
+
+        let path_name = url
+            .split(['?', '#'])
+            .next()
+            .and_then(|path| path.rsplit('/').next())
+            .filter(|name| !name.is_empty())
+            .unwrap_or("remote");
+        Thumbnail::from_bytes(path_name, &bytes)
+    }
+
     /// Turns into the internal `ThumbnailData` struct
     pub fn into_data(self) -> ThumbnailData {
         self.data
     }
 
+    /// Turns into the `DynamicImage` holding the image data
+    ///
+    /// This loads the actual image data to memory, if that has not happened yet.
+    /// Queued operations are not applied, use `apply_to_dynamic_image()` for that.
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotSupported` if the file could not be loaded to memory
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{DynamicImage, GenericImageView};
+    /// use thumbnailer::Thumbnail;
+    ///
+    /// let thumb = Thumbnail::from_dynamic_image("test.jpg", DynamicImage::new_rgb8(800, 500));
+    /// let image = thumb.to_dynamic_image().unwrap();
+    ///
+    /// assert_eq!(image.dimensions(), (800, 500));
+    /// ```
+    pub fn to_dynamic_image(self) -> Result<DynamicImage, FileError> {
+        self.data.into_dyn_image()
+    }
+
+    /// Applies the queued operations and turns into the `DynamicImage` holding the resulting image data
+    ///
+    /// # Errors
+    /// Can return a `ApplyError::LoadingImageError` if the file could not be loaded to memory
+    /// Can return a `ApplyError::OperationError` if an operation failed
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::BoundingBox(250, 250));
+    /// let image = thumb.into_dynamic_image().ok().unwrap();
+    ///
+    /// assert_eq!(image.dimensions(), (250, 69));
+    /// ```
+    pub fn into_dynamic_image(mut self) -> Result<DynamicImage, ApplyError> {
+        self.apply()?;
+
+        match self.data.into_dyn_image() {
+            Ok(image) => Ok(image),
+            Err(err) => Err(ApplyError::LoadingImageError(err)),
+        }
+    }
+
+    /// Applies the queued operations and turns into the `DynamicImage` holding the resulting image data
+    ///
+    /// This is the same as `into_dynamic_image()`.
+    ///
+    /// # Errors
+    /// Can return a `ApplyError::LoadingImageError` if the file could not be loaded to memory
+    /// Can return a `ApplyError::OperationError` if an operation failed
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{DynamicImage, GenericImageView};
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let mut thumb = Thumbnail::from_dynamic_image("test.jpg", DynamicImage::new_rgb8(800, 500));
+    /// thumb.resize(Resize::ExactBox(400, 250));
+    /// let image = thumb.apply_to_dynamic_image().ok().unwrap();
+    ///
+    /// assert_eq!(image.dimensions(), (400, 250));
+    /// ```
+    pub fn apply_to_dynamic_image(self) -> Result<DynamicImage, ApplyError> {
+        self.into_dynamic_image()
+    }
+
+    /// Applies the queued operations, continuing after failed operations, and clears the queue
+    ///
+    /// Unlike `apply()`, a failing operation does not stop the remaining operations.
+    /// This helps batch pipelines, where e.g. a text that can not be drawn should not prevent a resize.
+    /// The image keeps the changes of all operations that succeeded.
+    ///
+    /// Returns the errors of all failed operations, in the order they were queued.
+    ///
+    /// # Errors
+    /// Can return a `ApplyError::LoadingImageError` if the file could not be loaded to memory.
+    /// In that case no operation is applied.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{BoxPosition, Resize, Thumbnail};
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// // the text would end left of the image, drawing it fails
+    /// thumb.text("Thumbnailer".to_string(), BoxPosition::TopRight(0, 0));
+    /// thumb.resize(Resize::Width(100));
+    ///
+    /// let errors = thumb.apply_lenient().ok().unwrap();
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(thumb.queued_ops(), 0);
+    ///
+    /// let image = thumb.to_dynamic_image().unwrap();
+    /// assert_eq!(image.width(), 100);
+    /// ```
+    pub fn apply_lenient(&mut self) -> Result<Vec<OperationError>, ApplyError> {
+        let errors = self.data.apply_ops_list_lenient(&self.ops)?;
+        self.ops.clear();
+        Ok(errors)
+    }
+
+    /// Applies the queued operations and writes the resulting image in the given file type to a writer
+    ///
+    /// This allows to encode the image directly into e.g. a socket or a buffer in memory, instead of a file.
+    /// File types, whose encoders can not write directly into the writer, are encoded into memory first,
+    /// see `TargetFormat`.
+    ///
+    /// * writer: &mut W - The writer the encoded image is written to
+    /// * format: TargetFormat - The file type the image is encoded to
+    ///
+    /// # Errors
+    /// Can return a `ApplyError::LoadingImageError` if the file could not be loaded to memory
+    /// Can return a `ApplyError::OperationError` if an operation failed
+    /// Can return a `ApplyError::StoreError` if the image could not be encoded or written
+    ///
+    /// # Examples
+    /// ```
+    /// use std::io::Cursor;
+    /// use std::path::Path;
+    /// use image::{GenericImageView, ImageFormat};
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::ExactBox(120, 40));
+    ///
+    /// let mut cursor = Cursor::new(vec![]);
+    /// assert!(thumb.apply_store_to_writer(&mut cursor, TargetFormat::Png).is_ok());
+    ///
+    /// let bytes = cursor.into_inner();
+    /// let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png).unwrap();
+    /// assert_eq!(image.dimensions(), (120, 40));
+    /// ```
+    pub fn apply_store_to_writer<W: Write + Seek>(
+        mut self,
+        writer: &mut W,
+        format: TargetFormat,
+    ) -> Result<(), ApplyError> {
+        self.apply()?;
+
+        let format = format.resolve(self.source_format());
+        let path = self.get_path();
+        let image = match self.data.get_dyn_image() {
+            Ok(image) => image,
+            Err(err) => return Err(ApplyError::LoadingImageError(err)),
+        };
+
+        match target::write_image(image, &format, writer, &path) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(ApplyError::StoreError(err)),
+        }
+    }
+
+    /// Applies the queued operations and encodes the resulting image in the given file type as data URI
+    ///
+    /// The data URI has the form `data:<MIME type>;base64,<data>` and can be used to embed the image
+    /// e.g. in HTML or CSS. The image is encoded in memory, see `apply_store_to_writer()`.
+    ///
+    /// * format: TargetFormat - The file type the image is encoded to
+    ///
+    /// # Errors
+    /// Can return a `ApplyError::LoadingImageError` if the file could not be loaded to memory
+    /// Can return a `ApplyError::OperationError` if an operation failed
+    /// Can return a `ApplyError::StoreError` if the image could not be encoded
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::{GenericImageView, ImageFormat};
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::ExactBox(120, 40));
+    ///
+    /// let uri = thumb.apply_store_data_uri(TargetFormat::Png).ok().unwrap();
+    /// assert!(uri.starts_with("data:image/png;base64,"));
+    ///
+    /// let bytes = base64::decode(&uri["data:image/png;base64,".len()..]).unwrap();
+    /// let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png).unwrap();
+    /// assert_eq!(image.dimensions(), (120, 40));
+    /// ```
+    pub fn apply_store_data_uri(self, format: TargetFormat) -> Result<String, ApplyError> {
+        let format = format.resolve(self.source_format());
+        let mime_type = format.mime_type().to_string();
+        let mut cursor = Cursor::new(vec![]);
+        self.apply_store_to_writer(&mut cursor, format)?;
+
+        Ok(format!(
+            "data:{};base64,{}",
+            mime_type,
+            base64::encode(cursor.into_inner())
+        ))
+    }
+
+    /// Encodes the current state of the `Thumbnail` in the given file type, without applying the queue to it
+    ///
+    /// The queued operations are applied to a copy of the image, which is then encoded in memory,
+    /// see `apply_store_to_writer()`. The `Thumbnail` itself and its queue stay untouched,
+    /// so further operations can be queued afterwards, e.g. to inspect a pipeline in between or to branch it.
+    ///
+    /// * format: TargetFormat - The file type the image is encoded to
+    ///
+    /// # Errors
+    /// Can return a `ApplyError::LoadingImageError` if the file could not be loaded to memory
+    /// Can return a `ApplyError::OperationError` if an operation failed
+    /// Can return a `ApplyError::StoreError` if the image could not be encoded
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::{GenericImageView, ImageFormat};
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::ExactBox(120, 40));
+    ///
+    /// let snapshot = thumb.snapshot_bytes(TargetFormat::Png).ok().unwrap();
+    /// let image = image::load_from_memory_with_format(&snapshot, ImageFormat::Png).unwrap();
+    /// assert_eq!(image.dimensions(), (120, 40));
+    /// assert_eq!(thumb.queued_ops(), 1);
+    ///
+    /// thumb.blur(3.0);
+    /// let mut cursor = std::io::Cursor::new(vec![]);
+    /// thumb.apply_store_to_writer(&mut cursor, TargetFormat::Png).ok().unwrap();
+    /// let result = cursor.into_inner();
+    ///
+    /// let image = image::load_from_memory_with_format(&result, ImageFormat::Png).unwrap();
+    /// assert_eq!(image.dimensions(), (120, 40));
+    /// assert_ne!(snapshot, result);
+    /// ```
+    pub fn snapshot_bytes(&mut self, format: TargetFormat) -> Result<Vec<u8>, ApplyError> {
+        let snapshot = match self.try_clone_and_load() {
+            Ok(snapshot) => snapshot,
+            Err(err) => return Err(ApplyError::LoadingImageError(err)),
+        };
+
+        let mut cursor = Cursor::new(vec![]);
+        snapshot.apply_store_to_writer(&mut cursor, format)?;
+        Ok(cursor.into_inner())
+    }
+
+    /// Applies the queued operations and stores the result to the given `Target`, without blocking an async runtime
+    ///
+    /// This is the same as `apply_store()`, but the work is done on the blocking thread pool of tokio,
+    /// see `tokio::task::spawn_blocking`. This allows to create thumbnails e.g. in a web server running on tokio.
+    /// The returned future has to be awaited within a tokio runtime.
+    /// Only available with the `async` feature.
+    ///
+    /// * target: Target - The definition of the target image files
+    ///
+    /// # Errors
+    /// The same errors as `apply_store()`
+    /// Can return a `ApplyError::StoreError` with a `FileError::IoError`, if the runtime shut down before the work was done
+    ///
+    /// # Panic
+    /// Panics are propagated from the blocking thread pool to the caller
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::GenericImageView;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::target::TargetFormat;
+    /// use thumbnailer::{Resize, Target, Thumbnail};
+    ///
+    /// let dst = std::env::temp_dir().join("thumbnailer_doc_apply_store_async/thumb.png");
+    /// let target = Target::new(TargetFormat::Png, dst.clone());
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::Width(100));
+    ///
+    /// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// let paths = runtime.block_on(thumb.apply_store_async(target)).ok().unwrap();
+    ///
+    /// assert_eq!(paths, vec![dst]);
+    /// assert_eq!(image::open(&paths[0]).unwrap().width(), 100);
+    /// ```
+    #[cfg(feature = "async")]
+    pub async fn apply_store_async(self, target: Target) -> Result<Vec<PathBuf>, ApplyError> {
+        match tokio::task::spawn_blocking(move || self.apply_store(&target)).await {
+            Ok(result) => result,
+            Err(error) => match error.try_into_panic() {
+                Ok(panic) => std::panic::resume_unwind(panic),
+                // The task was cancelled, e.g. because the runtime shut down
+                Err(error) => Err(ApplyError::StoreError(FileError::IoError(
+                    std::io::Error::new(std::io::ErrorKind::Interrupted, error),
+                ))),
+            },
+        }
+    }
+
+    /// Turns the `Thumbnail` into an owned `ThumbnailPipeline`
+    ///
+    /// The methods of the pipeline take and return the pipeline by value,
+    /// which allows to chain the operations and to end the chain with e.g. `store()`.
+    /// Operations already queued on the `Thumbnail` are kept.
+    pub fn into_pipeline(self) -> ThumbnailPipeline {
+        ThumbnailPipeline::from_thumbnail(self)
+    }
+
+    /// Gets the number of operations that are queued and not applied yet
+    pub fn queued_ops(&self) -> usize {
+        self.ops.len()
+    }
+
+    /// Gets the operations that are queued and not applied yet, in the order they will be applied
+    ///
+    /// Each operation implements `Debug`, which allows to check that the expected operations have been queued,
+    /// before the operations are applied.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::Width(100)).blur(2.0).invert();
+    ///
+    /// assert_eq!(thumb.queued_ops(), 3);
+    /// let ops: Vec<String> = thumb.iter_queued_ops().map(|op| format!("{:?}", op)).collect();
+    /// assert!(ops[0].starts_with("ResizeOp"));
+    /// assert!(ops[1].starts_with("BlurOp"));
+    /// assert!(ops[2].starts_with("InvertOp"));
+    /// ```
+    pub fn iter_queued_ops(&self) -> impl Iterator<Item = &dyn Operation> {
+        self.ops.iter().map(|op| op.as_ref())
+    }
+
+    /// Removes all queued operations without applying them
+    ///
+    /// Operations that have already been applied are not undone.
+    /// This allows to reuse the loaded image for a different pipeline.
+    ///
+    /// Returns itself to allow method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{Resize, Thumbnail};
+    ///
+    /// let original = image::open("resources/tests/test.jpg").unwrap();
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// thumb.resize(Resize::Width(100)).blur(2.0);
+    /// assert_eq!(thumb.queued_ops(), 2);
+    ///
+    /// thumb.clear_ops();
+    /// assert_eq!(thumb.queued_ops(), 0);
+    ///
+    /// let image = thumb.into_dynamic_image().ok().unwrap();
+    /// assert_eq!(image.to_bytes(), original.to_bytes());
+    /// ```
+    pub fn clear_ops(&mut self) -> &mut Self {
+        self.ops.clear();
+        self
+    }
+
+    /// Sets the color type the image is converted to, before the queued operations are applied to it
+    ///
+    /// The image is converted once, right after it is decoded, and keeps the color type for all operations.
+    /// Operations which only work on some color types, e.g. combine on RGB and RGBA images,
+    /// then work on images of any color type, and operations which convert the image themselves can skip that.
+    /// The conversion applies to all later calls to `apply()`, images that already have the color type are not changed.
+    /// The stored files have the working color type, as far as their file type supports it.
+    ///
+    /// * color: ColorType - The color type of the image while the operations are applied
+    ///
+    /// Returns itself to allow method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::{ColorType, DynamicImage, GenericImageView, GrayImage, Luma, Rgba, RgbaImage};
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use thumbnailer::{BoxPosition, GenericThumbnail, Thumbnail};
+    ///
+    /// let gray = DynamicImage::ImageLuma8(GrayImage::from_pixel(40, 40, Luma([100])));
+    /// let overlay = DynamicImage::ImageRgba8(RgbaImage::from_pixel(10, 10, Rgba([255, 0, 0, 255])));
+    /// let overlay = StaticThumbnail::from_dynamic_image(overlay);
+    ///
+    /// // combining works on RGB and RGBA images only
+    /// let mut thumb = Thumbnail::from_dynamic_image("gray.png", gray.clone());
+    /// thumb.combine(overlay.clone(), BoxPosition::TopLeft(5, 5));
+    /// assert!(thumb.apply().is_err());
+    ///
+    /// let mut thumb = Thumbnail::from_dynamic_image("gray.png", gray);
+    /// thumb.set_working_color(ColorType::Rgba8);
+    /// thumb.combine(overlay, BoxPosition::TopLeft(5, 5));
+    /// assert!(thumb.apply().is_ok());
+    ///
+    /// let image = thumb.into_dynamic_image().ok().unwrap();
+    /// assert_eq!(image.color(), ColorType::Rgba8);
+    /// assert_eq!(image.get_pixel(10, 10), Rgba([255, 0, 0, 255]));
+    /// assert_eq!(image.get_pixel(30, 30), Rgba([100, 100, 100, 255]));
+    /// ```
+    pub fn set_working_color(&mut self, color: ColorType) -> &mut Self {
+        self.data.set_working_color(color);
+        self
+    }
+
     /// Gets the path stored in the `Thumbnail`. Usually the path from which the image was loaded.
     pub fn get_path(&self) -> PathBuf {
         self.data.get_path()
     }
 
+    /// Gets the format of the image
+    ///
+    /// For loaded files this is the format that was detected when loading the file, without decoding the image data.
+    /// For thumbnails created from a `DynamicImage` the format is inferred from the extension of the given path.
+    /// Returns `None` if the format could not be determined.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::{DynamicImage, ImageFormat};
+    /// use thumbnailer::Thumbnail;
+    /// let thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// assert_eq!(thumb.format(), Some(ImageFormat::Jpeg));
+    ///
+    /// let thumb = Thumbnail::from_dynamic_image("image.png", DynamicImage::new_rgb8(10, 10));
+    /// assert_eq!(thumb.format(), Some(ImageFormat::Png));
+    /// ```
+    pub fn format(&self) -> Option<ImageFormat> {
+        self.data.format()
+    }
+
+    /// Gets the format of the file the `Thumbnail` was loaded from
+    ///
+    /// This can be used to store a thumbnail in the same format as its source.
+    /// Unlike `format()` this is never inferred from the path:
+    /// Returns `None` if the `Thumbnail` was created with `from_dynamic_image()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use image::{DynamicImage, ImageFormat};
+    /// use thumbnailer::Thumbnail;
+    /// let thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// assert_eq!(thumb.source_format(), Some(ImageFormat::Jpeg));
+    ///
+    /// let thumb = Thumbnail::from_dynamic_image("image.png", DynamicImage::new_rgb8(10, 10));
+    /// assert_eq!(thumb.source_format(), None);
+    /// ```
+    pub fn source_format(&self) -> Option<ImageFormat> {
+        self.data.source_format()
+    }
+
+    /// Reads the EXIF metadata from the file or the encoded data the `Thumbnail` was loaded from
+    ///
+    /// Queued or applied operations are not taken into account.
+    /// For a `Thumbnail` created with `from_bytes()` the metadata is read from the data, never from its path.
+    /// Returns an empty `ExifData`, if the file type does not support EXIF or the file contains no EXIF metadata.
+    ///
+    /// # Errors
+    /// Returns a `FileError::NotFound` if the file could not be found, or the `Thumbnail` was created from a `DynamicImage`
+    /// Returns a `FileError::IoError` if an error occurred while reading the file
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::Thumbnail;
+    ///
+    /// let thumb = Thumbnail::load(Path::new("resources/tests/exif.jpg").to_path_buf()).unwrap();
+    /// let exif = thumb.read_exif().unwrap();
+    ///
+    /// assert_eq!(exif.orientation, Some(6));
+    /// assert_eq!(exif.date_time.as_deref(), Some("2020:05:17 14:30:00"));
+    /// assert_eq!(exif.make.as_deref(), Some("ThumbCam"));
+    /// assert_eq!(exif.model.as_deref(), Some("TC-1"));
+    /// let (latitude, longitude) = exif.gps.unwrap();
+    /// assert!((latitude - 48.1333).abs() < 0.001);
+    /// assert!((longitude - 11.575).abs() < 0.001);
+    ///
+    /// // PNG files carry the metadata in an eXIf chunk
+    /// let thumb = Thumbnail::load(Path::new("resources/tests/exif.png").to_path_buf()).unwrap();
+    /// assert_eq!(thumb.read_exif().unwrap().orientation, Some(6));
+    ///
+    /// // files without EXIF metadata result in an empty `ExifData`
+    /// let thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    /// assert_eq!(thumb.read_exif().unwrap(), Default::default());
+    ///
+    /// // encoded data carries its own metadata, even if its path names a different file
+    /// let bytes = std::fs::read("resources/tests/exif.jpg").unwrap();
+    /// let thumb = Thumbnail::from_bytes("resources/tests/test.jpg", &bytes).unwrap();
+    /// assert_eq!(thumb.read_exif().unwrap().orientation, Some(6));
+    /// let bytes = std::fs::read("resources/tests/test.jpg").unwrap();
+    /// let thumb = Thumbnail::from_bytes("resources/tests/exif.jpg", &bytes).unwrap();
+    /// assert_eq!(thumb.read_exif().unwrap(), Default::default());
+    /// ```
+    pub fn read_exif(&self) -> Result<ExifData, FileError> {
+        self.data.read_exif()
+    }
+
+    /// Gets the dimensions of the image as `(width, height)`
+    ///
+    /// This loads the actual image data to memory, if that has not happened yet.
+    /// Queued operations are not taken into account.
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotSupported` if the file could not be loaded to memory
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::Thumbnail;
+    /// let mut thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// assert_eq!(thumb.dimensions().unwrap(), (500, 138));
+    /// ```
+    pub fn dimensions(&mut self) -> Result<(u32, u32), FileError> {
+        self.data.dimensions()
+    }
+
+    /// Gets the dimensions of the image as `(width, height)` without decoding the image data
+    ///
+    /// If the image data has not been loaded to memory yet, only the header of the file is read.
+    /// This is considerably cheaper than `dimensions()` for images that have not been loaded yet.
+    /// Queued operations are not taken into account.
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotSupported` if the header of the file could not be read
+    /// Can return a `FileError::IoError` if an error occurred while accessing the file
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::Thumbnail;
+    /// let thumb = Thumbnail::load(Path::new("resources/tests/test.jpg").to_path_buf()).unwrap();
+    ///
+    /// assert_eq!(thumb.dimensions_without_decode().unwrap(), (500, 138));
+    /// ```
+    pub fn dimensions_without_decode(&self) -> Result<(u32, u32), FileError> {
+        self.data.dimensions_without_decode()
+    }
+
     /// Clones an instance of `StaticThumbnail` from this instance.
     ///
     /// This first loads the actual image data to memory, to allow cloning in the first place.
@@ -114,8 +948,8 @@ impl Thumbnail {
 
     /// Tries to load the binary data to memory and then clone the instance.
     ///
-    /// This load the data first, because otherwise both instances would hold the same file handle,
-    /// this could lead to weird problems we rather avoid.
+    /// This load the data first, because otherwise both instances would decode the same file,
+    /// which may have changed in between.
     ///
     /// # Errors
     /// Can return a `FileError::NotSupported` if the file could not be loaded to memory
@@ -127,15 +961,158 @@ impl Thumbnail {
         Ok(Thumbnail { data: image, ops })
     }
 
+    /// Extracts a region of the image as a new, independent `Thumbnail`
+    ///
+    /// The region is copied from the image as it is in memory, queued operations are not applied.
+    /// The new `Thumbnail` has no queued operations and keeps the path of this `Thumbnail`,
+    /// so it is stored with the same file stem and format. This `Thumbnail` stays untouched.
+    /// This allows to e.g. split a large image into tiles, that are processed independently.
+    ///
+    /// * x: u32 - The horizontal position of the top-left corner of the region
+    /// * y: u32 - The vertical position of the top-left corner of the region
+    /// * width: u32 - The width of the region
+    /// * height: u32 - The height of the region
+    ///
+    /// # Errors
+    /// Can return a `FileError::NotSupported` if the file could not be loaded to memory
+    /// Can return a `FileError::RegionOutOfBounds` if the region is empty or not fully inside the image
+    ///
+    /// # Examples
+    /// ```
+    /// use image::DynamicImage;
+    /// use thumbnailer::errors::FileError;
+    /// use thumbnailer::Thumbnail;
+    ///
+    /// let mut thumb = Thumbnail::from_dynamic_image("large.png", DynamicImage::new_rgb8(200, 200));
+    ///
+    /// let mut tile = thumb.extract_region(100, 50, 50, 50).unwrap();
+    /// assert_eq!(tile.dimensions().unwrap(), (50, 50));
+    /// assert_eq!(thumb.dimensions().unwrap(), (200, 200));
+    ///
+    /// assert!(matches!(
+    ///     thumb.extract_region(180, 0, 50, 50),
+    ///     Err(FileError::RegionOutOfBounds((180, 0, 50, 50)))
+    /// ));
+    /// assert!(thumb.extract_region(0, 0, 0, 50).is_err());
+    /// ```
+    pub fn extract_region(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Result<Thumbnail, FileError> {
+        let path = self.get_path();
+        let image = self.get_dyn_image()?;
+
+        let (image_width, image_height) = image.dimensions();
+        let inside = x as u64 + width as u64 <= image_width as u64
+            && y as u64 + height as u64 <= image_height as u64;
+        if width == 0 || height == 0 || !inside {
+            return Err(FileError::RegionOutOfBounds((x, y, width, height)));
+        }
+
+        let region = image.crop_imm(x, y, width, height);
+        Ok(Thumbnail::from_dynamic_image(
+            &path.to_string_lossy(),
+            region,
+        ))
+    }
+
+    /// Serializes the queued operations to JSON
+    ///
+    /// The JSON can be used to restore the queue later with `ops_from_json()`.
+    /// For the combine operation only the path of the overlay image is serialized, not the image data itself.
+    ///
+    /// # Errors
+    /// Returns a `serde_json::Error` if the serialization fails,
+    /// e.g. because an operation implemented outside of this crate is queued, see `Operation::to_spec()`
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{BoxPosition, Resize, Thumbnail};
+    /// use image::DynamicImage;
+    ///
+    /// let mut thumb = Thumbnail::from_dynamic_image("test.jpg", DynamicImage::new_rgb8(800, 500));
+    /// thumb
+    ///     .resize(Resize::Width(400))
+    ///     .blur(2.5)
+    ///     .text("Hello world!".to_string(), BoxPosition::TopLeft(5, 5));
+    ///
+    /// let json = thumb.ops_to_json().unwrap();
+    ///
+    /// let mut other = Thumbnail::from_dynamic_image("other.jpg", DynamicImage::new_rgb8(800, 500));
+    /// other.ops_from_json(&json).unwrap();
+    ///
+    /// assert!(json.contains("Resize"));
+    /// assert_eq!(other.ops_to_json().unwrap(), json);
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn ops_to_json(&self) -> Result<String, serde_json::Error> {
+        let specs = self
+            .ops
+            .iter()
+            .map(|op| {
+                op.to_spec().ok_or_else(|| {
+                    <serde_json::Error as serde::ser::Error>::custom(format!(
+                        "the operation {:?} can not be serialized",
+                        op
+                    ))
+                })
+            })
+            .collect::<Result<Vec<OperationSpec>, serde_json::Error>>()?;
+        serde_json::to_string(&specs)
+    }
+
+    /// Replaces the queued operations with the operations deserialized from the given JSON
+    ///
+    /// The JSON is expected to be created by `ops_to_json()`.
+    ///
+    /// # Errors
+    /// Returns a `serde_json::Error` if the JSON is not valid
+    /// or if an overlay image of a combine operation could not be loaded
+    #[cfg(feature = "serde")]
+    pub fn ops_from_json(&mut self, json: &str) -> Result<&mut Self, serde_json::Error> {
+        let specs: Vec<OperationSpec> = serde_json::from_str(json)?;
+        self.ops = specs
+            .into_iter()
+            .map(OperationSpec::into_operation)
+            .collect();
+        Ok(self)
+    }
+
     /// Checks if the given path is a file which could be loaded
     ///
+    /// The format is determined from the content of the file, and from the extension if the content is not recognized.
+    /// WebP images are supported, the decoder of the image crate is limited to lossy images without alpha channel.
+    /// Enable the `webp` feature to decode lossless images and images with alpha channel with libwebp.
+    ///
     /// * path: &Path - Path to check
+    ///
+    /// # Examples
+    /// ```
+    /// use std::path::Path;
+    /// use thumbnailer::generic::GenericThumbnailOperations;
+    /// use thumbnailer::{Resize, Thumbnail};
+    /// use image::GenericImageView;
+    ///
+    /// let path = Path::new("resources/tests/lossy.webp");
+    /// assert!(Thumbnail::can_load(path));
+    ///
+    /// let mut thumb = Thumbnail::load(path.to_path_buf()).unwrap();
+    /// thumb.resize(Resize::Width(30));
+    /// let image = thumb.apply_to_dynamic_image().ok().unwrap();
+    /// assert_eq!(image.width(), 30);
+    ///
+    /// assert!(!Thumbnail::can_load(Path::new("Cargo.toml")));
+    /// ```
     pub fn can_load(path: &Path) -> bool {
         if !path.is_file() {
             return false;
         }
 
-        match Reader::open(path) {
+        match Reader::open(path).and_then(|reader| reader.with_guessed_format()) {
             Err(_) => false,
             Ok(reader) => reader.format().is_some(),
         }
@@ -182,3 +1159,222 @@ impl GenericThumbnail for Thumbnail {
         }
     }
 }
+
+/// Downloads the data at a HTTP(S) URL
+///
+/// * url: &str - The URL of the data
+/// * max_bytes: u64 - The largest number of bytes, that is downloaded
+///
+/// # Errors
+/// Returns a `FileError::NotFound` if the server responded with the status 404 or 410
+/// Returns a `FileError::IoError` if the request failed, the server responded with another error status,
+/// or the data is larger than `max_bytes`
+#[cfg(feature = "remote")]
+#[allow(clippy::io_other_error)] // io::Error::other() needs Rust 1.74
+fn download(url: &str, max_bytes: u64) -> Result<Vec<u8>, FileError> {
+    let response = match ureq::get(url).call() {
+        Ok(response) => response,
+        Err(ureq::Error::Status(404, _)) | Err(ureq::Error::Status(410, _)) => {
+            return Err(FileError::NotFound(FileNotFoundError {
+                path: PathBuf::from(url),
+            }))
+        }
+        Err(ureq::Error::Status(status, _)) => {
+            return Err(FileError::IoError(io::Error::new(
+                io::ErrorKind::Other,
+                format!(
+                    "The server responded with the status {} for {}",
+                    status, url
+                ),
+            )))
+        }
+        Err(error) => {
+            return Err(FileError::IoError(io::Error::new(
+                io::ErrorKind::Other,
+                error,
+            )))
+        }
+    };
+
+    // One more byte is read, to tell data of exactly the maximum size from larger data
+    let mut bytes = vec![];
+    response
+        .into_reader()
+        .take(max_bytes.saturating_add(1))
+        .read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_bytes {
+        return Err(FileError::IoError(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("The data at {} is larger than {} bytes", url, max_bytes),
+        )));
+    }
+    Ok(bytes)
+}
+
+/// Loads the image at `src`, resizes it and stores the thumbnail at `dst`, all in one call
+///
+/// The file type of the thumbnail is determined by the file extension of `dst`, see `Target::from_path()`.
+/// For more control over the operations and the targets, use `Thumbnail::load()` or `Thumbnail::thumbnail()`.
+///
+/// * src: PathBuf - The path to the image file
+/// * dst: PathBuf - The path to store the thumbnail at
+/// * size: Resize - The size of the thumbnail
+///
+/// Returns the path of the stored thumbnail.
+///
+/// # Errors
+/// Can return a `ApplyError::LoadingImageError` if the image could not be loaded
+/// Can return a `ApplyError::OperationError` if resizing the image failed
+/// Can return a `ApplyError::StoreError` if the extension of `dst` is not supported, or the thumbnail could not be stored
+///
+/// # Examples
+/// ```
+/// use std::path::Path;
+/// use image::GenericImageView;
+/// use thumbnailer::Resize;
+///
+/// let src = Path::new("resources/tests/test.jpg").to_path_buf();
+/// let dst = std::env::temp_dir().join("thumbnailer_doc_quick_thumbnail/thumb.png");
+///
+/// let path = thumbnailer::quick_thumbnail(src, dst, Resize::Width(100)).ok().unwrap();
+/// assert_eq!(image::open(path).unwrap().width(), 100);
+/// ```
+pub fn quick_thumbnail(src: PathBuf, dst: PathBuf, size: Resize) -> Result<PathBuf, ApplyError> {
+    let target = Target::from_path(dst).map_err(ApplyError::StoreError)?;
+    let thumb = Thumbnail::thumbnail(src, size).map_err(ApplyError::LoadingImageError)?;
+
+    let mut paths = thumb.apply_store(&target)?;
+    Ok(paths.remove(0))
+}
+
+#[cfg(all(test, feature = "remote"))]
+mod tests {
+    use super::{download, Thumbnail};
+    use crate::errors::FileError;
+    use image::{GenericImageView, ImageFormat};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves a single request on a local port, responding with the given status and body
+    ///
+    /// Returns the URL of the server.
+    fn serve_once(status: &'static str, body: Vec<u8>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut line = String::new();
+            // The request is read until the empty line, that ends its header
+            while reader.read_line(&mut line).unwrap() > 2 {
+                line.clear();
+            }
+            let mut stream = reader.into_inner();
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                status,
+                body.len()
+            )
+            .unwrap();
+            stream.write_all(&body).unwrap();
+        });
+        format!("http://{}", address)
+    }
+
+    #[test]
+    fn image_is_loaded_from_url() {
+        let bytes = std::fs::read("resources/tests/test.jpg").unwrap();
+        let url = serve_once("200 OK", bytes);
+
+        let thumb = Thumbnail::load_url(&format!("{}/images/test.jpg?size=large", url)).unwrap();
+
+        assert_eq!(thumb.source_format(), Some(ImageFormat::Jpeg));
+        assert_eq!(thumb.get_path().file_name().unwrap(), "test.jpg");
+        assert_eq!(thumb.to_dynamic_image().unwrap().dimensions(), (500, 138));
+    }
+
+    #[test]
+    fn error_status_is_not_found() {
+        let url = serve_once("404 Not Found", vec![]);
+        let res = Thumbnail::load_url(&format!("{}/missing.jpg", url));
+        assert!(matches!(res, Err(FileError::NotFound(_))));
+
+        let url = serve_once("410 Gone", vec![]);
+        let res = Thumbnail::load_url(&format!("{}/removed.jpg", url));
+        assert!(matches!(res, Err(FileError::NotFound(_))));
+    }
+
+    #[test]
+    fn server_error_is_io_error() {
+        let url = serve_once("500 Internal Server Error", vec![]);
+
+        let res = Thumbnail::load_url(&format!("{}/test.jpg", url));
+
+        assert!(matches!(res, Err(FileError::IoError(_))));
+    }
+
+    #[test]
+    fn download_is_limited() {
+        let url = serve_once("200 OK", vec![0; 10]);
+        assert_eq!(download(&url, 10).unwrap().len(), 10);
+
+        let url = serve_once("200 OK", vec![0; 11]);
+        assert!(matches!(download(&url, 10), Err(FileError::IoError(_))));
+    }
+
+    #[test]
+    fn invalid_data_is_not_supported() {
+        let url = serve_once("200 OK", b"not an image".to_vec());
+
+        let res = Thumbnail::load_url(&format!("{}/broken.jpg", url));
+
+        assert!(matches!(res, Err(FileError::NotSupported(_))));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::Thumbnail;
+    use crate::GenericThumbnail;
+    use image::{DynamicImage, GenericImageView, GrayImage, Luma};
+
+    #[test]
+    fn deserialized_parameters_are_clamped() {
+        let mut thumb = Thumbnail::load("resources/tests/test.jpg".into()).unwrap();
+        thumb
+            .ops_from_json(
+                r#"[
+                    {"Quantize":{"max_colors":0}},
+                    {"Shadow":{"dx":2,"dy":2,"blur_sigma":-1.0,"color":[0,0,0,255]}}
+                ]"#,
+            )
+            .unwrap();
+
+        assert!(thumb.apply().is_ok());
+        // A negative sigma results in a hard shadow without a margin
+        assert_eq!(thumb.dimensions().unwrap(), (502, 140));
+
+        // A gamma of 10.0 brightens the midtones to 238, an unclamped gamma of 1000.0 would make them white
+        let gray = DynamicImage::ImageLuma8(GrayImage::from_pixel(4, 4, Luma([128])));
+        let mut thumb = Thumbnail::from_dynamic_image("gray.png", gray);
+        thumb
+            .ops_from_json(r#"[{"Levels":{"black":0,"white":255,"gamma":1000.0}}]"#)
+            .unwrap();
+        let image = thumb.apply_to_dynamic_image().ok().unwrap();
+        assert_eq!(image.get_pixel(0, 0)[0], 238);
+    }
+
+    #[test]
+    fn deserialized_rotation_fill_defaults_to_transparent() {
+        let image = DynamicImage::ImageLuma8(GrayImage::from_pixel(10, 10, Luma([255])));
+        let mut thumb = Thumbnail::from_dynamic_image("white.png", image);
+        thumb
+            .ops_from_json(r#"[{"Rotate":{"rotation":{"Degrees":45.0}}}]"#)
+            .unwrap();
+
+        let image = thumb.apply_to_dynamic_image().ok().unwrap();
+        assert_eq!(image.get_pixel(0, 0)[3], 0);
+    }
+}
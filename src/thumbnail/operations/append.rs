@@ -0,0 +1,157 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{rgba8, rgba8_fits, Operation, ResizeOp};
+use crate::{Orientation, Resize, StaticThumbnail};
+use image::{imageops, DynamicImage, GenericImageView, RgbaImage};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the append operation as a struct
+pub struct AppendOp {
+    /// The appended image as `StaticThumbnail`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "path", with = "crate::thumbnail::static_thumb::by_path")
+    )]
+    image: StaticThumbnail,
+    /// Specifies where the image is appended, represented by `Orientation` enum
+    orientation: Orientation,
+}
+
+impl AppendOp {
+    /// Returns a new `AppendOp` struct with defined:
+    /// * `image` as the image that should be appended to the 'DynamicImage'
+    /// * `orientation` as the direction the image is appended in, represented by `Orientation` enum
+    pub fn new(image: StaticThumbnail, orientation: Orientation) -> Self {
+        AppendOp { image, orientation }
+    }
+}
+
+impl Operation for AppendOp {
+    /// Logic for the operation of appending an image to another image
+    ///
+    /// This function places a `StaticThumbnail` next to a `DynamicImage` in the direction defined by the `Orientation`-enum:
+    /// * with `Orientation::Horizontal`: The image is appended on the right side.
+    ///   It is scaled to the height of the `DynamicImage`, keeping its aspect ratio.
+    /// * with `Orientation::Vertical`: The image is appended at the bottom.
+    ///   It is scaled to the width of the `DynamicImage`, keeping its aspect ratio.
+    ///
+    /// The result is a new image large enough for both images, e.g. two images of 100x100 pixels
+    /// appended horizontally give an image of 200x100 pixels.
+    /// The image is converted to `ImageRgba8`, or to `ImageRgb8` if it has no alpha channel.
+    /// Nothing is appended if one of the images is empty.
+    /// If the result would be too large to be created, e.g. for a very narrow appended image,
+    /// an `InvalidParameters` error is returned.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `AppendOp` struct
+    /// * `image` - The `DynamicImage` the image should be appended to
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::Orientation;
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::AppendOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// let left = RgbImage::from_pixel(100, 100, Rgb([255, 0, 0]));
+    /// let right = RgbImage::from_pixel(100, 100, Rgb([0, 0, 255]));
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(left);
+    ///
+    /// let append_op = AppendOp::new(
+    ///     StaticThumbnail::from_dynamic_image(DynamicImage::ImageRgb8(right)),
+    ///     Orientation::Horizontal,
+    /// );
+    /// let res = append_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// assert_eq!(dynamic_image.dimensions(), (200, 100));
+    /// assert_eq!(dynamic_image.get_pixel(99, 50)[0], 255);
+    /// assert_eq!(dynamic_image.get_pixel(100, 50)[2], 255);
+    /// ```
+    ///
+    /// The appended image is scaled to the shared dimension:
+    /// ```
+    /// use thumbnailer::generic::Orientation;
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::AppendOp;
+    /// use image::{DynamicImage, GenericImageView};
+    ///
+    /// let mut dynamic_image = DynamicImage::new_rgba8(100, 80);
+    /// let other = StaticThumbnail::from_dynamic_image(DynamicImage::new_rgba8(400, 200));
+    ///
+    /// let append_op = AppendOp::new(other, Orientation::Vertical);
+    /// assert!(append_op.apply(&mut dynamic_image).is_ok());
+    /// assert_eq!(dynamic_image.dimensions(), (100, 130));
+    ///
+    /// // scaled to the height, the appended image would be wider than `u32::MAX` pixels
+    /// let mut dynamic_image = DynamicImage::new_rgba8(1, 50_000);
+    /// let other = StaticThumbnail::from_dynamic_image(DynamicImage::new_rgba8(100_000, 1));
+    /// let append_op = AppendOp::new(other, Orientation::Horizontal);
+    /// assert!(append_op.apply(&mut dynamic_image).is_err());
+    /// assert_eq!(dynamic_image.dimensions(), (1, 50_000));
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (width, height) = image.dimensions();
+        let (other_width, other_height) = self.image.dimensions();
+        if width == 0 || height == 0 || other_width == 0 || other_height == 0 {
+            return Ok(());
+        }
+
+        let (width, height) = (width as u64, height as u64);
+        let (other_width, other_height) = (other_width as u64, other_height as u64);
+        let (scaled_width, scaled_height) = match self.orientation {
+            Orientation::Horizontal => (((other_width * height) / other_height).max(1), height),
+            Orientation::Vertical => (width, ((other_height * width) / other_width).max(1)),
+        };
+
+        // The sizes are computed in u64, so they can not overflow before they are checked
+        let (canvas_width, canvas_height, x, y) = match self.orientation {
+            Orientation::Horizontal => (width + scaled_width, height, width as u32, 0),
+            Orientation::Vertical => (width, height + scaled_height, 0, height as u32),
+        };
+        if !rgba8_fits(canvas_width, canvas_height) {
+            return Err(OperationError::new(
+                Box::new(self.clone()),
+                OperationErrorInfo::InvalidParameters,
+            ));
+        }
+
+        let mut other = self.image.as_dyn().clone();
+        ResizeOp::new(
+            Resize::ExactBox(scaled_width as u32, scaled_height as u32),
+            None,
+        )
+        .apply(&mut other)
+        .map_err(|error| OperationError::new(Box::new(self.clone()), error.get_info().clone()))?;
+
+        let mut canvas = RgbaImage::new(canvas_width as u32, canvas_height as u32);
+        imageops::replace(&mut canvas, &*rgba8(image), 0, 0);
+        imageops::replace(&mut canvas, &*rgba8(&other), x, y);
+
+        *image = if image.color().has_alpha() {
+            DynamicImage::ImageRgba8(canvas)
+        } else {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `AppendOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Append(self.clone()))
+    }
+}
@@ -0,0 +1,155 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::DynamicImage;
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "AutoContrastSpec"))]
+/// Representation of the auto-contrast-operation as a struct
+pub struct AutoContrastOp {
+    /// The percentage of the darkest and of the brightest pixels of each channel, that are ignored
+    /// when determining the range of the channel
+    clip: f32,
+}
+
+impl AutoContrastOp {
+    /// Returns a new `AutoContrastOp` struct with defined:
+    /// * `clip`: The percentage of pixels, between 0.0 and 50.0, that are ignored at each end of the histogram.
+    ///   This keeps a few outliers from dominating the range. Use 0.0 to stretch the full range.
+    pub fn new(clip: f32) -> Self {
+        AutoContrastOp {
+            clip: clip.clamp(0.0, 50.0),
+        }
+    }
+
+    /// Computes the lookup table that maps the values of a channel to the stretched values
+    ///
+    /// Returns `None` if the channel has only a single value after clipping, and can not be stretched.
+    ///
+    /// * histogram: &[u64; 256] - The number of pixels for each value of the channel
+    /// * pixel_count: u64 - The total number of pixels
+    fn lookup_table(&self, histogram: &[u64; 256], pixel_count: u64) -> Option<[u8; 256]> {
+        let clip_count = (pixel_count as f64 * self.clip as f64 / 100.0) as u64;
+
+        let mut sum = 0;
+        let low = histogram.iter().position(|count| {
+            sum += count;
+            sum > clip_count
+        })?;
+        let mut sum = 0;
+        let from_top = histogram.iter().rev().position(|count| {
+            sum += count;
+            sum > clip_count
+        })?;
+        let high = 255 - from_top;
+
+        if high <= low {
+            return None;
+        }
+
+        let mut table = [0u8; 256];
+        for (value, entry) in table.iter_mut().enumerate() {
+            let stretched = (value as f32 - low as f32) * 255.0 / (high - low) as f32;
+            *entry = stretched.round().clamp(0.0, 255.0) as u8;
+        }
+        Some(table)
+    }
+
+    /// Stretches the color channels of the raw pixel data in place
+    ///
+    /// * data: &mut [u8] - The interleaved pixel data
+    /// * channels: usize - The number of channels of each pixel
+    /// * color_channels: usize - The number of channels to stretch, the remaining channel is alpha
+    fn stretch(&self, data: &mut [u8], channels: usize, color_channels: usize) {
+        let pixel_count = (data.len() / channels) as u64;
+
+        for channel in 0..color_channels {
+            let mut histogram = [0u64; 256];
+            for pixel in data.chunks_exact(channels) {
+                histogram[pixel[channel] as usize] += 1;
+            }
+
+            if let Some(table) = self.lookup_table(&histogram, pixel_count) {
+                for pixel in data.chunks_exact_mut(channels) {
+                    pixel[channel] = table[pixel[channel] as usize];
+                }
+            }
+        }
+    }
+}
+
+/// The deserialized fields of a `AutoContrastOp`, which are validated by `AutoContrastOp::new()`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AutoContrastSpec {
+    clip: f32,
+}
+
+#[cfg(feature = "serde")]
+impl From<AutoContrastSpec> for AutoContrastOp {
+    fn from(spec: AutoContrastSpec) -> Self {
+        AutoContrastOp::new(spec.clip)
+    }
+}
+
+impl Operation for AutoContrastOp {
+    /// Logic for the auto-contrast-operation
+    ///
+    /// This function stretches the histogram of each color channel of a `DynamicImage` separately,
+    /// so that the darkest value is mapped to 0 and the brightest value to 255.
+    /// The given percentage of the darkest and brightest pixels is clipped before determining the range.
+    /// The values in between are remapped linearly. The alpha channel is left unchanged.
+    /// More information: [Normalization](https://en.wikipedia.org/wiki/Normalization_(image_processing))
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `AutoContrastOp` struct
+    /// * `image` - The `DynamicImage` that should be enhanced
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::AutoContrastOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// // A dull gradient, with values between 100 and 150
+    /// let dull = RgbImage::from_fn(51, 10, |x, _| Rgb([100 + x as u8, 100 + x as u8, 100 + x as u8]));
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(dull);
+    ///
+    /// let auto_contrast_op = AutoContrastOp::new(0.0);
+    /// let res = auto_contrast_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// assert_eq!(dynamic_image.get_pixel(0, 0)[0], 0);
+    /// assert_eq!(dynamic_image.get_pixel(50, 0)[0], 255);
+    /// assert_eq!(dynamic_image.get_pixel(25, 0)[0], 128);
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        *image = if image.color().has_alpha() {
+            let mut buffer = image.to_rgba8();
+            self.stretch(&mut buffer, 4, 3);
+            DynamicImage::ImageRgba8(buffer)
+        } else {
+            let mut buffer = image.to_rgb8();
+            self.stretch(&mut buffer, 3, 3);
+            DynamicImage::ImageRgb8(buffer)
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `AutoContrastOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::AutoContrast(*self))
+    }
+}
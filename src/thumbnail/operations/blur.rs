@@ -1,8 +1,11 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use image::DynamicImage;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the blur-operation as a struct
 pub struct BlurOp {
     /// Value that specifies how much the image should be blurred.
@@ -54,4 +57,10 @@ impl Operation for BlurOp {
         *image = image.blur(self.sigma);
         Ok(())
     }
+
+    /// Gets the serializable representation of the `BlurOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Blur(*self))
+    }
 }
@@ -0,0 +1,117 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImage, GenericImageView};
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the blur-region-operation as a struct
+pub struct BlurRegionOp {
+    /// The region that should be blurred, given by the coordinates of the top-left-corner, a width and a height
+    region: (u32, u32, u32, u32),
+    /// Value that specifies how much the region should be blurred.
+    /// More Information: [Gaussian Blur](https://en.wikipedia.org/wiki/Gaussian_blur)
+    sigma: f32,
+}
+
+impl BlurRegionOp {
+    /// Returns a new `BlurRegionOp` struct with defined:
+    /// * `x`: the x coordinate of the top-left-corner of the region
+    /// * `y`: the y coordinate of the top-left-corner of the region
+    /// * `width`: the width of the region
+    /// * `height`: the height of the region
+    /// * `sigma`: More Information: [Gaussian Blur](https://en.wikipedia.org/wiki/Gaussian_blur)
+    pub fn new(x: u32, y: u32, width: u32, height: u32, sigma: f32) -> Self {
+        BlurRegionOp {
+            region: (x, y, width, height),
+            sigma,
+        }
+    }
+}
+
+impl Operation for BlurRegionOp {
+    /// Logic for the blur-region-operation
+    ///
+    /// This function blurs a rectangular region of a `DynamicImage` based on a given `sigma` in `BlurRegionOp`,
+    /// e.g. to redact faces or license plates. The rest of the image is left unchanged.
+    /// The region is blurred on its own, so the pixels around the region do not bleed into it.
+    /// Mathematical background: [Gaussian Blur](https://en.wikipedia.org/wiki/Gaussian_blur).
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `BlurRegionOp` struct
+    /// * `image` - The `DynamicImage` of which a region should be blurred
+    ///
+    /// # Errors
+    ///
+    /// * CoordinatesOutOfRange - The region is not fully inside the image
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::BlurRegionOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// // a checkerboard, which is smoothed out by blurring
+    /// let checkerboard = RgbImage::from_fn(40, 40, |x, y| {
+    ///     if (x + y) % 2 == 0 { Rgb([255, 255, 255]) } else { Rgb([0, 0, 0]) }
+    /// });
+    /// let original = DynamicImage::ImageRgb8(checkerboard);
+    /// let mut dynamic_image = original.clone();
+    ///
+    /// let blur_region_op = BlurRegionOp::new(10, 10, 20, 20, 2.0);
+    /// let res = blur_region_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// // inside of the region the pixels are gray
+    /// let center = dynamic_image.get_pixel(20, 20)[0];
+    /// assert!(center > 64 && center < 192);
+    /// // outside of the region the pixels are untouched
+    /// for (x, y, pixel) in dynamic_image.pixels() {
+    ///     if !((10..30).contains(&x) && (10..30).contains(&y)) {
+    ///         assert_eq!(pixel, original.get_pixel(x, y));
+    ///     }
+    /// }
+    ///
+    /// // a region reaching outside of the image is rejected
+    /// let blur_region_op = BlurRegionOp::new(30, 30, 20, 20, 2.0);
+    /// assert!(blur_region_op.apply(&mut dynamic_image).is_err());
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (x, y, width, height) = self.region;
+        let (image_width, image_height) = image.dimensions();
+        if x as u64 + width as u64 > image_width as u64
+            || y as u64 + height as u64 > image_height as u64
+        {
+            return Err(OperationError::new(
+                Box::new(*self),
+                OperationErrorInfo::CoordinatesOutOfRange,
+            ));
+        }
+
+        let region = image.crop_imm(x, y, width, height).blur(self.sigma);
+        if image.copy_from(&region, x, y).is_err() {
+            return Err(OperationError::new(
+                Box::new(*self),
+                OperationErrorInfo::CoordinatesOutOfRange,
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `BlurRegionOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::BlurRegion(*self))
+    }
+}
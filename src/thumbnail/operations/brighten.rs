@@ -1,8 +1,11 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use image::DynamicImage;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the brighten-operation as a struct.
 pub struct BrightenOp {
     /// Value of how much the image should be brightened.
@@ -54,4 +57,10 @@ impl Operation for BrightenOp {
         *image = image.brighten(self.value);
         Ok(())
     }
+
+    /// Gets the serializable representation of the `BrightenOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Brighten(*self))
+    }
 }
@@ -0,0 +1,134 @@
+pub use crate::errors::OperationError;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{Operation, TextOp};
+use crate::{BoxPosition, StaticThumbnail};
+use image::{DynamicImage, GenericImage, GenericImageView, Pixel, Rgba};
+
+/// The distance of the caption to the bottom border of the image in pixels
+const CAPTION_MARGIN: u32 = 4;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the caption operation as a struct
+pub struct CaptionOp {
+    /// The text of the caption
+    text: String,
+}
+
+impl CaptionOp {
+    /// Returns a new `CaptionOp` struct with defined:
+    /// * `text` as the text of the caption
+    pub fn new(text: String) -> Self {
+        CaptionOp { text }
+    }
+}
+
+impl Operation for CaptionOp {
+    /// Logic for the operation of drawing a caption on an image
+    ///
+    /// This function draws a `String` centered along the bottom of a `DynamicImage`,
+    /// 4 pixels above the bottom border. The text is rendered like with `TextOp`.
+    /// Its color is chosen by the average luminance of the strip behind the caption, from the top of the text
+    /// minus the margin down to the bottom border: the text is white on dark strips and black on bright strips.
+    ///
+    /// Parts of the text outside of the image are clipped, e.g. if the text is wider than the image.
+    /// Nothing is drawn if the text is empty or consists of whitespace only.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `CaptionOp` struct
+    /// * `image` - The `DynamicImage` where the caption should be drawn on
+    ///
+    /// # Errors
+    ///
+    /// * FontLoadError - The font cannnot be loaded
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::CaptionOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// // Returns the brightest and the darkest red value in the bottom 30 rows
+    /// fn bottom_range(image: &DynamicImage) -> (u8, u8) {
+    ///     let values: Vec<u8> = image.pixels().filter(|(_, y, _)| *y >= 70).map(|(_, _, p)| p[0]).collect();
+    ///     (*values.iter().max().unwrap(), *values.iter().min().unwrap())
+    /// }
+    ///
+    /// let caption_op = CaptionOp::new("Holiday 2020".to_string());
+    ///
+    /// // white text on a dark image
+    /// let mut dark = DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 100, Rgb([20, 20, 20])));
+    /// assert!(caption_op.apply(&mut dark).is_ok());
+    /// assert!(bottom_range(&dark).0 > 200);
+    /// assert_eq!(bottom_range(&dark).1, 20);
+    ///
+    /// // black text on a bright image
+    /// let mut bright = DynamicImage::ImageRgb8(RgbImage::from_pixel(200, 100, Rgb([230, 230, 230])));
+    /// assert!(caption_op.apply(&mut bright).is_ok());
+    /// assert_eq!(bottom_range(&bright).0, 230);
+    /// assert!(bottom_range(&bright).1 < 50);
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        // The text is rendered onto a mask first, to measure it and to draw it in any color
+        let mask_width = (self.text.chars().count() as u32 + 1) * 12;
+        let mut mask = DynamicImage::new_luma8(mask_width, 24);
+        TextOp::new(self.text.clone(), BoxPosition::TopLeft(0, 0))
+            .apply(&mut mask)
+            .map_err(|error| {
+                OperationError::new(Box::new(self.clone()), error.get_info().clone())
+            })?;
+        let mask = mask.to_luma8();
+
+        let (text_width, text_height) = mask
+            .enumerate_pixels()
+            .filter(|(_, _, pixel)| pixel[0] > 0)
+            .fold((0, 0), |(w, h), (x, y, _)| (w.max(x + 1), h.max(y + 1)));
+        if text_width == 0 {
+            return Ok(());
+        }
+
+        let left = (width as i64 - text_width as i64) / 2;
+        let top = height as i64 - CAPTION_MARGIN as i64 - text_height as i64;
+
+        let strip_top = (top - CAPTION_MARGIN as i64).max(0) as u32;
+        let strip = image.crop_imm(0, strip_top, width, height - strip_top);
+        let [r, g, b, _] = StaticThumbnail::from_dynamic_image(strip).average_color();
+        let luminance = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+        let color = if luminance < 128.0 { 255 } else { 0 };
+
+        for (mask_x, mask_y, coverage) in mask.enumerate_pixels() {
+            let x = left + mask_x as i64;
+            let y = top + mask_y as i64;
+            if coverage[0] == 0 || x < 0 || y < 0 || x >= width as i64 || y >= height as i64 {
+                continue;
+            }
+
+            let mut pixel = image.get_pixel(x as u32, y as u32);
+            pixel.blend(&Rgba([color, color, color, coverage[0]]));
+            image.put_pixel(x as u32, y as u32, pixel);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `CaptionOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Caption(self.clone()))
+    }
+}
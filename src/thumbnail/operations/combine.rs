@@ -1,25 +1,81 @@
 pub use crate::errors::{OperationError, OperationErrorInfo};
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use crate::{BoxPosition, StaticThumbnail};
-use image::{DynamicImage, GenericImageView};
-use std::fmt;
-use std::fmt::Formatter;
+use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Primitive, Rgba};
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the combine operation as a struct
 pub struct CombineOp {
     /// The overlay image as `StaticThumbnail`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "path", with = "crate::thumbnail::static_thumb::by_path")
+    )]
     image: StaticThumbnail,
     /// Specifies the position of the Text, represented by `BoxPosition` enum
     pos: BoxPosition,
+    /// Whether the operation fails if the overlay image does not fully fit onto the background.
+    /// Otherwise the overlay image is clipped at the edges of the background.
+    #[cfg_attr(feature = "serde", serde(default))]
+    strict_bounds: bool,
 }
 
 impl<'a> CombineOp {
     /// Returns a new `CombineOp` struct with defined:
     /// * `image` as the image that should be drawn on the 'DynamicImage'
     /// * `pos` as the position of the text represented by `BoxPosition` enum
+    ///
+    /// Parts of the overlay image, that do not fit onto the background, are clipped.
     pub fn new(image: StaticThumbnail, pos: BoxPosition) -> Self {
-        CombineOp { image, pos }
+        CombineOp {
+            image,
+            pos,
+            strict_bounds: false,
+        }
+    }
+
+    /// Returns a new `CombineOp` struct with defined:
+    /// * `image` as the image that should be drawn on the 'DynamicImage'
+    /// * `pos` as the position of the text represented by `BoxPosition` enum
+    ///
+    /// Unlike with `new()` the operation fails with `CoordinatesOutOfRange`,
+    /// if the overlay image does not fully fit onto the background.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::BoxPosition;
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::CombineOp;
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    /// use std::path::PathBuf;
+    ///
+    /// // A red overlay, placed half off the right edge of the background
+    /// let overlay = DynamicImage::ImageRgba8(RgbaImage::from_pixel(50, 50, Rgba([255, 0, 0, 255])));
+    /// let overlay = StaticThumbnail::new(PathBuf::from("overlay.png"), overlay);
+    /// let position = BoxPosition::TopLeft(75, 25);
+    ///
+    /// // strict: the operation fails and the background stays untouched
+    /// let mut background = DynamicImage::new_rgba8(100, 100);
+    /// let res = CombineOp::new_strict_bounds(overlay.clone(), position).apply(&mut background);
+    /// assert!(res.is_err());
+    /// assert_eq!(background.get_pixel(80, 30), Rgba([0, 0, 0, 0]));
+    ///
+    /// // default: the overlay is clipped at the edge of the background
+    /// let mut background = DynamicImage::new_rgba8(100, 100);
+    /// let res = CombineOp::new(overlay, position).apply(&mut background);
+    /// assert!(res.is_ok());
+    /// assert_eq!(background.get_pixel(99, 30)[0], 255);
+    /// ```
+    pub fn new_strict_bounds(image: StaticThumbnail, pos: BoxPosition) -> Self {
+        CombineOp {
+            image,
+            pos,
+            strict_bounds: true,
+        }
     }
 }
 
@@ -41,8 +97,10 @@ impl Operation for CombineOp {
     ///
     /// # Errors
     ///
-    /// * CoordinatesOutOfRange - The coordinates for the overlayed image are not inside the background image
-    /// * ImageBufferConversionFailure - The supplied background image cannot be converted to an 'ImageBuffer'
+    /// * CoordinatesOutOfRange - The coordinates for the overlayed image are not inside the background image,
+    ///   or, with strict bounds, the overlayed image does not fully fit onto the background image
+    /// * ImageBufferConversionFailure - The supplied background image cannot be converted to an 'ImageBuffer'.
+    ///   Supported are RGB and RGBA images with 8 or 16 bits per channel.
     ///
     /// # Panic
     ///
@@ -111,71 +169,90 @@ impl Operation for CombineOp {
             }
         };
 
-        let overlay_image_buffer = self.image.as_dyn().to_rgba();
         let (bg_width, bg_height) = image.dimensions();
 
-        match image.as_mut_rgba8() {
-            Some(background_buffer) => {
-                // Insertion of the overlay if the background ist a RgbaImage
-                for (x, y, pixel) in overlay_image_buffer.enumerate_pixels() {
-                    let x_pos_current_pixel = x + x_pos_overlay_image;
-                    let y_pos_current_pixel = y + y_pos_overlay_image;
+        if self.strict_bounds
+            && (x_pos_overlay_image as u64 + overlay_width as u64 > bg_width as u64
+                || y_pos_overlay_image as u64 + overlay_height as u64 > bg_height as u64)
+        {
+            return Err(OperationError::new(
+                Box::new(self.clone()),
+                OperationErrorInfo::CoordinatesOutOfRange,
+            ));
+        }
 
-                    if x_pos_current_pixel < bg_width && y_pos_current_pixel < bg_height {
-                        let background_pixel = background_buffer
-                            .get_pixel_mut(x_pos_current_pixel, y_pos_current_pixel);
-                        let alpha = pixel[3] as f32 / 255.0;
-                        let alpha_inv = 1.0 - alpha;
+        let position = (x_pos_overlay_image, y_pos_overlay_image);
+        let overlay = self.image.as_dyn();
 
-                        for index in 0..2 {
-                            background_pixel[index] = (alpha * pixel[index] as f32
-                                + alpha_inv * background_pixel[index] as f32)
-                                as u8;
-                        }
-                    }
-                }
+        // 16-bit backgrounds are blended with a 16-bit overlay, to keep their precision
+        match image {
+            DynamicImage::ImageRgba8(background) => {
+                blend_overlay(background, &overlay.to_rgba8(), position)
+            }
+            DynamicImage::ImageRgb8(background) => {
+                blend_overlay(background, &overlay.to_rgba8(), position)
+            }
+            DynamicImage::ImageRgba16(background) => {
+                blend_overlay(background, &overlay.to_rgba16(), position)
+            }
+            DynamicImage::ImageRgb16(background) => {
+                blend_overlay(background, &overlay.to_rgba16(), position)
+            }
+            _ => {
+                return Err(OperationError::new(
+                    Box::new(self.clone()),
+                    OperationErrorInfo::ImageBufferConversionFailure,
+                ))
             }
-            None => match image.as_mut_rgb8() {
-                Some(background_buffer) => {
-                    // Insertion of the overlay if the background is a RgbImage
-                    for (x, y, pixel) in overlay_image_buffer.enumerate_pixels() {
-                        let x_pos_current_pixel = x + x_pos_overlay_image;
-                        let y_pos_current_pixel = y + y_pos_overlay_image;
-
-                        if x_pos_current_pixel < bg_width && y_pos_current_pixel < bg_height {
-                            let background_pixel = background_buffer
-                                .get_pixel_mut(x_pos_current_pixel, y_pos_current_pixel);
-                            let alpha = pixel[3] as f32 / 255.0;
-                            let alpha_inv = 1.0 - alpha;
-
-                            for index in 0..2 {
-                                background_pixel[index] = (alpha * pixel[index] as f32
-                                    + alpha_inv * background_pixel[index] as f32)
-                                    as u8;
-                            }
-                        }
-                    }
-                }
-                None => {
-                    return Err(OperationError::new(
-                        Box::new(self.clone()),
-                        OperationErrorInfo::ImageBufferConversionFailure,
-                    ))
-                }
-            },
         };
 
         Ok(())
     }
+
+    /// Gets the serializable representation of the `CombineOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Combine(self.clone()))
+    }
 }
 
-impl fmt::Debug for CombineOp {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "CombineOp: StaticThumbnail {} at pos {:?}",
-            self.image.get_src_path().to_str().unwrap_or_default(),
-            self.pos
-        )
+/// Blends the overlay onto the background, with the top-left corner of the overlay at the given position
+///
+/// The color channels are blended according to the alpha channel of the overlay,
+/// the alpha channel of the background is kept. Pixels outside of the background are skipped.
+///
+/// * background: &mut ImageBuffer<P, Vec<S>> - The background, RGB or RGBA with 8 or 16 bits per channel
+/// * overlay: &ImageBuffer<Rgba<S>, Vec<S>> - The overlay, with the same bit depth as the background
+/// * position: (u32, u32) - The position of the top-left corner of the overlay on the background
+fn blend_overlay<P, S>(
+    background: &mut ImageBuffer<P, Vec<S>>,
+    overlay: &ImageBuffer<Rgba<S>, Vec<S>>,
+    position: (u32, u32),
+) where
+    P: Pixel<Subpixel = S> + 'static,
+    S: Primitive + 'static,
+{
+    let (bg_width, bg_height) = background.dimensions();
+    let max_value = S::max_value().to_f32().unwrap_or(1.0);
+
+    for (x, y, pixel) in overlay.enumerate_pixels() {
+        let x_pos_current_pixel = x + position.0;
+        let y_pos_current_pixel = y + position.1;
+
+        if x_pos_current_pixel < bg_width && y_pos_current_pixel < bg_height {
+            let background_pixel = background
+                .get_pixel_mut(x_pos_current_pixel, y_pos_current_pixel)
+                .channels_mut();
+            let alpha = pixel[3].to_f32().unwrap_or(0.0) / max_value;
+            let alpha_inv = 1.0 - alpha;
+
+            for index in 0..3 {
+                let value = alpha * pixel[index].to_f32().unwrap_or(0.0)
+                    + alpha_inv * background_pixel[index].to_f32().unwrap_or(0.0);
+                if let Some(value) = S::from(value) {
+                    background_pixel[index] = value;
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,179 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{CombineOp, Operation, ResizeOp};
+use crate::{BoxPosition, Fit, Resize, StaticThumbnail};
+use image::DynamicImage;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the combine-in-region operation as a struct
+pub struct CombineInRegionOp {
+    /// The overlay image as `StaticThumbnail`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "path", with = "crate::thumbnail::static_thumb::by_path")
+    )]
+    image: StaticThumbnail,
+    /// The region of the background the overlay is drawn into, as (position_x, position_y, width, height)
+    region: (u32, u32, u32, u32),
+    /// How the overlay image is scaled to the region, represented by `Fit` enum
+    fit: Fit,
+}
+
+impl CombineInRegionOp {
+    /// Returns a new `CombineInRegionOp` struct with defined:
+    /// * `image` as the image that should be drawn on the 'DynamicImage'
+    /// * `region` as the region of the background the image is drawn into, as (position_x, position_y, width, height)
+    /// * `fit` as the way the image is scaled to the region, represented by `Fit` enum
+    pub fn new(image: StaticThumbnail, region: (u32, u32, u32, u32), fit: Fit) -> Self {
+        CombineInRegionOp { image, region, fit }
+    }
+
+    /// Scales the overlay image to the region as defined by `fit`
+    ///
+    /// Returns the scaled overlay and its position relative to the top-left-corner of the region.
+    /// With `Fit::Cover` the overflowing parts are cut off evenly on both sides,
+    /// with `Fit::Contain` the overlay is centered in the region.
+    fn fit_overlay(&self) -> Result<(DynamicImage, (u32, u32)), OperationError> {
+        let (width, height) = self.image.dimensions();
+        let (width, height) = (width as u64, height as u64);
+        let (region_width, region_height) = (self.region.2 as u64, self.region.3 as u64);
+        // Whether the overlay is wider than the region, relative to their heights
+        let wider = width * region_height >= height * region_width;
+
+        #[allow(clippy::manual_div_ceil)] // div_ceil() needs Rust 1.73
+        let (scaled_width, scaled_height) = match (self.fit, wider) {
+            (Fit::Stretch, _) => (region_width, region_height),
+            (Fit::Cover, true) => ((width * region_height + height - 1) / height, region_height),
+            (Fit::Cover, false) => (region_width, (height * region_width + width - 1) / width),
+            (Fit::Contain, true) => (region_width, (height * region_width / width).max(1)),
+            (Fit::Contain, false) => ((width * region_height / height).max(1), region_height),
+        };
+        let (scaled_width, scaled_height) = (scaled_width as u32, scaled_height as u32);
+
+        let mut overlay = self.image.as_dyn().clone();
+        ResizeOp::new(Resize::ExactBox(scaled_width, scaled_height), None).apply(&mut overlay)?;
+
+        let (region_width, region_height) = (self.region.2, self.region.3);
+        match self.fit {
+            Fit::Cover => {
+                let left = (scaled_width - region_width) / 2;
+                let top = (scaled_height - region_height) / 2;
+                let overlay = overlay.crop_imm(left, top, region_width, region_height);
+                Ok((overlay, (0, 0)))
+            }
+            Fit::Contain => {
+                let left = (region_width - scaled_width) / 2;
+                let top = (region_height - scaled_height) / 2;
+                Ok((overlay, (left, top)))
+            }
+            Fit::Stretch => Ok((overlay, (0, 0))),
+        }
+    }
+}
+
+impl Operation for CombineInRegionOp {
+    /// Logic for the operation of drawing an image into a region of another image
+    ///
+    /// This function scales a `StaticThumbnail` to the region of a `DynamicImage` given as
+    /// (position_x, position_y, width, height), and draws it there like `CombineOp`.
+    /// How the image is scaled is defined by the `Fit`-enum:
+    /// * with `Fit::Cover`: The image is scaled to cover the whole region, keeping its aspect ratio.
+    ///   The parts outside of the region are cut off evenly on both sides.
+    /// * with `Fit::Contain`: The image is scaled to fit inside the region, keeping its aspect ratio.
+    ///   It is centered in the region, the rest of the region stays untouched.
+    /// * with `Fit::Stretch`: The image is scaled to the size of the region exactly, the aspect ratio may be changed.
+    ///
+    /// As with `CombineOp`, parts of the region outside of the background are clipped.
+    /// Nothing is drawn if the region or the image is empty.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `CombineInRegionOp` struct
+    /// * `image` - The `DynamicImage` where the image should be drawn on
+    ///
+    /// # Errors
+    ///
+    /// * ImageBufferConversionFailure - The supplied background image cannot be converted to an 'ImageBuffer'.
+    ///   Supported are RGB and RGBA images with 8 or 16 bits per channel.
+    /// * CoordinatesOutOfRange - The position of the overlay in the region does not fit into a `u32`.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::Fit;
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::CombineInRegionOp;
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    ///
+    /// let overlay = DynamicImage::ImageRgba8(RgbaImage::from_pixel(100, 100, Rgba([255, 0, 0, 255])));
+    /// let overlay = StaticThumbnail::from_dynamic_image(overlay);
+    /// let region = (20, 30, 50, 80);
+    ///
+    /// // cover: the overlay fills the whole region, and nothing outside of it
+    /// let mut dynamic_image = DynamicImage::new_rgba8(200, 200);
+    /// let combine_op = CombineInRegionOp::new(overlay.clone(), region, Fit::Cover);
+    /// assert!(combine_op.apply(&mut dynamic_image).is_ok());
+    /// for (x, y, pixel) in dynamic_image.pixels() {
+    ///     let inside = (20..70).contains(&x) && (30..110).contains(&y);
+    ///     assert_eq!(pixel[0] == 255, inside, "pixel ({}, {})", x, y);
+    /// }
+    ///
+    /// // contain: the overlay is scaled to 50x50 and centered vertically in the region
+    /// let mut dynamic_image = DynamicImage::new_rgba8(200, 200);
+    /// let combine_op = CombineInRegionOp::new(overlay, region, Fit::Contain);
+    /// assert!(combine_op.apply(&mut dynamic_image).is_ok());
+    /// assert_eq!(dynamic_image.get_pixel(20, 44)[0], 0);
+    /// assert_eq!(dynamic_image.get_pixel(20, 45)[0], 255);
+    /// assert_eq!(dynamic_image.get_pixel(69, 94)[0], 255);
+    /// assert_eq!(dynamic_image.get_pixel(69, 95)[0], 0);
+    ///
+    /// // the overlay would be centered at a position beyond u32::MAX
+    /// let overlay = StaticThumbnail::from_dynamic_image(DynamicImage::new_rgba8(10, 10));
+    /// let combine_op = CombineInRegionOp::new(overlay, (u32::MAX - 5, 0, 50, 10), Fit::Contain);
+    /// assert!(combine_op.apply(&mut dynamic_image).is_err());
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (width, height) = self.image.dimensions();
+        if width == 0 || height == 0 || self.region.2 == 0 || self.region.3 == 0 {
+            return Ok(());
+        }
+
+        let to_own_error = |error: OperationError| {
+            OperationError::new(Box::new(self.clone()), error.get_info().clone())
+        };
+
+        let (overlay, (left, top)) = self.fit_overlay().map_err(to_own_error)?;
+        let (x, y) = match (
+            self.region.0.checked_add(left),
+            self.region.1.checked_add(top),
+        ) {
+            (Some(x), Some(y)) => (x, y),
+            _ => {
+                return Err(OperationError::new(
+                    Box::new(self.clone()),
+                    OperationErrorInfo::CoordinatesOutOfRange,
+                ))
+            }
+        };
+        let position = BoxPosition::TopLeft(x, y);
+        CombineOp::new(StaticThumbnail::from_dynamic_image(overlay), position)
+            .apply(image)
+            .map_err(to_own_error)
+    }
+
+    /// Gets the serializable representation of the `CombineInRegionOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::CombineInRegion(self.clone()))
+    }
+}
@@ -1,8 +1,11 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use image::DynamicImage;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the contrast-operation as a struct.
 pub struct ContrastOp {
     /// Value of how much the contrast should be adjusted.
@@ -54,4 +57,10 @@ impl Operation for ContrastOp {
         *image = image.adjust_contrast(self.value);
         Ok(())
     }
+
+    /// Gets the serializable representation of the `ContrastOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Contrast(*self))
+    }
 }
@@ -1,9 +1,12 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
-use crate::Crop;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::{Crop, Gravity};
 use image::{DynamicImage, GenericImageView};
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the crop-operation as a struct
 pub struct CropOp {
     /// contains the `Crop` enum as option
@@ -16,6 +19,40 @@ impl CropOp {
     pub fn new(crop: Crop) -> Self {
         CropOp { crop }
     }
+
+    /// Crops the image to the maximum rectangle with the given width-height-ratio
+    ///
+    /// The position of the rectangle inside the image is given by the gravity.
+    ///
+    /// * image: &mut DynamicImage - The image to crop
+    /// * w_r: f32 - The width part of the ratio
+    /// * h_r: f32 - The height part of the ratio
+    /// * gravity: Gravity - The part of the image that is kept
+    fn crop_ratio(image: &mut DynamicImage, w_r: f32, h_r: f32, gravity: Gravity) {
+        let (width, height) = image.dimensions();
+        let ratio_old = width as f32 / height as f32;
+        let ratio_new = w_r / h_r;
+
+        if ratio_old <= ratio_new {
+            let height_new = ((ratio_old / ratio_new) * height as f32) as u32;
+            let y_new = match gravity {
+                Gravity::North => 0,
+                Gravity::South => height - height_new,
+                _ => (height - height_new) / 2,
+            };
+
+            *image = image.crop(0, y_new, width, height_new);
+        } else {
+            let width_new = ((ratio_new / ratio_old) * width as f32) as u32;
+            let x_new = match gravity {
+                Gravity::West => 0,
+                Gravity::East => width - width_new,
+                _ => (width - width_new) / 2,
+            };
+
+            *image = image.crop(x_new, 0, width_new, height);
+        }
+    }
 }
 
 impl Operation for CropOp {
@@ -24,7 +61,10 @@ impl Operation for CropOp {
     /// This function crops a `DynamicImage`, based on the type of the `Crop` enum
     /// * with `Crop::Box`: Exactly crops the image to a rectangle defined by the coordinates of the top-left-corner, a width and a height.
     /// * with `Crop::Ratio`: Crops the image to a rectangle given by a width-height-ratio. The rectangle is scaled to the maximum that fits
-    /// inside the image
+    ///   inside the image
+    /// * with `Crop::RatioGravity`: Crops the image like `Crop::Ratio`, but keeps the part of the image given by the `Gravity`
+    ///   instead of the center. `Gravity::North` and `Gravity::South` only apply to images that are cropped vertically,
+    ///   `Gravity::East` and `Gravity::West` only to images that are cropped horizontally. Otherwise the center is kept.
     ///
     /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
     ///
@@ -39,10 +79,10 @@ impl Operation for CropOp {
     ///
     /// # Examples
     /// ```
-    /// use thumbnailer::generic::Crop;
+    /// use thumbnailer::generic::{Crop, Gravity};
     /// use thumbnailer::thumbnail::operations::Operation;
     /// use thumbnailer::thumbnail::operations::CropOp;
-    /// use image::DynamicImage;
+    /// use image::{DynamicImage, GenericImageView, GrayImage, Luma};
     ///
     /// let crop = Crop::Ratio(16.0, 9.0);
     /// let mut dynamic_image = DynamicImage::new_rgb8(800, 500);
@@ -51,31 +91,35 @@ impl Operation for CropOp {
     /// let res = crop_op.apply(&mut dynamic_image);
     ///
     /// assert!(res.is_ok());
+    ///
+    /// // A tall image, in which each row has the value of its y coordinate
+    /// let tall = DynamicImage::ImageLuma8(GrayImage::from_fn(100, 200, |_, y| Luma([y as u8])));
+    ///
+    /// let mut north = tall.clone();
+    /// assert!(CropOp::new(Crop::RatioGravity(1.0, 1.0, Gravity::North)).apply(&mut north).is_ok());
+    /// assert_eq!(north.dimensions(), (100, 100));
+    /// assert_eq!(north.get_pixel(0, 0)[0], 0);
+    ///
+    /// let mut south = tall;
+    /// assert!(CropOp::new(Crop::RatioGravity(1.0, 1.0, Gravity::South)).apply(&mut south).is_ok());
+    /// assert_eq!(south.dimensions(), (100, 100));
+    /// assert_eq!(south.get_pixel(0, 0)[0], 100);
+    /// assert_eq!(south.get_pixel(0, 99)[0], 199);
     /// ```
     fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError> {
-        let (width, height) = image.dimensions();
-
         match self.crop {
             Crop::Box(x, y, w, h) => {
                 *image = image.crop(x, y, w, h);
             }
-            Crop::Ratio(w_r, h_r) => {
-                let ratio_old = width as f32 / height as f32;
-                let ratio_new = w_r / h_r;
-
-                if ratio_old <= ratio_new {
-                    let height_new = ((ratio_old / ratio_new) * height as f32) as u32;
-                    let y_new = (height - height_new) / 2;
-
-                    *image = image.crop(0, y_new, width, height_new);
-                } else {
-                    let width_new = ((ratio_new / ratio_old) * width as f32) as u32;
-                    let x_new = (width - width_new) / 2;
-
-                    *image = image.crop(x_new, 0, width_new, height);
-                }
-            }
+            Crop::Ratio(w_r, h_r) => CropOp::crop_ratio(image, w_r, h_r, Gravity::Center),
+            Crop::RatioGravity(w_r, h_r, gravity) => CropOp::crop_ratio(image, w_r, h_r, gravity),
         }
         Ok(())
     }
+
+    /// Gets the serializable representation of the `CropOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Crop(*self))
+    }
 }
@@ -0,0 +1,156 @@
+pub use crate::errors::OperationError;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{CombineOp, OpacityOp, Operation, RotateOp, TextOp};
+use crate::{BoxPosition, Rotation, RotationFill, StaticThumbnail};
+use image::{DynamicImage, GenericImageView};
+
+/// The margin around the text, when it is rendered onto its tile
+const TEXT_MARGIN: u32 = 2;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the diagonal-watermark-operation as a struct
+pub struct DiagonalWatermarkOp {
+    /// The text of the watermark
+    text: String,
+    /// The opacity of the watermark, between 0.0 (invisible) and 1.0 (opaque)
+    opacity: f32,
+    /// The space in pixels between the repetitions of the watermark
+    spacing: u32,
+}
+
+impl DiagonalWatermarkOp {
+    /// Returns a new `DiagonalWatermarkOp` struct with defined:
+    /// * `text`: The text of the watermark, e.g. "SAMPLE"
+    /// * `opacity`: The opacity of the watermark, between 0.0 (invisible) and 1.0 (opaque).
+    ///   Other values are clamped to that range.
+    /// * `spacing`: The space in pixels between the repetitions of the watermark, horizontally and vertically
+    pub fn new(text: String, opacity: f32, spacing: u32) -> Self {
+        DiagonalWatermarkOp {
+            text,
+            opacity: opacity.clamp(0.0, 1.0),
+            spacing,
+        }
+    }
+
+    /// Renders the text onto a transparent tile, rotated by 45 degrees counter-clockwise and faded to the opacity
+    ///
+    /// Returns `Ok(None)` if the text renders no pixels, e.g. if it is empty or consists of whitespace only.
+    fn render_tile(&self) -> Result<Option<DynamicImage>, OperationError> {
+        // The font is 12 pixels high, no glyph is wider than that
+        let width = (self.text.chars().count() as u32 + 1) * 12 + 2 * TEXT_MARGIN;
+        let mut tile = DynamicImage::new_rgba8(width, 16 + 2 * TEXT_MARGIN);
+        let position = BoxPosition::TopLeft(TEXT_MARGIN, TEXT_MARGIN);
+        TextOp::new(self.text.clone(), position).apply(&mut tile)?;
+
+        // The tile is cut down to the rendered pixels, so the spacing is measured from the text itself
+        let (mut left, mut top, mut right, mut bottom) = (u32::MAX, u32::MAX, 0, 0);
+        for (x, y, pixel) in tile.pixels() {
+            if pixel[3] > 0 {
+                left = left.min(x);
+                top = top.min(y);
+                right = right.max(x + 1);
+                bottom = bottom.max(y + 1);
+            }
+        }
+        if left >= right {
+            return Ok(None);
+        }
+        let mut tile = tile.crop_imm(left, top, right - left, bottom - top);
+
+        RotateOp::new_with_fill(Rotation::Degrees(-45.0), RotationFill::Transparent)
+            .apply(&mut tile)?;
+        OpacityOp::new(self.opacity).apply(&mut tile)?;
+        Ok(Some(tile))
+    }
+}
+
+impl Operation for DiagonalWatermarkOp {
+    /// Logic for the diagonal-watermark-operation
+    ///
+    /// This function covers a `DynamicImage` with a repeating, diagonal text, like the "SAMPLE" watermark of stock photos.
+    /// The text is drawn in white like with `TextOp`, rotated by 45 degrees counter-clockwise with `RotateOp`,
+    /// and faded to `opacity` with `OpacityOp`. The result is repeated in rows and columns over the whole image,
+    /// with `spacing` pixels between the repetitions, and drawn on the image with `CombineOp`.
+    /// Repetitions at the right and bottom edges of the image are clipped.
+    /// A spacing larger than the image leaves only the repetition in the top left corner.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `DiagonalWatermarkOp` struct
+    /// * `image` - The `DynamicImage` that should be watermarked
+    ///
+    /// # Errors
+    ///
+    /// * FontLoadError - The font cannnot be loaded
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::DiagonalWatermarkOp;
+    /// use image::{DynamicImage, GenericImageView};
+    ///
+    /// let mut dynamic_image = DynamicImage::new_rgb8(200, 200);
+    ///
+    /// let watermark_op = DiagonalWatermarkOp::new("SAMPLE".to_string(), 0.5, 10);
+    /// let res = watermark_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// // The watermark appears in each quadrant of the image, faded to about half of the brightness
+    /// for (left, top) in vec![(0, 0), (100, 0), (0, 100), (100, 100)] {
+    ///     let brightest = dynamic_image
+    ///         .view(left, top, 100, 100)
+    ///         .pixels()
+    ///         .map(|(_, _, pixel)| pixel[0])
+    ///         .max()
+    ///         .unwrap();
+    ///     assert!(brightest > 32 && brightest <= 128, "no watermark at ({}, {})", left, top);
+    /// }
+    ///
+    /// // the largest spacing repeats the watermark only once
+    /// let mut dynamic_image = DynamicImage::new_rgb8(200, 200);
+    /// let watermark_op = DiagonalWatermarkOp::new("SAMPLE".to_string(), 0.5, u32::MAX);
+    /// assert!(watermark_op.apply(&mut dynamic_image).is_ok());
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let to_own_error = |error: OperationError| {
+            OperationError::new(Box::new(self.clone()), error.get_info().clone())
+        };
+
+        let tile = match self.render_tile().map_err(to_own_error)? {
+            Some(tile) => tile,
+            None => return Ok(()),
+        };
+
+        let (width, height) = image.dimensions();
+        let (tile_width, tile_height) = tile.dimensions();
+        // The steps saturate, a step beyond the image size leaves a single repetition
+        let step_x = tile_width.saturating_add(self.spacing) as usize;
+        let step_y = tile_height.saturating_add(self.spacing) as usize;
+        for y in (0..height).step_by(step_y) {
+            for x in (0..width).step_by(step_x) {
+                let overlay = StaticThumbnail::from_dynamic_image(tile.clone());
+                CombineOp::new(overlay, BoxPosition::TopLeft(x, y))
+                    .apply(image)
+                    .map_err(to_own_error)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `DiagonalWatermarkOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::DiagonalWatermark(self.clone()))
+    }
+}
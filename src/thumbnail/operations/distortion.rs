@@ -0,0 +1,135 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::rotate::interpolate;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the distortion-correction-operation as a struct
+pub struct DistortionOp {
+    /// The coefficient of the second power of the radius
+    k1: f32,
+    /// The coefficient of the fourth power of the radius
+    k2: f32,
+}
+
+impl DistortionOp {
+    /// Returns a new `DistortionOp` struct with defined:
+    /// * `k1`: The coefficient of the second power of the radius. Negative values correct barrel distortion,
+    ///   positive values correct pincushion distortion.
+    /// * `k2`: The coefficient of the fourth power of the radius, to correct distortions that change with the radius
+    pub fn new(k1: f32, k2: f32) -> Self {
+        DistortionOp { k1, k2 }
+    }
+}
+
+impl Operation for DistortionOp {
+    /// Logic for the distortion-correction-operation
+    ///
+    /// This function corrects the radial distortion of a lens in a `DynamicImage` with the polynomial model.
+    /// Each pixel of the corrected image at the radius `r` from the center of the image is taken from the radius
+    /// `r * (1 + k1 * r^2 + k2 * r^4)` of the distorted image, in the same direction, and interpolated bilinearly.
+    /// The radius is relative to half of the diagonal of the image, so the corners are at the radius 1.0.
+    /// Pixels that are taken from outside of the image are transparent, or black for images without alpha channel.
+    /// The image is converted to `ImageRgba8`, or to `ImageRgb8` if it has no alpha channel.
+    /// With `k1` and `k2` both 0.0 the image stays untouched.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `DistortionOp` struct
+    /// * `image` - The `DynamicImage` that should be corrected
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::DistortionOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// let radius = (200.0f32 * 200.0 + 200.0 * 200.0).sqrt() / 2.0;
+    /// let k1 = -0.2;
+    ///
+    /// // A vertical line 20 pixels from the right border, bent outwards by barrel distortion
+    /// let mut image = RgbImage::new(200, 200);
+    /// for step in 0..2000 {
+    ///     let (x, y) = (80.0 / radius, (step as f32 / 10.0 - 100.0) / radius);
+    ///     let factor = 1.0 + k1 * (x * x + y * y);
+    ///     let (x, y) = (x * factor * radius + 100.0, y * factor * radius + 100.0);
+    ///     image.put_pixel(x as u32, y as u32, Rgb([255, 255, 255]));
+    /// }
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(image);
+    ///
+    /// // Returns how far the line is bent: the range of its horizontal position in the rows 30 to 170
+    /// fn bend(image: &DynamicImage) -> f32 {
+    ///     let positions: Vec<f32> = (30..170)
+    ///         .map(|y| {
+    ///             let row: Vec<f32> = (0..200).map(|x| image.get_pixel(x, y)[0] as f32).collect();
+    ///             let sum: f32 = row.iter().sum();
+    ///             row.iter().enumerate().map(|(x, value)| x as f32 * value).sum::<f32>() / sum
+    ///         })
+    ///         .collect();
+    ///     let min = positions.iter().cloned().fold(f32::MAX, f32::min);
+    ///     let max = positions.iter().cloned().fold(f32::MIN, f32::max);
+    ///     max - min
+    /// }
+    ///
+    /// let bent = bend(&dynamic_image);
+    /// let distortion_op = DistortionOp::new(k1, 0.0);
+    /// let res = distortion_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// // The line is straight again, up to the rounding of its pixels
+    /// assert!(bent > 4.0);
+    /// assert!(bend(&dynamic_image) < bent / 3.0);
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (width, height) = image.dimensions();
+        if (self.k1 == 0.0 && self.k2 == 0.0) || width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let source = image.to_rgba8();
+        let (k1, k2) = (self.k1 as f64, self.k2 as f64);
+        let (center_x, center_y) = (width as f64 / 2.0, height as f64 / 2.0);
+        let radius = (center_x * center_x + center_y * center_y).sqrt();
+
+        let corrected = RgbaImage::from_fn(width, height, |x, y| {
+            let dx = (x as f64 + 0.5 - center_x) / radius;
+            let dy = (y as f64 + 0.5 - center_y) / radius;
+            let r2 = dx * dx + dy * dy;
+            let factor = 1.0 + k1 * r2 + k2 * r2 * r2;
+            let source_x = dx * factor * radius + center_x - 0.5;
+            let source_y = dy * factor * radius + center_y - 0.5;
+
+            let inside = (-0.5..=width as f64 - 0.5).contains(&source_x)
+                && (-0.5..=height as f64 - 0.5).contains(&source_y);
+            if inside {
+                interpolate(&source, source_x, source_y)
+            } else {
+                Rgba([0, 0, 0, 0])
+            }
+        });
+
+        *image = if image.color().has_alpha() {
+            DynamicImage::ImageRgba8(corrected)
+        } else {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(corrected).to_rgb8())
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `DistortionOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Distortion(*self))
+    }
+}
@@ -0,0 +1,88 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GrayImage, Luma};
+use imageproc::gradients::sobel_gradients;
+
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the edge-detection-operation as struct
+pub struct EdgeDetectOp;
+
+impl EdgeDetectOp {
+    /// Returns a new `EdgeDetectOp` struct
+    pub fn new() -> Self {
+        EdgeDetectOp {}
+    }
+}
+
+impl Operation for EdgeDetectOp {
+    /// Logic for the edge-detection-operation
+    ///
+    /// This function converts a `DynamicImage` to grayscale and applies the Sobel operator to it.
+    /// The result is a high-contrast edge map, where the gradient magnitudes are normalized into the range 0-255.
+    /// The strongest edge of the image is white, flat areas are black.
+    /// More information: [Sobel operator](https://en.wikipedia.org/wiki/Sobel_operator)
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `EdgeDetectOp` struct
+    /// * `image` - The `DynamicImage` in which the edges should be detected
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::EdgeDetectOp;
+    /// use image::{DynamicImage, GrayImage, Luma};
+    ///
+    /// // A single vertical boundary, black on the left, white on the right
+    /// let gray_image = GrayImage::from_fn(20, 20, |x, _| if x < 10 { Luma([0]) } else { Luma([255]) });
+    /// let mut dynamic_image = DynamicImage::ImageLuma8(gray_image);
+    ///
+    /// let edge_detect_op = EdgeDetectOp::new();
+    /// let res = edge_detect_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    ///
+    /// let edges = dynamic_image.to_luma8();
+    /// for y in 0..20 {
+    ///     // the columns along the boundary light up
+    ///     assert_eq!(edges.get_pixel(9, y), &Luma([255]));
+    ///     assert_eq!(edges.get_pixel(10, y), &Luma([255]));
+    ///     // flat areas stay dark
+    ///     assert_eq!(edges.get_pixel(3, y), &Luma([0]));
+    ///     assert_eq!(edges.get_pixel(16, y), &Luma([0]));
+    /// }
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let gradients = sobel_gradients(&image.to_luma8());
+        let max_gradient = gradients.pixels().map(|p| p[0]).max().unwrap_or(0);
+
+        let edges = GrayImage::from_fn(gradients.width(), gradients.height(), |x, y| {
+            if max_gradient == 0 {
+                Luma([0])
+            } else {
+                let gradient = gradients.get_pixel(x, y)[0] as u32;
+                Luma([(gradient * 255 / max_gradient as u32) as u8])
+            }
+        });
+
+        *image = DynamicImage::ImageLuma8(edges);
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `EdgeDetectOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::EdgeDetect(*self))
+    }
+}
@@ -0,0 +1,120 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImageView, ImageBuffer, Pixel, Rgb, Rgba};
+use imageproc::filter::filter3x3;
+
+/// The 3x3 emboss kernel. Its values add up to 0, so flat areas result in 0 before the offset is added.
+const EMBOSS_KERNEL: [i32; 9] = [-2, -1, 0, -1, 0, 1, 0, 1, 2];
+
+/// The value added to every filtered channel, which lifts flat areas to mid-gray.
+const EMBOSS_OFFSET: i32 = 128;
+
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the emboss-operation as struct
+pub struct EmbossOp;
+
+impl EmbossOp {
+    /// Returns a new `EmbossOp` struct
+    pub fn new() -> Self {
+        EmbossOp {}
+    }
+}
+
+/// Maps the result of the convolution of one channel back to the range of a `u8`
+fn emboss_channel(value: i32) -> u8 {
+    (value + EMBOSS_OFFSET).clamp(0, 255) as u8
+}
+
+impl Operation for EmbossOp {
+    /// Logic for the emboss-operation
+    ///
+    /// This function convolves a `DynamicImage` with a 3x3 emboss kernel, which results in a relief effect.
+    /// Edges gain a high contrast, depending on their direction they turn either bright or dark,
+    /// while flat areas of the image go near mid-gray.
+    /// Each color channel is processed on its own, an existing alpha channel is left untouched.
+    /// More information: [Image embossing](https://en.wikipedia.org/wiki/Image_embossing)
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `EmbossOp` struct
+    /// * `image` - The `DynamicImage` that should be embossed
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::EmbossOp;
+    /// use image::{DynamicImage, GenericImage, Rgb};
+    ///
+    /// // A white square on a black background
+    /// let mut dynamic_image = DynamicImage::new_rgb8(30, 30);
+    /// for x in 10..20 {
+    ///     for y in 10..20 {
+    ///         dynamic_image.put_pixel(x, y, image::Rgba([255, 255, 255, 255]));
+    ///     }
+    /// }
+    ///
+    /// let emboss_op = EmbossOp::new();
+    /// let res = emboss_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    ///
+    /// let embossed = dynamic_image.to_rgb8();
+    /// // the upper left edge of the square turns bright, the lower right edge turns dark
+    /// assert_eq!(embossed.get_pixel(10, 15), &Rgb([255, 255, 255]));
+    /// assert_eq!(embossed.get_pixel(19, 15), &Rgb([0, 0, 0]));
+    /// // flat areas go to mid-gray
+    /// assert_eq!(embossed.get_pixel(2, 2), &Rgb([128, 128, 128]));
+    /// assert_eq!(embossed.get_pixel(15, 15), &Rgb([128, 128, 128]));
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        if image.color().has_alpha() {
+            let source = image.to_rgba8();
+            let filtered = filter3x3::<_, i32, i32>(&source, &EMBOSS_KERNEL);
+
+            let (width, height) = source.dimensions();
+            let embossed = ImageBuffer::from_fn(width, height, |x, y| {
+                let channels = filtered.get_pixel(x, y).channels();
+                Rgba([
+                    emboss_channel(channels[0]),
+                    emboss_channel(channels[1]),
+                    emboss_channel(channels[2]),
+                    source.get_pixel(x, y)[3],
+                ])
+            });
+            *image = DynamicImage::ImageRgba8(embossed);
+        } else {
+            let source = image.to_rgb8();
+            let filtered = filter3x3::<_, i32, i32>(&source, &EMBOSS_KERNEL);
+
+            let (width, height) = image.dimensions();
+            let embossed = ImageBuffer::from_fn(width, height, |x, y| {
+                let channels = filtered.get_pixel(x, y).channels();
+                Rgb([
+                    emboss_channel(channels[0]),
+                    emboss_channel(channels[1]),
+                    emboss_channel(channels[2]),
+                ])
+            });
+            *image = DynamicImage::ImageRgb8(embossed);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `EmbossOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Emboss(*self))
+    }
+}
@@ -1,9 +1,12 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use crate::Exif;
 use image::DynamicImage;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExifOp {
     metadata: Exif,
 }
@@ -21,4 +24,10 @@ impl Operation for ExifOp {
     {
         unimplemented!()
     }
+
+    /// Gets the serializable representation of the `ExifOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Exif(self.clone()))
+    }
 }
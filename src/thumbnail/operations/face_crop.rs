@@ -0,0 +1,258 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImageView};
+use rustface::{FaceInfo, ImageData, Model};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+
+/// The detection models loaded by `FaceCropOp`, by their path, so each model is only read once
+static MODELS: Mutex<Vec<(PathBuf, Model)>> = Mutex::new(Vec::new());
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the face-crop operation as a struct
+pub struct FaceCropOp {
+    /// The path of the SeetaFace detection model used by `rustface`
+    model: PathBuf,
+    /// The space around the face, relative to the size of the face
+    padding: f32,
+}
+
+impl FaceCropOp {
+    /// Returns a new `FaceCropOp` struct with defined:
+    /// * `model` as the path of the SeetaFace frontal face detection model, e.g. `seeta_fd_frontal_v1.0.bin`.
+    ///   The model is not part of this crate, it is available from the `rustface` repository.
+    /// * `padding` as the space around the face on each side, relative to the size of the face,
+    ///   e.g. 0.5 adds half of the size of the face on each side
+    pub fn new(model: PathBuf, padding: f32) -> Self {
+        FaceCropOp { model, padding }
+    }
+
+    /// Gets the detection model
+    ///
+    /// The model is read from its path the first time it is used, and cached for all later operations.
+    /// Changes of the file afterwards are not picked up.
+    fn model(&self) -> Result<Model, OperationError> {
+        let mut models = MODELS.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, model)) = models.iter().find(|(path, _)| *path == self.model) {
+            return Ok(model.clone());
+        }
+
+        let model = match rustface::load_model(&self.model.to_string_lossy()) {
+            Ok(model) => model,
+            Err(_) => {
+                return Err(OperationError::new(
+                    Box::new(self.clone()),
+                    OperationErrorInfo::ModelLoadError,
+                ))
+            }
+        };
+        models.push((self.model.clone(), model.clone()));
+        Ok(model)
+    }
+
+    /// Detects the faces in the image and returns the bounding box of the largest one as (x, y, width, height)
+    ///
+    /// Returns `None` if no face was found.
+    fn largest_face(
+        &self,
+        image: &DynamicImage,
+    ) -> Result<Option<(i64, i64, u32, u32)>, OperationError> {
+        let mut detector = rustface::create_detector_with_model(self.model()?);
+        detector.set_min_face_size(20);
+        detector.set_score_thresh(2.0);
+        detector.set_pyramid_scale_factor(0.8);
+        detector.set_slide_window_step(4, 4);
+
+        let gray = image.to_luma8();
+        let image_data = ImageData::new(gray.as_raw(), gray.width(), gray.height());
+        let faces: Vec<FaceInfo> = detector.detect(&image_data);
+
+        Ok(faces
+            .iter()
+            .map(|face| {
+                let bbox = face.bbox();
+                (
+                    bbox.x() as i64,
+                    bbox.y() as i64,
+                    bbox.width(),
+                    bbox.height(),
+                )
+            })
+            .max_by_key(|&(_, _, width, height)| width as u64 * height as u64))
+    }
+}
+
+impl Operation for FaceCropOp {
+    /// Logic for the face-crop operation
+    ///
+    /// This function detects faces in a `DynamicImage` with `rustface`, and crops the image to a square
+    /// around the largest face, e.g. for avatars. The square is centered on the face, and has the size of the face
+    /// plus the padding on each side. It is moved or shrunk to fit into the image, if necessary.
+    /// If no face is found, the image is cropped to the largest square in its center.
+    /// Only available with the `face-detection` feature.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `FaceCropOp` struct
+    /// * `image` - The `DynamicImage` that should be cropped
+    ///
+    /// # Errors
+    ///
+    /// * ModelLoadError - The detection model cannot be loaded from the given path.
+    ///   The model is only loaded once, and cached for all `FaceCropOp`s with the same path.
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let face = self.largest_face(image)?;
+        let (x, y, size) = crop_square((width, height), face, self.padding);
+        *image = image.crop_imm(x, y, size, size);
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `FaceCropOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::FaceCrop(self.clone()))
+    }
+}
+
+/// Computes the square region the image is cropped to, as (x, y, size)
+///
+/// With a face the square is centered on it, and is the larger side of the face plus the padding on each side.
+/// The square is moved into the image, and shrunk to the shorter side of the image if it is larger.
+/// Without a face the square is the largest one in the center of the image.
+///
+/// * dimensions: (u32, u32) - The dimensions of the image
+/// * face: Option<(i64, i64, u32, u32)> - The bounding box of the face as (x, y, width, height), if any
+/// * padding: f32 - The space around the face on each side, relative to the size of the face
+fn crop_square(
+    dimensions: (u32, u32),
+    face: Option<(i64, i64, u32, u32)>,
+    padding: f32,
+) -> (u32, u32, u32) {
+    let (width, height) = dimensions;
+    let max_size = width.min(height);
+
+    let (center_x, center_y, size) = match face {
+        Some((x, y, face_width, face_height)) => {
+            let face_size = face_width.max(face_height) as f64;
+            let size = (face_size * (1.0 + 2.0 * padding.max(0.0) as f64)).round() as u32;
+            (
+                x as f64 + face_width as f64 / 2.0,
+                y as f64 + face_height as f64 / 2.0,
+                size.clamp(1, max_size),
+            )
+        }
+        None => (width as f64 / 2.0, height as f64 / 2.0, max_size),
+    };
+
+    let place = |center: f64, length: u32| {
+        let start = (center - size as f64 / 2.0).round();
+        start.clamp(0.0, (length - size) as f64) as u32
+    };
+    (place(center_x, width), place(center_y, height), size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crop_square, FaceCropOp, MODELS};
+    use crate::thumbnail::operations::Operation;
+    use image::{DynamicImage, GenericImageView};
+    use std::path::PathBuf;
+
+    /// The environment variable with the path of the SeetaFace model, which is not part of this crate
+    const MODEL_VAR: &str = "THUMBNAILER_FACE_MODEL";
+
+    /// Gets the path of the SeetaFace model from `MODEL_VAR`
+    fn model_path() -> PathBuf {
+        match std::env::var_os(MODEL_VAR) {
+            Some(model) => PathBuf::from(model),
+            None => panic!("{} is not set", MODEL_VAR),
+        }
+    }
+
+    #[test]
+    #[ignore = "needs the rustface model, set THUMBNAILER_FACE_MODEL"]
+    fn crop_contains_face() {
+        let model = model_path();
+        let op = FaceCropOp::new(model.clone(), 0.5);
+
+        // A crop of the 1927 Solvay conference photograph (public domain), showing a single face
+        let mut image = image::open("resources/tests/portrait.jpg").unwrap();
+        let (_, _, face_width, _) = op.largest_face(&image).unwrap().expect("no face found");
+        assert!(MODELS
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(path, _)| *path == model));
+
+        assert!(op.apply(&mut image).is_ok());
+        let (width, height) = image.dimensions();
+        assert_eq!(width, height);
+        assert!(width < 140);
+
+        // The face is found again, entirely inside the crop
+        let (x, y, cropped_width, cropped_height) = op
+            .largest_face(&image)
+            .unwrap()
+            .expect("no face in the crop");
+        assert!(x >= 0 && y >= 0);
+        assert!(x + cropped_width as i64 <= width as i64);
+        assert!(y + cropped_height as i64 <= height as i64);
+        assert!((cropped_width as f64 - face_width as f64).abs() <= face_width as f64 * 0.2);
+    }
+
+    #[test]
+    #[ignore = "needs the rustface model, set THUMBNAILER_FACE_MODEL"]
+    fn blank_image_is_cropped_to_center() {
+        let op = FaceCropOp::new(model_path(), 0.5);
+        let mut image = DynamicImage::new_rgb8(400, 300);
+        assert!(op.largest_face(&image).unwrap().is_none());
+
+        assert!(op.apply(&mut image).is_ok());
+        assert_eq!(image.dimensions(), (300, 300));
+    }
+
+    #[test]
+    fn crop_square_is_centered_on_face() {
+        // a 40x40 face at (100, 60), with half of its size as padding on each side
+        assert_eq!(
+            crop_square((400, 300), Some((100, 60, 40, 40)), 0.5),
+            (80, 40, 80)
+        );
+    }
+
+    #[test]
+    fn crop_square_is_moved_into_image() {
+        // a face at the top-left corner
+        assert_eq!(
+            crop_square((400, 300), Some((0, 0, 40, 40)), 0.5),
+            (0, 0, 80)
+        );
+        // a face larger than the image with its padding
+        assert_eq!(
+            crop_square((400, 300), Some((150, 50, 200, 200)), 0.5),
+            (100, 0, 300)
+        );
+    }
+
+    #[test]
+    fn crop_square_falls_back_to_center() {
+        assert_eq!(crop_square((400, 300), None, 0.5), (50, 0, 300));
+        assert_eq!(crop_square((300, 500), None, 0.5), (0, 100, 300));
+    }
+}
@@ -0,0 +1,173 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImageView, RgbaImage};
+
+/// The number of box blur passes. Three passes are a good approximation of a Gaussian blur.
+const BOX_BLUR_PASSES: u32 = 3;
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the fast-blur-operation as a struct
+pub struct FastBlurOp {
+    /// The radius of the box, a pixel is averaged with `radius` pixels in each direction.
+    radius: u32,
+}
+
+impl FastBlurOp {
+    /// Returns a new `FastBlurOp` struct with defined:
+    /// * `radius`: The radius of the box used for the box blur.
+    ///   The result is similar to the one of `BlurOp` with a `sigma` of about `1.2 * radius`.
+    pub fn new(radius: u32) -> Self {
+        FastBlurOp { radius }
+    }
+}
+
+/// Blurs the given channels of one row or column with a box of the given radius.
+///
+/// `data` holds the interleaved channels of all pixels, the pixels of the row or column are found
+/// at `start`, `start + stride`, `start + 2 * stride`, ...
+/// Pixels outside of the row or column are treated as copies of the pixels at the edges.
+/// The radius is limited to the length of the row or column, so together with the running sum
+/// the costs are independent from the radius.
+fn box_blur_line(
+    data: &mut [u8],
+    buffer: &mut Vec<[u32; 4]>,
+    start: usize,
+    stride: usize,
+    len: usize,
+    radius: usize,
+) {
+    buffer.clear();
+    buffer.extend((0..len).map(|i| {
+        let index = start + i * stride;
+        [
+            data[index] as u32,
+            data[index + 1] as u32,
+            data[index + 2] as u32,
+            data[index + 3] as u32,
+        ]
+    }));
+
+    let pixel = |i: isize| buffer[i.clamp(0, len as isize - 1) as usize];
+    let radius = radius.min(len - 1);
+    let window = (2 * radius + 1) as u64;
+    let radius = radius as isize;
+
+    let mut sum = [0u64; 4];
+    for i in -radius..=radius {
+        let p = pixel(i);
+        for c in 0..4 {
+            sum[c] += p[c] as u64;
+        }
+    }
+
+    for i in 0..len as isize {
+        let index = start + i as usize * stride;
+        for c in 0..4 {
+            data[index + c] = ((sum[c] + window / 2) / window) as u8;
+        }
+
+        let (outgoing, incoming) = (pixel(i - radius), pixel(i + radius + 1));
+        for c in 0..4 {
+            sum[c] = sum[c] + incoming[c] as u64 - outgoing[c] as u64;
+        }
+    }
+}
+
+impl Operation for FastBlurOp {
+    /// Logic for the fast-blur-operation
+    ///
+    /// This function blurs a `DynamicImage` with a separable box blur, that is applied three times.
+    /// The result approximates a Gaussian blur, but unlike `BlurOp` the costs do not grow with the radius,
+    /// which makes it considerably faster for large radii.
+    /// More information: [Box blur](https://en.wikipedia.org/wiki/Box_blur)
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `FastBlurOp` struct
+    /// * `image` - The `DynamicImage` that should be blurred
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::FastBlurOp;
+    /// use image::GenericImageView;
+    ///
+    /// let image = image::open("resources/tests/test.jpg").unwrap();
+    /// let radius = 10;
+    /// let sigma = 1.2 * radius as f32;
+    ///
+    /// let gaussian = image.blur(sigma);
+    /// let mut fast = image.clone();
+    /// assert!(FastBlurOp::new(radius).apply(&mut fast).is_ok());
+    ///
+    /// // the mean difference of the channels is small
+    /// let total_difference: u64 = gaussian
+    ///     .pixels()
+    ///     .zip(fast.pixels())
+    ///     .map(|((_, _, a), (_, _, b))| (0..3).map(|c| (a[c] as i64 - b[c] as i64).abs() as u64).sum::<u64>())
+    ///     .sum();
+    /// let mean_difference = total_difference as f64 / (image.width() * image.height() * 3) as f64;
+    /// assert!(mean_difference < 6.0, "mean difference {}", mean_difference);
+    ///
+    /// // radii larger than the image are limited to its size, a uniform image stays as it is
+    /// let mut uniform = image::DynamicImage::new_rgb8(20, 10);
+    /// assert!(FastBlurOp::new(u32::MAX).apply(&mut uniform).is_ok());
+    /// assert!(uniform.pixels().all(|(_, _, p)| p[0] == 0));
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        if self.radius == 0 || image.width() == 0 || image.height() == 0 {
+            return Ok(());
+        }
+
+        let has_alpha = image.color().has_alpha();
+        let buffer = image.to_rgba8();
+        let (width, height) = (buffer.width() as usize, buffer.height() as usize);
+        let radius = self.radius as usize;
+
+        let mut data = buffer.into_raw();
+        let mut line = Vec::with_capacity(width.max(height));
+
+        for _ in 0..BOX_BLUR_PASSES {
+            for y in 0..height {
+                box_blur_line(&mut data, &mut line, y * width * 4, 4, width, radius);
+            }
+            for x in 0..width {
+                box_blur_line(&mut data, &mut line, x * 4, width * 4, height, radius);
+            }
+        }
+
+        let blurred = match RgbaImage::from_raw(width as u32, height as u32, data) {
+            Some(blurred) => DynamicImage::ImageRgba8(blurred),
+            None => {
+                return Err(OperationError::new(
+                    Box::new(*self),
+                    OperationErrorInfo::ImageBufferConversionFailure,
+                ))
+            }
+        };
+
+        *image = if has_alpha {
+            blurred
+        } else {
+            DynamicImage::ImageRgb8(blurred.to_rgb8())
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `FastBlurOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::FastBlur(*self))
+    }
+}
@@ -1,9 +1,12 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use crate::Orientation;
 use image::DynamicImage;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the flip-operation as struct
 pub struct FlipOp {
     /// contains the `Orientation` enum
@@ -61,4 +64,10 @@ impl Operation for FlipOp {
         }
         Ok(())
     }
+
+    /// Gets the serializable representation of the `FlipOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Flip(*self))
+    }
 }
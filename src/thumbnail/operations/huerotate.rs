@@ -1,8 +1,11 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use image::DynamicImage;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the hue rotate operation as a struct.
 pub struct HuerotateOp {
     /// Degrees each picel will be hue rotated by.
@@ -52,4 +55,10 @@ impl Operation for HuerotateOp {
         *image = image.huerotate(self.degree);
         Ok(())
     }
+
+    /// Gets the serializable representation of the `HuerotateOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Huerotate(*self))
+    }
 }
@@ -1,15 +1,59 @@
-pub use crate::errors::OperationError;
+pub use crate::errors::{OperationError, OperationErrorInfo};
 use crate::thumbnail::operations::Operation;
-use image::DynamicImage;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImage, GenericImageView};
 
 #[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the invert-operation as struct
-pub struct InvertOp;
+pub struct InvertOp {
+    /// The region that should be inverted, given by the coordinates of the top-left-corner, a width and a height.
+    /// If `None`, the whole image is inverted.
+    region: Option<(u32, u32, u32, u32)>,
+}
 
 impl InvertOp {
-    /// Returns a new `InvertOp` struct
+    /// Returns a new `InvertOp` struct, which inverts the whole image
     pub fn new() -> Self {
-        InvertOp {}
+        InvertOp { region: None }
+    }
+
+    /// Returns a new `InvertOp` struct, which only inverts a rectangular region of the image, defined by:
+    /// * `x`: the x coordinate of the top-left-corner of the region
+    /// * `y`: the y coordinate of the top-left-corner of the region
+    /// * `width`: the width of the region
+    /// * `height`: the height of the region
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::InvertOp;
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    ///
+    /// let mut dynamic_image =
+    ///     DynamicImage::ImageRgba8(RgbaImage::from_pixel(30, 30, Rgba([200, 100, 50, 255])));
+    ///
+    /// // invert the center square
+    /// let invert_op = InvertOp::new_region(10, 10, 10, 10);
+    /// let res = invert_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// assert_eq!(dynamic_image.get_pixel(10, 10), Rgba([55, 155, 205, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(19, 19), Rgba([55, 155, 205, 255]));
+    /// // the border is unchanged
+    /// assert_eq!(dynamic_image.get_pixel(9, 10), Rgba([200, 100, 50, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(20, 19), Rgba([200, 100, 50, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(0, 0), Rgba([200, 100, 50, 255]));
+    ///
+    /// // a region reaching outside of the image is rejected
+    /// let invert_op = InvertOp::new_region(25, 25, 10, 10);
+    /// assert!(invert_op.apply(&mut dynamic_image).is_err());
+    /// ```
+    pub fn new_region(x: u32, y: u32, width: u32, height: u32) -> Self {
+        InvertOp {
+            region: Some((x, y, width, height)),
+        }
     }
 }
 
@@ -17,6 +61,7 @@ impl Operation for InvertOp {
     /// Logic for the invert-operation
     ///
     /// This function inverts the colors in a `Dynamic-Image`.
+    /// If a region is defined, only the colors inside of that region are inverted.
     /// More information: [Negative colors](https://en.wikipedia.org/wiki/Negative_(photography))
     /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
     ///
@@ -25,6 +70,10 @@ impl Operation for InvertOp {
     /// * `&self` - The `InvertOp` struct
     /// * `image` - The `DynamicImage` that should be inverted
     ///
+    /// # Errors
+    ///
+    /// * CoordinatesOutOfRange - The region is not fully inside the image
+    ///
     /// # Panic
     ///
     /// This function won't panic.
@@ -46,7 +95,36 @@ impl Operation for InvertOp {
     where
         Self: Sized,
     {
-        image.invert();
+        match self.region {
+            None => image.invert(),
+            Some((x, y, width, height)) => {
+                let (image_width, image_height) = image.dimensions();
+                if x as u64 + width as u64 > image_width as u64
+                    || y as u64 + height as u64 > image_height as u64
+                {
+                    return Err(OperationError::new(
+                        Box::new(*self),
+                        OperationErrorInfo::CoordinatesOutOfRange,
+                    ));
+                }
+
+                let mut region = image.crop_imm(x, y, width, height);
+                region.invert();
+                if image.copy_from(&region, x, y).is_err() {
+                    return Err(OperationError::new(
+                        Box::new(*self),
+                        OperationErrorInfo::CoordinatesOutOfRange,
+                    ));
+                }
+            }
+        }
+
         Ok(())
     }
+
+    /// Gets the serializable representation of the `InvertOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Invert(*self))
+    }
 }
@@ -0,0 +1,189 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::Channel;
+use image::DynamicImage;
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "LevelsSpec"))]
+/// Representation of the levels-operation as a struct
+pub struct LevelsOp {
+    /// The input value that is mapped to black, all darker values become black as well
+    black: u8,
+    /// The input value that is mapped to white, all brighter values become white as well
+    white: u8,
+    /// The gamma correction of the midtones, values above 1.0 brighten and values below 1.0 darken them
+    gamma: f32,
+    /// The color channels that are adjusted
+    channel: Channel,
+}
+
+impl LevelsOp {
+    /// Returns a new `LevelsOp` struct with defined:
+    /// * `black`: The input value that is mapped to black. Has to be lower than `white`.
+    /// * `white`: The input value that is mapped to white
+    /// * `gamma`: The gamma correction of the midtones, between 0.1 and 10.0. Other values are clamped to that range.
+    ///   1.0 keeps the midtones linear.
+    pub fn new(black: u8, white: u8, gamma: f32) -> Self {
+        LevelsOp::new_channel(Channel::All, black, white, gamma)
+    }
+
+    /// Returns a new `LevelsOp` struct, that adjusts a single color channel only, with defined:
+    /// * `channel`: The color channel that is adjusted, represented by `Channel` enum. `Channel::All` adjusts all of them.
+    /// * `black`: The input value that is mapped to black. Has to be lower than `white`.
+    /// * `white`: The input value that is mapped to white
+    /// * `gamma`: The gamma correction of the midtones, between 0.1 and 10.0. Other values are clamped to that range.
+    ///   1.0 keeps the midtones linear.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::Channel;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::LevelsOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage, Rgba};
+    ///
+    /// let colors = [Rgb([40, 40, 40]), Rgb([60, 10, 10]), Rgb([255, 10, 10])];
+    /// let image = RgbImage::from_fn(3, 1, |x, _| colors[x as usize]);
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(image);
+    ///
+    /// let levels_op = LevelsOp::new_channel(Channel::Red, 60, 255, 1.0);
+    /// assert!(levels_op.apply(&mut dynamic_image).is_ok());
+    ///
+    /// // dark reds clip to zero, the other channels are not changed
+    /// assert_eq!(dynamic_image.get_pixel(0, 0), Rgba([0, 40, 40, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(1, 0), Rgba([0, 10, 10, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(2, 0), Rgba([255, 10, 10, 255]));
+    /// ```
+    pub fn new_channel(channel: Channel, black: u8, white: u8, gamma: f32) -> Self {
+        LevelsOp {
+            black,
+            white,
+            gamma: gamma.clamp(0.1, 10.0),
+            channel,
+        }
+    }
+
+    /// Computes the lookup table that maps the input values to the output values
+    fn lookup_table(&self) -> [u8; 256] {
+        let range = (self.white - self.black) as f32;
+        let mut table = [0u8; 256];
+        for (value, entry) in table.iter_mut().enumerate() {
+            let normalized = ((value as f32 - self.black as f32) / range).clamp(0.0, 1.0);
+            *entry = (normalized.powf(1.0 / self.gamma) * 255.0).round() as u8;
+        }
+        table
+    }
+}
+
+/// The deserialized fields of a `LevelsOp`, which are validated by `LevelsOp::new_channel()`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct LevelsSpec {
+    black: u8,
+    white: u8,
+    gamma: f32,
+    #[serde(default)]
+    channel: Channel,
+}
+
+#[cfg(feature = "serde")]
+impl From<LevelsSpec> for LevelsOp {
+    fn from(spec: LevelsSpec) -> Self {
+        LevelsOp::new_channel(spec.channel, spec.black, spec.white, spec.gamma)
+    }
+}
+
+impl Operation for LevelsOp {
+    /// Logic for the levels-operation
+    ///
+    /// This function adjusts the levels of a `DynamicImage`, like the levels dialog of image editors:
+    /// The input range from `black` to `white` is stretched to the full range, values outside of it are clipped.
+    /// The midtones are then gamma corrected. Both steps are combined into a single lookup table,
+    /// which is applied to each color channel, or only to the channel given by `Channel`. The alpha channel is left unchanged.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `LevelsOp` struct
+    /// * `image` - The `DynamicImage` whose levels should be adjusted
+    ///
+    /// # Errors
+    ///
+    /// * InvalidParameters - `black` is not lower than `white`
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::LevelsOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// let values = [30, 50, 125, 200, 220];
+    /// let image = RgbImage::from_fn(5, 1, |x, _| Rgb([values[x as usize]; 3]));
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(image);
+    ///
+    /// let levels_op = LevelsOp::new(50, 200, 1.0);
+    /// let res = levels_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// let stretched: Vec<u8> = (0..5).map(|x| dynamic_image.get_pixel(x, 0)[0]).collect();
+    /// assert_eq!(stretched, vec![0, 0, 128, 255, 255]);
+    ///
+    /// // the black point has to be lower than the white point
+    /// assert!(LevelsOp::new(200, 50, 1.0).apply(&mut dynamic_image).is_err());
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        if self.black >= self.white {
+            return Err(OperationError::new(
+                Box::new(*self),
+                OperationErrorInfo::InvalidParameters,
+            ));
+        }
+
+        let table = self.lookup_table();
+        let adjusted = match self.channel {
+            Channel::All => 0..3,
+            Channel::Red => 0..1,
+            Channel::Green => 1..2,
+            Channel::Blue => 2..3,
+        };
+        let map = |data: &mut [u8], channels: usize| {
+            for pixel in data.chunks_exact_mut(channels) {
+                for value in &mut pixel[adjusted.clone()] {
+                    *value = table[*value as usize];
+                }
+            }
+        };
+
+        match image {
+            // RGB and RGBA images are changed in place
+            DynamicImage::ImageRgba8(buffer) => map(buffer, 4),
+            DynamicImage::ImageRgb8(buffer) => map(buffer, 3),
+            _ if image.color().has_alpha() => {
+                let mut buffer = image.to_rgba8();
+                map(&mut buffer, 4);
+                *image = DynamicImage::ImageRgba8(buffer);
+            }
+            _ => {
+                let mut buffer = image.to_rgb8();
+                map(&mut buffer, 3);
+                *image = DynamicImage::ImageRgb8(buffer);
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `LevelsOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Levels(*self))
+    }
+}
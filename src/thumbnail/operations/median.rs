@@ -0,0 +1,108 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImageView};
+use imageproc::filter::median_filter;
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the median-operation as a struct
+pub struct MedianOp {
+    /// The radius of the square window, each pixel is replaced with the median of that window.
+    radius: u32,
+}
+
+impl MedianOp {
+    /// Returns a new `MedianOp` struct with defined:
+    /// * `radius`: The radius of the window. The window is a square with a side length of `2 * radius + 1` pixels.
+    pub fn new(radius: u32) -> Self {
+        MedianOp { radius }
+    }
+}
+
+impl Operation for MedianOp {
+    /// Logic for the median-operation
+    ///
+    /// This function replaces each channel of each pixel of a `DynamicImage` with the median of the
+    /// channel within the surrounding window. This reduces noise, especially salt-and-pepper noise,
+    /// while preserving edges better than a Gaussian blur.
+    /// More information: [Median filter](https://en.wikipedia.org/wiki/Median_filter)
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Cost
+    ///
+    /// The costs grow linearly with the radius and the number of pixels,
+    /// so large radii on large images are slow. Resizing the image first is considerably cheaper.
+    /// The radius is limited to the longer side of the image, as larger windows do not change the result.
+    /// If the window still holds more than `u32::MAX` pixels, `OperationErrorInfo::InvalidParameters` is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `MedianOp` struct
+    /// * `image` - The `DynamicImage` that should be filtered
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::MedianOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// // A gray image with salt-and-pepper noise on every 7th pixel
+    /// let noisy = RgbImage::from_fn(60, 60, |x, y| match (x + 60 * y) % 7 {
+    ///     0 if (x + y) % 2 == 0 => Rgb([255, 255, 255]),
+    ///     0 => Rgb([0, 0, 0]),
+    ///     _ => Rgb([128, 128, 128]),
+    /// });
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(noisy);
+    ///
+    /// let count_noise = |image: &DynamicImage| {
+    ///     image.pixels().filter(|(_, _, p)| (p[0] as i32 - 128).abs() > 50).count()
+    /// };
+    /// let noise_before = count_noise(&dynamic_image);
+    ///
+    /// let median_op = MedianOp::new(2);
+    /// let res = median_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// assert!(noise_before > 400);
+    /// assert!(count_noise(&dynamic_image) * 20 < noise_before);
+    ///
+    /// // Oversized radii are limited to the image
+    /// assert!(MedianOp::new(40_000).apply(&mut dynamic_image).is_ok());
+    ///
+    /// // A window of 80_001 x 80_001 pixels is too large
+    /// let mut wide = DynamicImage::new_rgb8(40_000, 1);
+    /// assert!(MedianOp::new(40_000).apply(&mut wide).is_err());
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let radius = self.radius.min(image.width().max(image.height()));
+        let side = 2 * radius as u64 + 1;
+        if side * side > u32::MAX as u64 {
+            return Err(OperationError::new(
+                Box::new(*self),
+                OperationErrorInfo::InvalidParameters,
+            ));
+        }
+
+        *image = if image.color().has_alpha() {
+            DynamicImage::ImageRgba8(median_filter(&image.to_rgba8(), radius, radius))
+        } else {
+            DynamicImage::ImageRgb8(median_filter(&image.to_rgb8(), radius, radius))
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `MedianOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Median(*self))
+    }
+}
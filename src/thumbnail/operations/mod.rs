@@ -1,34 +1,85 @@
-use image::DynamicImage;
+use image::{DynamicImage, RgbaImage};
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 // Include all submodules
+pub mod append;
+pub mod auto_contrast;
 pub mod blur;
+pub mod blur_region;
 pub mod brighten;
+pub mod caption;
 pub mod combine;
+pub mod combine_in_region;
 pub mod contrast;
 pub mod crop;
+pub mod diagonal_watermark;
+pub mod distortion;
+pub mod edges;
+pub mod emboss;
 pub mod exif;
+#[cfg(feature = "face-detection")]
+pub mod face_crop;
+pub mod fast_blur;
 pub mod flip;
 pub mod huerotate;
 pub mod invert;
+pub mod levels;
+pub mod median;
+pub mod opacity;
+pub mod pad_ratio;
+pub mod quantize;
 pub mod resize;
 pub mod rotate;
+pub mod selective_desaturate;
+pub mod shadow;
+pub mod smart_watermark;
+#[cfg(feature = "serde")]
+pub mod spec;
+pub mod temperature;
 pub mod text;
+pub mod tint;
+pub mod trim;
 pub mod unsharpen;
 
 pub use crate::errors::OperationError;
+pub use append::AppendOp;
+pub use auto_contrast::AutoContrastOp;
 pub use blur::BlurOp;
+pub use blur_region::BlurRegionOp;
 pub use brighten::BrightenOp;
+pub use caption::CaptionOp;
 pub use combine::CombineOp;
+pub use combine_in_region::CombineInRegionOp;
 pub use contrast::ContrastOp;
 pub use crop::CropOp;
+pub use diagonal_watermark::DiagonalWatermarkOp;
+pub use distortion::DistortionOp;
+pub use edges::EdgeDetectOp;
+pub use emboss::EmbossOp;
 pub use exif::ExifOp;
+#[cfg(feature = "face-detection")]
+pub use face_crop::FaceCropOp;
+pub use fast_blur::FastBlurOp;
 pub use flip::FlipOp;
 pub use huerotate::HuerotateOp;
 pub use invert::InvertOp;
+pub use levels::LevelsOp;
+pub use median::MedianOp;
+pub use opacity::OpacityOp;
+pub use pad_ratio::PadToRatioOp;
+pub use quantize::QuantizeOp;
 pub use resize::ResizeOp;
 pub use rotate::RotateOp;
+pub use selective_desaturate::SelectiveDesaturateOp;
+pub use shadow::ShadowOp;
+pub use smart_watermark::SmartWatermarkOp;
+#[cfg(feature = "serde")]
+pub use spec::OperationSpec;
+pub use temperature::TemperatureOp;
 pub use text::TextOp;
+pub use tint::TintOp;
+pub use trim::TrimOp;
 pub use unsharpen::UnsharpenOp;
 
 /// The `Operation` trait.
@@ -37,6 +88,50 @@ pub use unsharpen::UnsharpenOp;
 /// Passing the image to the apply function should perform the desired modifications to it.
 pub trait Operation: OperationClone + Debug + Send + Sync {
     fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>;
+
+    /// Gets the minimum dimensions the image needs to have, for the result of this operation to be unaffected
+    ///
+    /// If this is the first queued operation, large JPEG images are decoded at a reduced scale,
+    /// that is still at least as large as these dimensions.
+    /// Returns `None` if the image is needed in its full size, which is the default.
+    ///
+    /// * dimensions: (u32, u32) - The dimensions of the image in its full size
+    fn min_source_dimensions(&self, _dimensions: (u32, u32)) -> Option<(u32, u32)> {
+        None
+    }
+
+    /// Gets the serializable representation of the operation
+    ///
+    /// Returns `None` by default. Only the operations of this crate have a representation in `OperationSpec`,
+    /// so operations implemented elsewhere can not be serialized.
+    ///
+    /// # Examples
+    /// ```
+    /// use image::DynamicImage;
+    /// use thumbnailer::errors::OperationError;
+    /// use thumbnailer::generic::OperationContainer;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::Thumbnail;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct GrayscaleOp;
+    ///
+    /// impl Operation for GrayscaleOp {
+    ///     fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError> {
+    ///         *image = image.grayscale();
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut thumb = Thumbnail::from_dynamic_image("test.jpg", DynamicImage::new_rgb8(80, 50));
+    /// thumb.add_op(Box::new(GrayscaleOp));
+    /// assert!(GrayscaleOp.to_spec().is_none());
+    /// assert!(thumb.ops_to_json().is_err());
+    /// ```
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        None
+    }
 }
 
 pub trait OperationClone {
@@ -60,3 +155,51 @@ impl Clone for Box<dyn Operation> {
         self.box_clone()
     }
 }
+
+/// Changes the pixels of the image as RGBA
+///
+/// RGBA images, e.g. after `Thumbnail::set_working_color()`, are changed in place.
+/// Other images are converted to RGBA, and back to RGB afterwards, if they have no alpha channel.
+///
+/// * image: &mut DynamicImage - The image to change
+/// * change: impl FnOnce(&mut RgbaImage) - Changes the RGBA pixels
+pub(crate) fn change_rgba8(image: &mut DynamicImage, change: impl FnOnce(&mut RgbaImage)) {
+    if let DynamicImage::ImageRgba8(buffer) = image {
+        change(buffer);
+        return;
+    }
+
+    let mut buffer = image.to_rgba8();
+    change(&mut buffer);
+    *image = if image.color().has_alpha() {
+        DynamicImage::ImageRgba8(buffer)
+    } else {
+        DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(buffer).to_rgb8())
+    };
+}
+
+/// Checks whether an RGBA image of the given size can be created
+///
+/// Both dimensions have to fit into a `u32`, and the length of the buffer into an `isize`.
+/// Creating a larger image panics.
+///
+/// * width: u64 - The width of the image
+/// * height: u64 - The height of the image
+pub(crate) fn rgba8_fits(width: u64, height: u64) -> bool {
+    let len = width
+        .checked_mul(height)
+        .and_then(|pixels| pixels.checked_mul(4));
+    width <= u32::MAX as u64
+        && height <= u32::MAX as u64
+        && matches!(len, Some(len) if len <= isize::MAX as u64)
+}
+
+/// Gets the pixels of the image as RGBA, RGBA images are borrowed instead of copied
+///
+/// * image: &DynamicImage - The image
+pub(crate) fn rgba8(image: &DynamicImage) -> Cow<'_, RgbaImage> {
+    match image {
+        DynamicImage::ImageRgba8(buffer) => Cow::Borrowed(buffer),
+        _ => Cow::Owned(image.to_rgba8()),
+    }
+}
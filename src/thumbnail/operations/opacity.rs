@@ -0,0 +1,114 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::DynamicImage;
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "OpacitySpec"))]
+/// Representation of the opacity-operation as a struct
+pub struct OpacityOp {
+    /// The factor the alpha channel is multiplied with
+    factor: f32,
+}
+
+impl OpacityOp {
+    /// Returns a new `OpacityOp` struct with defined:
+    /// * `factor`: The factor the alpha channel is multiplied with, between 0.0 (transparent) and 1.0 (unchanged).
+    ///   Other values are clamped to that range.
+    pub fn new(factor: f32) -> Self {
+        OpacityOp {
+            factor: factor.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// The deserialized fields of a `OpacityOp`, which are validated by `OpacityOp::new()`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct OpacitySpec {
+    factor: f32,
+}
+
+#[cfg(feature = "serde")]
+impl From<OpacitySpec> for OpacityOp {
+    fn from(spec: OpacitySpec) -> Self {
+        OpacityOp::new(spec.factor)
+    }
+}
+
+impl Operation for OpacityOp {
+    /// Logic for the opacity-operation
+    ///
+    /// This function multiplies the alpha channel of a `DynamicImage` with `factor`.
+    /// Images without an alpha channel are converted to RGBA first, so they become fully opaque before they are faded.
+    /// Images with 16 bits per channel are converted to `ImageRgba16`, all other images to `ImageRgba8`.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `OpacityOp` struct
+    /// * `image` - The `DynamicImage` that should be faded
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::OpacityOp;
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    ///
+    /// let image = RgbaImage::from_fn(16, 16, |x, y| Rgba([255, 0, 0, (x * 16 + y) as u8]));
+    /// let mut dynamic_image = DynamicImage::ImageRgba8(image.clone());
+    ///
+    /// let opacity_op = OpacityOp::new(0.5);
+    /// let res = opacity_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// for (x, y, pixel) in dynamic_image.pixels() {
+    ///     let alpha = image.get_pixel(x, y)[3] as f32;
+    ///     assert_eq!(pixel[3], (alpha * 0.5).round() as u8);
+    /// }
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        if let DynamicImage::ImageRgba8(buffer) = image {
+            for pixel in buffer.pixels_mut() {
+                pixel[3] = (pixel[3] as f32 * self.factor).round() as u8;
+            }
+            return Ok(());
+        }
+
+        *image = match image {
+            DynamicImage::ImageLuma16(_)
+            | DynamicImage::ImageLumaA16(_)
+            | DynamicImage::ImageRgb16(_)
+            | DynamicImage::ImageRgba16(_) => {
+                let mut buffer = image.to_rgba16();
+                for pixel in buffer.pixels_mut() {
+                    pixel[3] = (pixel[3] as f32 * self.factor).round() as u16;
+                }
+                DynamicImage::ImageRgba16(buffer)
+            }
+            _ => {
+                let mut buffer = image.to_rgba8();
+                for pixel in buffer.pixels_mut() {
+                    pixel[3] = (pixel[3] as f32 * self.factor).round() as u8;
+                }
+                DynamicImage::ImageRgba8(buffer)
+            }
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `OpacityOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Opacity(*self))
+    }
+}
@@ -0,0 +1,128 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{rgba8, rgba8_fits, Operation};
+use image::{imageops, DynamicImage, GenericImageView, Rgba, RgbaImage};
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the pad-to-ratio operation as a struct
+pub struct PadToRatioOp {
+    /// The width part of the ratio
+    ratio_width: f32,
+    /// The height part of the ratio
+    ratio_height: f32,
+    /// The color of the added bars as RGBA
+    fill: [u8; 4],
+}
+
+impl PadToRatioOp {
+    /// Returns a new `PadToRatioOp` struct with defined:
+    /// * `ratio_width` and `ratio_height` as the ratio of width and height the image is padded to, e.g. 16.0 and 9.0
+    /// * `fill` as the color of the added bars as RGBA
+    pub fn new(ratio_width: f32, ratio_height: f32, fill: [u8; 4]) -> Self {
+        PadToRatioOp {
+            ratio_width,
+            ratio_height,
+            fill,
+        }
+    }
+}
+
+impl Operation for PadToRatioOp {
+    /// Logic for the pad-to-ratio operation
+    ///
+    /// This function pads a `DynamicImage` to a width-height-ratio, the inverse of cropping with `Crop::Ratio`.
+    /// Bars of the fill color are added on both sides of the image, left and right of images that are too tall
+    /// and above and below images that are too wide. The image is centered and keeps all of its content.
+    /// Images that already have the ratio, and ratios that are not positive, leave the image untouched.
+    /// The image is converted to `ImageRgba8`, or to `ImageRgb8` if it has no alpha channel and the fill color is opaque.
+    /// If the padded image would be too large to be created, e.g. for an extreme ratio, an `InvalidParameters` error is returned.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `PadToRatioOp` struct
+    /// * `image` - The `DynamicImage` that should be padded
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::PadToRatioOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage, Rgba};
+    ///
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(RgbImage::from_pixel(90, 90, Rgb([255, 0, 0])));
+    ///
+    /// let pad_op = PadToRatioOp::new(16.0, 9.0, [0, 0, 255, 255]);
+    /// let res = pad_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// assert_eq!(dynamic_image.dimensions(), (160, 90));
+    /// // bars on the left and right side
+    /// assert_eq!(dynamic_image.get_pixel(0, 45), Rgba([0, 0, 255, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(159, 45), Rgba([0, 0, 255, 255]));
+    /// // the whole image in the center
+    /// assert_eq!(dynamic_image.get_pixel(34, 45), Rgba([0, 0, 255, 255]));
+    /// for x in 35..125 {
+    ///     assert_eq!(dynamic_image.get_pixel(x, 0), Rgba([255, 0, 0, 255]));
+    ///     assert_eq!(dynamic_image.get_pixel(x, 89), Rgba([255, 0, 0, 255]));
+    /// }
+    /// assert_eq!(dynamic_image.get_pixel(125, 45), Rgba([0, 0, 255, 255]));
+    ///
+    /// // the padded image would be far too wide
+    /// let extreme_op = PadToRatioOp::new(f32::MAX, 1.0, [0, 0, 255, 255]);
+    /// assert!(extreme_op.apply(&mut dynamic_image).is_err());
+    /// assert_eq!(dynamic_image.dimensions(), (160, 90));
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (width, height) = image.dimensions();
+        let ratio = self.ratio_width as f64 / self.ratio_height as f64;
+        if !(ratio.is_finite() && ratio > 0.0) || width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        // The padded size is computed in f64, the casts to u64 saturate for extreme ratios
+        let (padded_width, padded_height) = if (width as f64 / height as f64) < ratio {
+            let padded_width = (height as f64 * ratio).round() as u64;
+            (padded_width.max(width as u64), height as u64)
+        } else {
+            let padded_height = (width as f64 / ratio).round() as u64;
+            (width as u64, padded_height.max(height as u64))
+        };
+        if !rgba8_fits(padded_width, padded_height) {
+            return Err(OperationError::new(
+                Box::new(*self),
+                OperationErrorInfo::InvalidParameters,
+            ));
+        }
+        let (padded_width, padded_height) = (padded_width as u32, padded_height as u32);
+        if (padded_width, padded_height) == (width, height) {
+            return Ok(());
+        }
+
+        let mut canvas = RgbaImage::from_pixel(padded_width, padded_height, Rgba(self.fill));
+        let x = (padded_width - width) / 2;
+        let y = (padded_height - height) / 2;
+        imageops::replace(&mut canvas, &*rgba8(image), x, y);
+
+        *image = if self.fill[3] == u8::MAX && !image.color().has_alpha() {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(canvas).to_rgb8())
+        } else {
+            DynamicImage::ImageRgba8(canvas)
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `PadToRatioOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::PadToRatio(*self))
+    }
+}
@@ -0,0 +1,109 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use color_quant::NeuQuant;
+use image::DynamicImage;
+
+/// The sampling factor of NeuQuant. 1 samples every pixel, higher values are faster, 10 is the recommended default.
+const SAMPLE_FACTOR: i32 = 10;
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "QuantizeSpec"))]
+/// Representation of the quantize-operation as a struct
+pub struct QuantizeOp {
+    /// The maximum number of colors of the resulting image
+    max_colors: u16,
+}
+
+impl QuantizeOp {
+    /// Returns a new `QuantizeOp` struct with defined:
+    /// * `max_colors`: The maximum number of colors, between 2 and 256. Other values are clamped to that range.
+    pub fn new(max_colors: u16) -> Self {
+        QuantizeOp {
+            max_colors: max_colors.clamp(2, 256),
+        }
+    }
+}
+
+/// The deserialized fields of a `QuantizeOp`, which are validated by `QuantizeOp::new()`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct QuantizeSpec {
+    max_colors: u16,
+}
+
+#[cfg(feature = "serde")]
+impl From<QuantizeSpec> for QuantizeOp {
+    fn from(spec: QuantizeSpec) -> Self {
+        QuantizeOp::new(spec.max_colors)
+    }
+}
+
+impl Operation for QuantizeOp {
+    /// Logic for the quantize-operation
+    ///
+    /// This function reduces the colors of a `DynamicImage` to a palette of at most `max_colors` colors.
+    /// The palette is computed with the NeuQuant algorithm, every pixel is then replaced with the closest color of the palette.
+    /// This is useful before storing an image as GIF, which is limited to a palette of 256 colors:
+    /// A smaller palette results in smaller files, and the encoder does not need to quantize the image itself.
+    /// More information: [Color quantization](https://en.wikipedia.org/wiki/Color_quantization)
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `QuantizeOp` struct
+    /// * `image` - The `DynamicImage` whose colors should be reduced
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::collections::HashSet;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::QuantizeOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// // A colorful gradient with thousands of distinct colors
+    /// let gradient = RgbImage::from_fn(64, 64, |x, y| Rgb([(x * 4) as u8, (y * 4) as u8, ((x + y) * 2) as u8]));
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(gradient);
+    ///
+    /// let quantize_op = QuantizeOp::new(16);
+    /// let res = quantize_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// let colors: HashSet<_> = dynamic_image.pixels().map(|(_, _, pixel)| pixel).collect();
+    /// assert!(colors.len() <= 16);
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let has_alpha = image.color().has_alpha();
+        let mut buffer = image.to_rgba8();
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        let quantizer = NeuQuant::new(SAMPLE_FACTOR, self.max_colors as usize, &buffer);
+        for pixel in buffer.chunks_exact_mut(4) {
+            quantizer.map_pixel(pixel);
+        }
+
+        *image = if has_alpha {
+            DynamicImage::ImageRgba8(buffer)
+        } else {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(buffer).to_rgb8())
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `QuantizeOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Quantize(*self))
+    }
+}
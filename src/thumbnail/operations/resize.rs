@@ -1,10 +1,16 @@
 pub use crate::errors::{OperationError, OperationErrorInfo};
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use crate::{ResampleFilter, Resize};
 use image::imageops::FilterType;
 use image::{DynamicImage, GenericImageView};
 
+/// The filter used, if no filter is given to the `ResizeOp`
+pub const DEFAULT_FILTER: ResampleFilter = ResampleFilter::Triangle;
+
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the resizing operation as a struct
 pub struct ResizeOp {
     /// Contains the `Resize` enum as option
@@ -16,7 +22,28 @@ pub struct ResizeOp {
 impl ResizeOp {
     /// Returns a new `ResizeOp` struct with defined:
     /// * `size` as instance of `Resize` enum
-    /// * optional `filter`
+    /// * optional `filter`: If `None`, `DEFAULT_FILTER` (`ResampleFilter::Triangle`) is used.
+    ///
+    /// Previous versions resized with `DynamicImage::thumbnail()` when no filter was given,
+    /// which uses a fixed filter that could not be changed.
+    /// Resizing without a filter now is the same as resizing with `ResampleFilter::Triangle`.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::{Resize, ResampleFilter};
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::ResizeOp;
+    ///
+    /// let image = image::open("resources/tests/test.jpg").unwrap();
+    ///
+    /// let mut default_filter = image.clone();
+    /// assert!(ResizeOp::new(Resize::Width(120), None).apply(&mut default_filter).is_ok());
+    /// let mut triangle = image.clone();
+    /// let resize_op = ResizeOp::new(Resize::Width(120), Some(ResampleFilter::Triangle));
+    /// assert!(resize_op.apply(&mut triangle).is_ok());
+    ///
+    /// assert_eq!(default_filter.to_bytes(), triangle.to_bytes());
+    /// ```
     pub fn new(size: Resize, filter: Option<ResampleFilter>) -> Self {
         ResizeOp { size, filter }
     }
@@ -26,6 +53,7 @@ impl Operation for ResizeOp {
     /// Logic for the resize-operation
     ///
     /// This function resizes a `DynamicImage`, depending on the options given by the members of `ResizeOp` struct.
+    /// If no filter is given, `DEFAULT_FILTER` is used.
     /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
     ///
     /// # Arguments
@@ -57,54 +85,81 @@ impl Operation for ResizeOp {
         let (width, height) = image.dimensions();
         let aspect_ratio = width as f32 / height as f32;
 
-        let filter_type = match self.filter {
-            Some(ResampleFilter::Nearest) => Some(FilterType::Nearest),
-            Some(ResampleFilter::Triangle) => Some(FilterType::Triangle),
-            Some(ResampleFilter::CatmullRom) => Some(FilterType::CatmullRom),
-            Some(ResampleFilter::Gaussian) => Some(FilterType::Gaussian),
-            Some(ResampleFilter::Lanczos3) => Some(FilterType::Lanczos3),
-            None => None,
+        let image_filter = match self.filter.unwrap_or(DEFAULT_FILTER) {
+            ResampleFilter::Nearest => FilterType::Nearest,
+            ResampleFilter::Triangle => FilterType::Triangle,
+            ResampleFilter::CatmullRom => FilterType::CatmullRom,
+            ResampleFilter::Gaussian => FilterType::Gaussian,
+            ResampleFilter::Lanczos3 => FilterType::Lanczos3,
         };
 
-        match filter_type {
-            Some(image_filter) => {
-                match self.size {
-                    Resize::Height(y) => {
-                        let x: u32 = (aspect_ratio * y as f32) as u32 + 1;
-                        *image = image.resize(x, y, image_filter);
-                    }
-                    Resize::Width(x) => {
-                        let y: u32 = (x as f32 / aspect_ratio) as u32 + 1;
-                        *image = image.resize(x, y, image_filter);
-                    }
-                    Resize::BoundingBox(x, y) => {
-                        *image = image.resize(x, y, image_filter);
-                    }
-                    Resize::ExactBox(x, y) => {
-                        *image = image.resize_exact(x, y, image_filter);
-                    }
-                };
+        match self.size {
+            Resize::Height(y) => {
+                let x: u32 = (aspect_ratio * y as f32) as u32 + 1;
+                *image = image.resize(x, y, image_filter);
             }
-            None => {
-                match self.size {
-                    Resize::Height(y) => {
-                        let x: u32 = (aspect_ratio * y as f32) as u32 + 1;
-                        *image = image.thumbnail(x, y);
-                    }
-                    Resize::Width(x) => {
-                        let y: u32 = (x as f32 / aspect_ratio) as u32 + 1;
-                        *image = image.thumbnail(x, y);
-                    }
-                    Resize::BoundingBox(x, y) => {
-                        *image = image.thumbnail(x, y);
-                    }
-                    Resize::ExactBox(x, y) => {
-                        *image = image.thumbnail_exact(x, y);
-                    }
-                };
+            Resize::Width(x) => {
+                let y: u32 = (x as f32 / aspect_ratio) as u32 + 1;
+                *image = image.resize(x, y, image_filter);
+            }
+            Resize::BoundingBox(x, y) => {
+                *image = image.resize(x, y, image_filter);
+            }
+            Resize::ExactBox(x, y) => {
+                *image = image.resize_exact(x, y, image_filter);
             }
         };
 
         Ok(())
     }
+
+    /// Gets the minimum dimensions the image needs to have, before it is resized
+    ///
+    /// These are the dimensions of the resized image, if the image is scaled down in both directions.
+    /// Otherwise the image is needed in its full size and `None` is returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::Resize;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::ResizeOp;
+    ///
+    /// let resize_op = ResizeOp::new(Resize::BoundingBox(200, 200), None);
+    /// assert_eq!(resize_op.min_source_dimensions((6000, 4000)), Some((200, 134)));
+    ///
+    /// let resize_op = ResizeOp::new(Resize::Width(8000), None);
+    /// assert_eq!(resize_op.min_source_dimensions((6000, 4000)), None);
+    /// ```
+    fn min_source_dimensions(&self, dimensions: (u32, u32)) -> Option<(u32, u32)> {
+        let (width, height) = dimensions;
+        let aspect_ratio = width as f32 / height as f32;
+
+        #[allow(clippy::manual_div_ceil)] // div_ceil() needs Rust 1.73
+        let (target_width, target_height) = match self.size {
+            Resize::Height(y) => ((aspect_ratio * y as f32) as u32 + 1, y),
+            Resize::Width(x) => (x, (x as f32 / aspect_ratio) as u32 + 1),
+            Resize::BoundingBox(x, y) => {
+                let (x, y, width, height) = (x as u64, y as u64, width as u64, height as u64);
+                // The box is filled either in width or in height, the other side is rounded up
+                if x * height <= y * width {
+                    (x as u32, ((height * x + width - 1) / width) as u32)
+                } else {
+                    (((width * y + height - 1) / height) as u32, y as u32)
+                }
+            }
+            Resize::ExactBox(x, y) => (x, y),
+        };
+
+        if target_width < width && target_height < height {
+            Some((target_width, target_height))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the serializable representation of the `ResizeOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Resize(*self))
+    }
 }
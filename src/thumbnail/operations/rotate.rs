@@ -1,21 +1,146 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
-use crate::Rotation;
-use image::DynamicImage;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::{Rotation, RotationFill};
+use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the rotate-operation as struct
 pub struct RotateOp {
     /// contains the `Rotation` enum
     rotation: Rotation,
+    /// How the corners exposed by a rotation by an arbitrary angle are filled
+    #[cfg_attr(feature = "serde", serde(default))]
+    fill: RotationFill,
 }
 
 impl RotateOp {
     /// Returns a new `RotateOp` struct with defined:
     /// * `rotation` as instance of `Rotation` enum
+    ///
+    /// The corners exposed by `Rotation::Degrees` are transparent.
     pub fn new(rotation: Rotation) -> Self {
-        RotateOp { rotation }
+        RotateOp {
+            rotation,
+            fill: RotationFill::Transparent,
+        }
     }
+
+    /// Returns a new `RotateOp` struct with defined:
+    /// * `rotation` as instance of `Rotation` enum
+    /// * `fill` as instance of `RotationFill` enum, which defines how the corners exposed by `Rotation::Degrees` are filled.
+    ///   Rotations by multiples of 90 degrees expose no corners, and are not affected.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::{Rotation, RotationFill};
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::RotateOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage, Rgba};
+    ///
+    /// let red = DynamicImage::ImageRgb8(RgbImage::from_pixel(20, 20, Rgb([255, 0, 0])));
+    ///
+    /// // transparent corners
+    /// let mut dynamic_image = red.clone();
+    /// let rotate_op = RotateOp::new_with_fill(Rotation::Degrees(45.0), RotationFill::Transparent);
+    /// assert!(rotate_op.apply(&mut dynamic_image).is_ok());
+    /// assert_eq!(dynamic_image.dimensions(), (29, 29));
+    /// assert_eq!(dynamic_image.get_pixel(0, 0)[3], 0);
+    /// assert_eq!(dynamic_image.get_pixel(14, 14), Rgba([255, 0, 0, 255]));
+    ///
+    /// // corners filled with a color
+    /// let mut dynamic_image = red.clone();
+    /// let fill = RotationFill::Color([0, 255, 0, 255]);
+    /// assert!(RotateOp::new_with_fill(Rotation::Degrees(45.0), fill).apply(&mut dynamic_image).is_ok());
+    /// assert_eq!(dynamic_image.get_pixel(0, 0), Rgba([0, 255, 0, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(14, 14), Rgba([255, 0, 0, 255]));
+    ///
+    /// // corners filled with the nearest edge pixel
+    /// let mut dynamic_image = red;
+    /// assert!(RotateOp::new_with_fill(Rotation::Degrees(45.0), RotationFill::Edge).apply(&mut dynamic_image).is_ok());
+    /// assert_eq!(dynamic_image.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+    /// ```
+    pub fn new_with_fill(rotation: Rotation, fill: RotationFill) -> Self {
+        RotateOp { rotation, fill }
+    }
+
+    /// Rotates the image clockwise by an arbitrary angle
+    ///
+    /// The canvas grows to the bounding box of the rotated image. Each pixel of the canvas is mapped back
+    /// to the source image and interpolated bilinearly. Pixels that map outside of the source image are filled
+    /// as defined by `fill`. Images without alpha channel stay opaque, unless the fill is transparent.
+    ///
+    /// * image: &DynamicImage - The image to rotate
+    /// * degrees: f32 - The angle in degrees
+    fn rotate_degrees(&self, image: &DynamicImage, degrees: f32) -> DynamicImage {
+        let source = image.to_rgba8();
+        let (width, height) = source.dimensions();
+        let (sin, cos) = (degrees as f64).to_radians().sin_cos();
+
+        // Tiny rounding errors must not grow the canvas by a whole pixel
+        let fit = |size: f64| (size - 1e-6).ceil().max(1.0) as u32;
+        let canvas_width = fit(width as f64 * cos.abs() + height as f64 * sin.abs());
+        let canvas_height = fit(width as f64 * sin.abs() + height as f64 * cos.abs());
+
+        let (center_x, center_y) = (width as f64 / 2.0, height as f64 / 2.0);
+        let (canvas_center_x, canvas_center_y) =
+            (canvas_width as f64 / 2.0, canvas_height as f64 / 2.0);
+
+        let rotated = RgbaImage::from_fn(canvas_width, canvas_height, |x, y| {
+            let dx = x as f64 + 0.5 - canvas_center_x;
+            let dy = y as f64 + 0.5 - canvas_center_y;
+            // The inverse rotation, counter-clockwise, gives the position in the source image
+            let source_x = dx * cos + dy * sin + center_x - 0.5;
+            let source_y = -dx * sin + dy * cos + center_y - 0.5;
+
+            let inside = (-0.5..=width as f64 - 0.5).contains(&source_x)
+                && (-0.5..=height as f64 - 0.5).contains(&source_y);
+            match (inside, self.fill) {
+                (false, RotationFill::Transparent) => Rgba([0, 0, 0, 0]),
+                (false, RotationFill::Color(color)) => Rgba(color),
+                _ => interpolate(&source, source_x, source_y),
+            }
+        });
+
+        let opaque = match self.fill {
+            RotationFill::Transparent => false,
+            RotationFill::Color(color) => color[3] == u8::MAX,
+            RotationFill::Edge => true,
+        };
+        if opaque && !image.color().has_alpha() {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(rotated).to_rgb8())
+        } else {
+            DynamicImage::ImageRgba8(rotated)
+        }
+    }
+}
+
+/// Interpolates the pixel at the given position bilinearly
+///
+/// Positions outside of the image are clamped to the nearest edge pixel.
+///
+/// * image: &RgbaImage - The image to sample
+/// * x: f64 - The x coordinate, relative to the center of the top-left pixel
+/// * y: f64 - The y coordinate, relative to the center of the top-left pixel
+pub(crate) fn interpolate(image: &RgbaImage, x: f64, y: f64) -> Rgba<u8> {
+    let (width, height) = image.dimensions();
+    let x = x.clamp(0.0, (width - 1) as f64);
+    let y = y.clamp(0.0, (height - 1) as f64);
+
+    let (left, top) = (x.floor() as u32, y.floor() as u32);
+    let (right, bottom) = ((left + 1).min(width - 1), (top + 1).min(height - 1));
+    let (fx, fy) = (x - left as f64, y - top as f64);
+
+    let mut pixel = [0u8; 4];
+    for (channel, value) in pixel.iter_mut().enumerate() {
+        let at = |x: u32, y: u32| image.get_pixel(x, y)[channel] as f64;
+        let upper = at(left, top) * (1.0 - fx) + at(right, top) * fx;
+        let lower = at(left, bottom) * (1.0 - fx) + at(right, bottom) * fx;
+        *value = (upper * (1.0 - fy) + lower * fy).round() as u8;
+    }
+    Rgba(pixel)
 }
 
 impl Operation for RotateOp {
@@ -25,6 +150,8 @@ impl Operation for RotateOp {
     /// * with `Rotation::Rotate90`: Rotates the image 90 degrees clockwise.
     /// * with `Rotation::Rotate180`: Rotates the image 180 degrees clockwise.
     /// * with `Rotation::Rotate270`: Rotates the image 270 degrees clockwise.
+    /// * with `Rotation::Degrees`: Rotates the image clockwise by the given angle. The canvas grows to fit the
+    ///   rotated image, and the exposed corners are filled as defined by the `RotationFill`-enum.
     ///
     /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
     ///
@@ -60,7 +187,19 @@ impl Operation for RotateOp {
             Rotation::Rotate90 => *image = image.rotate90(),
             Rotation::Rotate180 => *image = image.rotate180(),
             Rotation::Rotate270 => *image = image.rotate270(),
+            Rotation::Degrees(degrees) => {
+                let (width, height) = image.dimensions();
+                if width > 0 && height > 0 {
+                    *image = self.rotate_degrees(image, degrees);
+                }
+            }
         }
         Ok(())
     }
+
+    /// Gets the serializable representation of the `RotateOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Rotate(*self))
+    }
 }
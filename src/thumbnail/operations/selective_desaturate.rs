@@ -0,0 +1,122 @@
+pub use crate::errors::OperationError;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{change_rgba8, Operation};
+use image::{DynamicImage, Rgba};
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the selective-desaturate operation as a struct
+pub struct SelectiveDesaturateOp {
+    /// The hue in degrees, whose colors are kept
+    keep_hue: f32,
+    /// The maximum distance in degrees from `keep_hue`, of hues that are kept
+    range: f32,
+}
+
+impl SelectiveDesaturateOp {
+    /// Returns a new `SelectiveDesaturateOp` struct with defined:
+    /// * `keep_hue` as the hue in degrees, whose colors are kept, e.g. 0.0 for red or 240.0 for blue
+    /// * `range` as the maximum distance in degrees from `keep_hue`, of hues that are kept
+    pub fn new(keep_hue: f32, range: f32) -> Self {
+        SelectiveDesaturateOp { keep_hue, range }
+    }
+}
+
+impl Operation for SelectiveDesaturateOp {
+    /// Logic for the selective-desaturate operation
+    ///
+    /// This function keeps the colors of a `DynamicImage` within a range of hues, and turns everything else gray,
+    /// also known as "color splash". The hue of each pixel is taken from the HSV color space,
+    /// the distance to `keep_hue` wraps around, e.g. 350° is 20° away from 10°.
+    /// Pixels whose hue is at most `range` degrees away from `keep_hue` stay untouched,
+    /// all other pixels are replaced by their luminance, so the brightness of the image is kept.
+    /// The image is converted to `ImageRgba8`, or to `ImageRgb8` if it has no alpha channel.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `SelectiveDesaturateOp` struct
+    /// * `image` - The `DynamicImage` that should be desaturated
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::SelectiveDesaturateOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// // The left half is red, the right half is blue
+    /// let image = RgbImage::from_fn(40, 20, |x, _| if x < 20 { Rgb([220, 30, 40]) } else { Rgb([30, 60, 200]) });
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(image);
+    ///
+    /// let desaturate_op = SelectiveDesaturateOp::new(0.0, 30.0);
+    /// let res = desaturate_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// // the red stays vivid, the blue turns gray
+    /// assert_eq!(dynamic_image.get_pixel(5, 5).0, [220, 30, 40, 255]);
+    /// let gray = dynamic_image.get_pixel(30, 5);
+    /// assert_eq!(gray[0], gray[1]);
+    /// assert_eq!(gray[1], gray[2]);
+    /// assert_eq!(gray[0], 64);
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        change_rgba8(image, |buffer| {
+            for pixel in buffer.pixels_mut() {
+                let distance = match hue(pixel) {
+                    Some(hue) => {
+                        let distance = (hue - self.keep_hue).rem_euclid(360.0);
+                        distance.min(360.0 - distance)
+                    }
+                    // gray pixels stay gray
+                    None => continue,
+                };
+                if distance <= self.range {
+                    continue;
+                }
+
+                let luminance = (0.2126 * pixel[0] as f32
+                    + 0.7152 * pixel[1] as f32
+                    + 0.0722 * pixel[2] as f32)
+                    .round()
+                    .clamp(0.0, 255.0) as u8;
+                *pixel = Rgba([luminance, luminance, luminance, pixel[3]]);
+            }
+        });
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `SelectiveDesaturateOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::SelectiveDesaturate(*self))
+    }
+}
+
+/// Gets the hue of a pixel in the HSV color space, in degrees from 0.0 to 360.0
+///
+/// Returns `None` for gray pixels, which have no hue.
+fn hue(pixel: &Rgba<u8>) -> Option<f32> {
+    let [red, green, blue] = [pixel[0] as f32, pixel[1] as f32, pixel[2] as f32];
+    let max = red.max(green).max(blue);
+    let chroma = max - red.min(green).min(blue);
+    if chroma == 0.0 {
+        return None;
+    }
+
+    let sector = if max == red {
+        (green - blue) / chroma
+    } else if max == green {
+        (blue - red) / chroma + 2.0
+    } else {
+        (red - green) / chroma + 4.0
+    };
+    Some((sector * 60.0).rem_euclid(360.0))
+}
@@ -0,0 +1,163 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{rgba8, Operation};
+use image::{imageops, DynamicImage, GrayImage, Luma, Rgba, RgbaImage};
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "ShadowSpec"))]
+/// Representation of the drop-shadow-operation as a struct
+pub struct ShadowOp {
+    /// The horizontal offset of the shadow, positive values move it to the right
+    dx: i32,
+    /// The vertical offset of the shadow, positive values move it down
+    dy: i32,
+    /// The sigma of the gaussian blur, that softens the edges of the shadow
+    blur_sigma: f32,
+    /// The RGBA color of the shadow, the alpha channel controls the opacity of the shadow
+    color: [u8; 4],
+}
+
+impl ShadowOp {
+    /// Returns a new `ShadowOp` struct with defined:
+    /// * `dx`: The horizontal offset of the shadow, positive values move it to the right
+    /// * `dy`: The vertical offset of the shadow, positive values move it down
+    /// * `blur_sigma`: The sigma of the gaussian blur of the shadow. 0.0 results in a hard shadow,
+    ///   negative values are treated as 0.0.
+    /// * `color`: The RGBA color of the shadow
+    pub fn new(dx: i32, dy: i32, blur_sigma: f32, color: [u8; 4]) -> Self {
+        ShadowOp {
+            dx,
+            dy,
+            blur_sigma: blur_sigma.max(0.0),
+            color,
+        }
+    }
+}
+
+/// The deserialized fields of a `ShadowOp`, which are validated by `ShadowOp::new()`
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ShadowSpec {
+    dx: i32,
+    dy: i32,
+    blur_sigma: f32,
+    color: [u8; 4],
+}
+
+#[cfg(feature = "serde")]
+impl From<ShadowSpec> for ShadowOp {
+    fn from(spec: ShadowSpec) -> Self {
+        ShadowOp::new(spec.dx, spec.dy, spec.blur_sigma, spec.color)
+    }
+}
+
+impl Operation for ShadowOp {
+    /// Logic for the drop-shadow-operation
+    ///
+    /// This function adds a drop shadow to the visible parts of a `DynamicImage`.
+    /// The shadow is the alpha channel of the image, offset by `dx` and `dy`, blurred and filled with `color`.
+    /// The canvas grows by the offset and by a margin of three times `blur_sigma` on each side,
+    /// so neither the image nor its shadow is clipped. The image is then drawn on top of its shadow.
+    /// The shadow is only visible on images with transparent parts, e.g. after masking them.
+    /// The resulting image is always converted to `ImageRgba8`.
+    /// If the canvas would be wider or higher than `u32::MAX` pixels, an `InvalidParameters` error is returned.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `ShadowOp` struct
+    /// * `image` - The `DynamicImage` that should get a shadow
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::ShadowOp;
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    ///
+    /// // An opaque red square in the center of a transparent image
+    /// let image = RgbaImage::from_fn(40, 40, |x, y| {
+    ///     if (10..30).contains(&x) && (10..30).contains(&y) {
+    ///         Rgba([255, 0, 0, 255])
+    ///     } else {
+    ///         Rgba([0, 0, 0, 0])
+    ///     }
+    /// });
+    /// let mut dynamic_image = DynamicImage::ImageRgba8(image);
+    ///
+    /// let shadow_op = ShadowOp::new(5, 5, 2.0, [0, 0, 0, 255]);
+    /// let res = shadow_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// // The canvas grows by the offset and a margin of 6 pixels on each side
+    /// assert_eq!(dynamic_image.dimensions(), (57, 57));
+    /// // The square moved by the margin, and is drawn on top of its shadow
+    /// assert_eq!(dynamic_image.get_pixel(20, 20), Rgba([255, 0, 0, 255]));
+    /// // The shadow appears below and right of the square, but not above and left of it
+    /// let shadow = dynamic_image.get_pixel(38, 38);
+    /// assert_eq!(shadow[0], 0);
+    /// assert!(shadow[3] > 128);
+    /// assert_eq!(dynamic_image.get_pixel(12, 12)[3], 0);
+    ///
+    /// // Offset and margin together exceed the maximum canvas size
+    /// let huge_op = ShadowOp::new(i32::MIN, i32::MIN, 1.0e9, [0, 0, 0, 255]);
+    /// assert!(huge_op.apply(&mut dynamic_image).is_err());
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let source = rgba8(image);
+        let (width, height) = source.dimensions();
+
+        let margin = (self.blur_sigma * 3.0).ceil() as u32;
+        // The canvas is computed in i64, so neither the offsets nor the margin can overflow
+        let (dx, dy, margin) = (self.dx as i64, self.dy as i64, margin as i64);
+        let canvas_width = width as i64 + dx.abs() + 2 * margin;
+        let canvas_height = height as i64 + dy.abs() + 2 * margin;
+        if canvas_width > u32::MAX as i64 || canvas_height > u32::MAX as i64 {
+            return Err(OperationError::new(
+                Box::new(*self),
+                OperationErrorInfo::InvalidParameters,
+            ));
+        }
+        let (canvas_width, canvas_height) = (canvas_width as u32, canvas_height as u32);
+
+        // The image is placed on the side opposite to the offset, the shadow is placed at the offset from it
+        let image_x = (margin + (-dx).max(0)) as u32;
+        let image_y = (margin + (-dy).max(0)) as u32;
+        let shadow_x = (image_x as i64 + dx) as u32;
+        let shadow_y = (image_y as i64 + dy) as u32;
+
+        let mut silhouette = GrayImage::new(canvas_width, canvas_height);
+        for (x, y, pixel) in source.enumerate_pixels() {
+            silhouette.put_pixel(shadow_x + x, shadow_y + y, Luma([pixel[3]]));
+        }
+        if self.blur_sigma > 0.0 {
+            silhouette = imageops::blur(&silhouette, self.blur_sigma);
+        }
+
+        let [red, green, blue, alpha] = self.color;
+        let mut canvas = RgbaImage::from_fn(canvas_width, canvas_height, |x, y| {
+            let coverage = silhouette.get_pixel(x, y)[0] as u32;
+            Rgba([red, green, blue, (alpha as u32 * coverage / 255) as u8])
+        });
+        imageops::overlay(&mut canvas, &*source, image_x, image_y);
+        // The source may borrow the image, which is replaced by the canvas
+        drop(source);
+
+        *image = DynamicImage::ImageRgba8(canvas);
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `ShadowOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Shadow(*self))
+    }
+}
@@ -0,0 +1,187 @@
+pub use crate::errors::{OperationError, OperationErrorInfo};
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{CombineOp, Operation};
+use crate::{BoxPosition, StaticThumbnail};
+use image::{DynamicImage, GenericImageView};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the smart-watermark operation as a struct
+pub struct SmartWatermarkOp {
+    /// The logo as `StaticThumbnail`
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "path", with = "crate::thumbnail::static_thumb::by_path")
+    )]
+    logo: StaticThumbnail,
+    /// The positions the logo may be placed at, represented by `BoxPosition` enum
+    candidates: Vec<BoxPosition>,
+}
+
+impl SmartWatermarkOp {
+    /// Returns a new `SmartWatermarkOp` struct with defined:
+    /// * `logo` as the image that should be drawn on the 'DynamicImage'
+    /// * `candidates` as the positions the logo may be placed at, represented by `BoxPosition` enum
+    pub fn new(logo: StaticThumbnail, candidates: Vec<BoxPosition>) -> Self {
+        SmartWatermarkOp { logo, candidates }
+    }
+
+    /// Gets the position of the top-left-corner of the logo for the given candidate, like `CombineOp` places it
+    ///
+    /// Returns `None` if the logo would start left of or above the image.
+    fn top_left(&self, candidate: BoxPosition) -> Option<(u32, u32)> {
+        let (width, height) = self.logo.dimensions();
+        match candidate {
+            BoxPosition::TopLeft(x, y) => Some((x, y)),
+            BoxPosition::TopRight(x, y) => Some((x.checked_sub(width)?, y)),
+            BoxPosition::BottomLeft(x, y) => Some((x, y.checked_sub(height)?)),
+            BoxPosition::BottomRight(x, y) => Some((x.checked_sub(width)?, y.checked_sub(height)?)),
+        }
+    }
+
+    /// Rates how visible the logo is at the given candidate, higher is better
+    ///
+    /// The rating is the difference of the mean luminance of the background behind the logo
+    /// and the luminance of the logo, minus the standard deviation of the background luminance.
+    /// Returns `None` if the logo would not be inside the image at all.
+    fn visibility(
+        &self,
+        image: &DynamicImage,
+        logo_luminance: f64,
+        candidate: BoxPosition,
+    ) -> Option<f64> {
+        let (x, y) = self.top_left(candidate)?;
+        let (width, height) = image.dimensions();
+        let (logo_width, logo_height) = self.logo.dimensions();
+        if x >= width || y >= height {
+            return None;
+        }
+
+        let region = image
+            .crop_imm(x, y, logo_width.min(width - x), logo_height.min(height - y))
+            .to_luma8();
+        let count = (region.width() * region.height()) as f64;
+        if count == 0.0 {
+            return None;
+        }
+        let mean = region.pixels().map(|pixel| pixel[0] as f64).sum::<f64>() / count;
+        let variance = region
+            .pixels()
+            .map(|pixel| (pixel[0] as f64 - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Some((mean - logo_luminance).abs() - variance.sqrt())
+    }
+}
+
+impl Operation for SmartWatermarkOp {
+    /// Logic for the smart-watermark operation
+    ///
+    /// This function draws a logo on a `DynamicImage` like `CombineOp`, at the candidate position where it is most visible.
+    /// For each candidate the background behind the logo is rated: the more its mean luminance differs from the
+    /// luminance of the logo, and the more uniform it is, the better. E.g. a light logo is placed on the darkest
+    /// uniform candidate. The luminance of the logo is weighted by its alpha channel.
+    /// On equal ratings the first candidate is used. Candidates that would place the logo outside of the image are skipped.
+    /// Nothing is drawn if there are no candidates.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `SmartWatermarkOp` struct
+    /// * `image` - The `DynamicImage` where the logo should be drawn on
+    ///
+    /// # Errors
+    ///
+    /// * CoordinatesOutOfRange - None of the candidates places the logo inside the image
+    /// * ImageBufferConversionFailure - The supplied background image cannot be converted to an 'ImageBuffer'
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::BoxPosition;
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::SmartWatermarkOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage, Rgba, RgbaImage};
+    ///
+    /// // A light image with a dark bottom-left corner
+    /// let background = RgbImage::from_fn(200, 200, |x, y| {
+    ///     if x < 60 && y >= 140 { Rgb([20, 20, 20]) } else { Rgb([200, 200, 200]) }
+    /// });
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(background);
+    ///
+    /// let logo = RgbaImage::from_pixel(30, 30, Rgba([255, 255, 255, 255]));
+    /// let logo = StaticThumbnail::from_dynamic_image(DynamicImage::ImageRgba8(logo));
+    /// let candidates = vec![
+    ///     BoxPosition::TopLeft(10, 10),
+    ///     BoxPosition::TopRight(190, 10),
+    ///     BoxPosition::BottomLeft(10, 190),
+    ///     BoxPosition::BottomRight(190, 190),
+    /// ];
+    ///
+    /// let watermark_op = SmartWatermarkOp::new(logo, candidates);
+    /// let res = watermark_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// // the light logo lands on the dark corner
+    /// assert_eq!(dynamic_image.get_pixel(25, 175), Rgba([255, 255, 255, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(25, 25), Rgba([200, 200, 200, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(175, 175), Rgba([200, 200, 200, 255]));
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        if self.candidates.is_empty() {
+            return Ok(());
+        }
+
+        let logo = self.logo.as_dyn().to_luma_alpha8();
+        let alpha_sum: f64 = logo.pixels().map(|pixel| pixel[1] as f64).sum();
+        let logo_luminance = if alpha_sum > 0.0 {
+            logo.pixels()
+                .map(|pixel| pixel[0] as f64 * pixel[1] as f64)
+                .sum::<f64>()
+                / alpha_sum
+        } else {
+            0.0
+        };
+
+        let mut best: Option<(BoxPosition, f64)> = None;
+        for &candidate in &self.candidates {
+            let rating = match self.visibility(image, logo_luminance, candidate) {
+                Some(rating) => rating,
+                None => continue,
+            };
+            match best {
+                Some((_, best_rating)) if best_rating >= rating => {}
+                _ => best = Some((candidate, rating)),
+            }
+        }
+
+        let position = match best {
+            Some((position, _)) => position,
+            None => {
+                return Err(OperationError::new(
+                    Box::new(self.clone()),
+                    OperationErrorInfo::CoordinatesOutOfRange,
+                ))
+            }
+        };
+
+        CombineOp::new(self.logo.clone(), position)
+            .apply(image)
+            .map_err(|error| OperationError::new(Box::new(self.clone()), error.get_info().clone()))
+    }
+
+    /// Gets the serializable representation of the `SmartWatermarkOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::SmartWatermark(self.clone()))
+    }
+}
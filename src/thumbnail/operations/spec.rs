@@ -0,0 +1,132 @@
+#[cfg(feature = "face-detection")]
+use crate::thumbnail::operations::FaceCropOp;
+use crate::thumbnail::operations::{
+    AppendOp, AutoContrastOp, BlurOp, BlurRegionOp, BrightenOp, CaptionOp, CombineInRegionOp,
+    CombineOp, ContrastOp, CropOp, DiagonalWatermarkOp, DistortionOp, EdgeDetectOp, EmbossOp,
+    ExifOp, FastBlurOp, FlipOp, HuerotateOp, InvertOp, LevelsOp, MedianOp, OpacityOp, Operation,
+    PadToRatioOp, QuantizeOp, ResizeOp, RotateOp, SelectiveDesaturateOp, ShadowOp,
+    SmartWatermarkOp, TemperatureOp, TextOp, TintOp, TrimOp, UnsharpenOp,
+};
+
+/// The `OperationSpec` type.
+///
+/// Serializable representation of an operation. Each variant holds the struct of the corresponding operation.
+/// This allows to persist a queue of operations, e.g. as JSON, and to restore it later.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum OperationSpec {
+    /// Representation of `AppendOp`
+    Append(AppendOp),
+    /// Representation of `AutoContrastOp`
+    AutoContrast(AutoContrastOp),
+    /// Representation of `BlurOp`
+    Blur(BlurOp),
+    /// Representation of `BlurRegionOp`
+    BlurRegion(BlurRegionOp),
+    /// Representation of `BrightenOp`
+    Brighten(BrightenOp),
+    /// Representation of `CaptionOp`
+    Caption(CaptionOp),
+    /// Representation of `CombineOp`
+    Combine(CombineOp),
+    /// Representation of `CombineInRegionOp`
+    CombineInRegion(CombineInRegionOp),
+    /// Representation of `ContrastOp`
+    Contrast(ContrastOp),
+    /// Representation of `CropOp`
+    Crop(CropOp),
+    /// Representation of `DiagonalWatermarkOp`
+    DiagonalWatermark(DiagonalWatermarkOp),
+    /// Representation of `DistortionOp`
+    Distortion(DistortionOp),
+    /// Representation of `EdgeDetectOp`
+    EdgeDetect(EdgeDetectOp),
+    /// Representation of `EmbossOp`
+    Emboss(EmbossOp),
+    /// Representation of `ExifOp`
+    Exif(ExifOp),
+    /// Representation of `FaceCropOp`
+    #[cfg(feature = "face-detection")]
+    FaceCrop(FaceCropOp),
+    /// Representation of `FastBlurOp`
+    FastBlur(FastBlurOp),
+    /// Representation of `FlipOp`
+    Flip(FlipOp),
+    /// Representation of `HuerotateOp`
+    Huerotate(HuerotateOp),
+    /// Representation of `InvertOp`
+    Invert(InvertOp),
+    /// Representation of `LevelsOp`
+    Levels(LevelsOp),
+    /// Representation of `MedianOp`
+    Median(MedianOp),
+    /// Representation of `OpacityOp`
+    Opacity(OpacityOp),
+    /// Representation of `PadToRatioOp`
+    PadToRatio(PadToRatioOp),
+    /// Representation of `QuantizeOp`
+    Quantize(QuantizeOp),
+    /// Representation of `ResizeOp`
+    Resize(ResizeOp),
+    /// Representation of `RotateOp`
+    Rotate(RotateOp),
+    /// Representation of `SelectiveDesaturateOp`
+    SelectiveDesaturate(SelectiveDesaturateOp),
+    /// Representation of `ShadowOp`
+    Shadow(ShadowOp),
+    /// Representation of `SmartWatermarkOp`
+    SmartWatermark(SmartWatermarkOp),
+    /// Representation of `TemperatureOp`
+    Temperature(TemperatureOp),
+    /// Representation of `TextOp`
+    Text(TextOp),
+    /// Representation of `TintOp`
+    Tint(TintOp),
+    /// Representation of `TrimOp`
+    Trim(TrimOp),
+    /// Representation of `UnsharpenOp`
+    Unsharpen(UnsharpenOp),
+}
+
+impl OperationSpec {
+    /// Turns the `OperationSpec` into the `Operation` it represents
+    pub fn into_operation(self) -> Box<dyn Operation> {
+        match self {
+            OperationSpec::Append(op) => Box::new(op),
+            OperationSpec::AutoContrast(op) => Box::new(op),
+            OperationSpec::Blur(op) => Box::new(op),
+            OperationSpec::BlurRegion(op) => Box::new(op),
+            OperationSpec::Brighten(op) => Box::new(op),
+            OperationSpec::Caption(op) => Box::new(op),
+            OperationSpec::Combine(op) => Box::new(op),
+            OperationSpec::CombineInRegion(op) => Box::new(op),
+            OperationSpec::Contrast(op) => Box::new(op),
+            OperationSpec::Crop(op) => Box::new(op),
+            OperationSpec::DiagonalWatermark(op) => Box::new(op),
+            OperationSpec::Distortion(op) => Box::new(op),
+            OperationSpec::EdgeDetect(op) => Box::new(op),
+            OperationSpec::Emboss(op) => Box::new(op),
+            OperationSpec::Exif(op) => Box::new(op),
+            #[cfg(feature = "face-detection")]
+            OperationSpec::FaceCrop(op) => Box::new(op),
+            OperationSpec::FastBlur(op) => Box::new(op),
+            OperationSpec::Flip(op) => Box::new(op),
+            OperationSpec::Huerotate(op) => Box::new(op),
+            OperationSpec::Invert(op) => Box::new(op),
+            OperationSpec::Levels(op) => Box::new(op),
+            OperationSpec::Median(op) => Box::new(op),
+            OperationSpec::Opacity(op) => Box::new(op),
+            OperationSpec::PadToRatio(op) => Box::new(op),
+            OperationSpec::Quantize(op) => Box::new(op),
+            OperationSpec::Resize(op) => Box::new(op),
+            OperationSpec::Rotate(op) => Box::new(op),
+            OperationSpec::SelectiveDesaturate(op) => Box::new(op),
+            OperationSpec::Shadow(op) => Box::new(op),
+            OperationSpec::SmartWatermark(op) => Box::new(op),
+            OperationSpec::Temperature(op) => Box::new(op),
+            OperationSpec::Text(op) => Box::new(op),
+            OperationSpec::Tint(op) => Box::new(op),
+            OperationSpec::Trim(op) => Box::new(op),
+            OperationSpec::Unsharpen(op) => Box::new(op),
+        }
+    }
+}
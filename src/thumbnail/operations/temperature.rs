@@ -0,0 +1,121 @@
+pub use crate::errors::OperationError;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use crate::thumbnail::operations::{change_rgba8, Operation};
+use image::{DynamicImage, Rgba};
+
+/// The shift in Kelvin, that changes the red and blue channels by 1 percent
+const KELVIN_PER_PERCENT: f32 = 100.0;
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the color-temperature-operation as a struct
+pub struct TemperatureOp {
+    /// The shift of the color temperature, positive values warm and negative values cool the image
+    kelvin_shift: i32,
+}
+
+impl TemperatureOp {
+    /// Returns a new `TemperatureOp` struct with defined:
+    /// * `kelvin_shift`: The shift of the color temperature. Positive values warm the image, negative values cool it.
+    ///   Every 100 raise one of the red and blue channels by 1 percent and lower the other one by 1 percent,
+    ///   e.g. 1000 is a noticeable and 3000 a strong warming.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TemperatureOp;
+    /// use image::{DynamicImage, GenericImageView};
+    ///
+    /// // Returns the mean of each color channel
+    /// fn channel_means(image: &DynamicImage) -> [f64; 3] {
+    ///     let mut sums = [0f64; 3];
+    ///     for (_, _, pixel) in image.pixels() {
+    ///         for channel in 0..3 {
+    ///             sums[channel] += pixel[channel] as f64;
+    ///         }
+    ///     }
+    ///     let count = (image.width() * image.height()) as f64;
+    ///     [sums[0] / count, sums[1] / count, sums[2] / count]
+    /// }
+    ///
+    /// let original = image::open("resources/tests/test.jpg").unwrap();
+    /// let before = channel_means(&original);
+    ///
+    /// let mut warm = original.clone();
+    /// assert!(TemperatureOp::new(1500).apply(&mut warm).is_ok());
+    /// let after = channel_means(&warm);
+    /// assert!(after[0] > before[0]);
+    /// assert!(after[2] < before[2]);
+    /// assert!((after[1] - before[1]).abs() < 0.5);
+    ///
+    /// let mut cool = original.clone();
+    /// assert!(TemperatureOp::new(-1500).apply(&mut cool).is_ok());
+    /// let after = channel_means(&cool);
+    /// assert!(after[0] < before[0]);
+    /// assert!(after[2] > before[2]);
+    /// ```
+    pub fn new(kelvin_shift: i32) -> Self {
+        TemperatureOp { kelvin_shift }
+    }
+}
+
+impl Operation for TemperatureOp {
+    /// Logic for the color-temperature-operation
+    ///
+    /// This function shifts the white balance of a `DynamicImage` towards warm or cool colors.
+    /// Warming multiplies the red channel of each pixel with a factor above 1.0 and the blue channel with
+    /// a factor below 1.0 by the same amount, cooling does the opposite. The channels are clamped to their range.
+    /// The green and the alpha channel are kept.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `TemperatureOp` struct
+    /// * `image` - The `DynamicImage` whose color temperature should be shifted
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TemperatureOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(RgbImage::from_pixel(10, 10, Rgb([100, 100, 250])));
+    ///
+    /// let temperature_op = TemperatureOp::new(1000);
+    /// assert!(temperature_op.apply(&mut dynamic_image).is_ok());
+    ///
+    /// assert_eq!(dynamic_image.get_pixel(5, 5).0, [110, 100, 225, 255]);
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let shift = (self.kelvin_shift as f32 / KELVIN_PER_PERCENT / 100.0).clamp(-1.0, 1.0);
+        if shift == 0.0 {
+            return Ok(());
+        }
+
+        change_rgba8(image, |buffer| {
+            for pixel in buffer.pixels_mut() {
+                *pixel = Rgba([
+                    (pixel[0] as f32 * (1.0 + shift)).round().clamp(0.0, 255.0) as u8,
+                    pixel[1],
+                    (pixel[2] as f32 * (1.0 - shift)).round().clamp(0.0, 255.0) as u8,
+                    pixel[3],
+                ]);
+            }
+        });
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `TemperatureOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Temperature(*self))
+    }
+}
@@ -1,17 +1,22 @@
 pub use crate::errors::{OperationError, OperationErrorInfo};
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use crate::BoxPosition;
-use image::{DynamicImage, Pixel};
-use imageproc::drawing::draw_text_mut;
-use rusttype::{Font, Scale};
+use image::{DynamicImage, GenericImage, GenericImageView, ImageBuffer, Pixel, Rgba};
+use rusttype::{point, Font, PositionedGlyph, Scale};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the operation of drawing texts as a struct
 pub struct TextOp {
     /// The text that should be drawn
     text: String,
     /// Specifies the position of the Text, represented by `BoxPosition` enum
     pos: BoxPosition,
+    /// The font height relative to the image height. If not set, the font height is 12 pixels
+    #[cfg_attr(feature = "serde", serde(default))]
+    height_fraction: Option<f32>,
 }
 
 impl TextOp {
@@ -19,7 +24,53 @@ impl TextOp {
     /// * `text` as the text that should be drawn
     /// * `pos` as the position of the text represented by `BoxPosition` enum
     pub fn new(text: String, pos: BoxPosition) -> Self {
-        TextOp { text, pos }
+        TextOp {
+            text,
+            pos,
+            height_fraction: None,
+        }
+    }
+
+    /// Returns a new `TextOp` struct, that scales the text with the image, with defined:
+    /// * `text` as the text that should be drawn
+    /// * `pos` as the position of the text represented by `BoxPosition` enum
+    /// * `fraction` as the font height relative to the height of the image, e.g. 0.1 for a tenth of it.
+    ///   Has to be greater than 0.0.
+    ///
+    /// The font height is computed when the operation is applied, so the same caption keeps its proportions
+    /// on images of different sizes. It spans from the top of the highest to the bottom of the lowest glyphs of the font,
+    /// e.g. the accent of `É` and the descender of `g`.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::BoxPosition;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TextOp;
+    /// use image::{DynamicImage, GenericImageView};
+    ///
+    /// // Returns the height of all non-black pixels
+    /// fn text_height(image: &DynamicImage) -> u32 {
+    ///     let rows: Vec<u32> = image.pixels().filter(|(_, _, p)| p[0] > 0).map(|(_, y, _)| y).collect();
+    ///     rows.iter().max().unwrap() - rows.iter().min().unwrap() + 1
+    /// }
+    ///
+    /// let text_op = TextOp::new_relative("Ég".to_string(), BoxPosition::TopLeft(10, 10), 0.1);
+    ///
+    /// let mut large = DynamicImage::new_luma8(400, 1000);
+    /// assert!(text_op.apply(&mut large).is_ok());
+    /// let height = text_height(&large);
+    /// assert!(height > 90 && height <= 100, "the text is {} pixels high", height);
+    ///
+    /// let mut small = DynamicImage::new_luma8(400, 500);
+    /// assert!(text_op.apply(&mut small).is_ok());
+    /// assert!((text_height(&small) as i64 - height as i64 / 2).abs() <= 2);
+    /// ```
+    pub fn new_relative(text: String, pos: BoxPosition, fraction: f32) -> Self {
+        TextOp {
+            text,
+            pos,
+            height_fraction: Some(fraction),
+        }
     }
 }
 
@@ -32,6 +83,19 @@ impl Operation for TextOp {
     /// * with `BoxPosition::BottomLeft`: The bottom-left-corner of the text is placed at the defined coordinates
     /// * with `BoxPosition::BottomRight`: The bottom-right-corner of the text is placed at the defined coordinates
     ///
+    /// The corners refer to the bounding box of the rendered pixels of the text.
+    /// As with `CombineOp`, the right and bottom edges are exclusive,
+    /// e.g. with `BoxPosition::BottomRight(x, y)` the last pixel of the text is drawn at `(x - 1, y - 1)`.
+    /// Parts of the text outside of the image are clipped.
+    ///
+    /// The text is measured by laying out all glyphs with the kerning of the font, so the bounding box also fits
+    /// non-ASCII text, e.g. accents of combining characters. Characters the font has no glyph for are drawn as a box,
+    /// the missing glyph of the font, and take up space like any other character.
+    ///
+    /// The text is anti-aliased: the coverage of each pixel is the exact area of the glyph outlines inside the pixel,
+    /// with the glyphs placed at subpixel positions. Rendering the text at a multiple of the size and scaling it down
+    /// afterwards gives the same result, so small texts don't get smoother by supersampling.
+    ///
     /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
     ///
     /// # Arguments
@@ -43,6 +107,7 @@ impl Operation for TextOp {
     ///
     /// * FontLoadError - The font cannnot be loaded
     /// * CoordinatesOutOfRange - The coordinates for the text are not inside the background image
+    /// * InvalidParameters - The relative font height is not greater than 0.0
     ///
     /// # Panic
     ///
@@ -63,11 +128,115 @@ impl Operation for TextOp {
     ///
     /// assert!(res.is_ok());
     /// ```
+    ///
+    /// The named corner of the rendered text lands at the given coordinates:
+    /// ```
+    /// use thumbnailer::generic::BoxPosition;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TextOp;
+    /// use image::{DynamicImage, GenericImageView};
+    ///
+    /// // Returns the bounding box of all non-black pixels as (left, top, right, bottom), right and bottom exclusive
+    /// fn text_bounds(image: &DynamicImage) -> (i64, i64, i64, i64) {
+    ///     let mut bounds = (i64::MAX, i64::MAX, i64::MIN, i64::MIN);
+    ///     for (x, y, pixel) in image.pixels() {
+    ///         if pixel[0] > 0 {
+    ///             bounds.0 = bounds.0.min(x as i64);
+    ///             bounds.1 = bounds.1.min(y as i64);
+    ///             bounds.2 = bounds.2.max(x as i64 + 1);
+    ///             bounds.3 = bounds.3.max(y as i64 + 1);
+    ///         }
+    ///     }
+    ///     bounds
+    /// }
+    ///
+    /// let positions = vec![
+    ///     BoxPosition::TopLeft(20, 20),
+    ///     BoxPosition::TopRight(180, 20),
+    ///     BoxPosition::BottomLeft(20, 80),
+    ///     BoxPosition::BottomRight(180, 80),
+    /// ];
+    ///
+    /// for position in positions {
+    ///     let mut dynamic_image = DynamicImage::new_rgb8(200, 100);
+    ///     let text_op = TextOp::new("Typography".to_string(), position);
+    ///     assert!(text_op.apply(&mut dynamic_image).is_ok());
+    ///
+    ///     let (left, top, right, bottom) = text_bounds(&dynamic_image);
+    ///     let (x, y) = match position {
+    ///         BoxPosition::TopLeft(x, y) => (left - x as i64, top - y as i64),
+    ///         BoxPosition::TopRight(x, y) => (right - x as i64, top - y as i64),
+    ///         BoxPosition::BottomLeft(x, y) => (left - x as i64, bottom - y as i64),
+    ///         BoxPosition::BottomRight(x, y) => (right - x as i64, bottom - y as i64),
+    ///     };
+    ///     assert!(x.abs() <= 1 && y.abs() <= 1, "{:?} is off by ({}, {})", position, x, y);
+    /// }
+    /// ```
+    ///
+    /// Accented, combined and missing characters are measured correctly as well:
+    /// ```
+    /// use thumbnailer::generic::BoxPosition;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TextOp;
+    /// use image::{DynamicImage, GenericImageView};
+    ///
+    /// // Returns the right and bottom edge of all non-black pixels, exclusive
+    /// fn right_bottom(image: &DynamicImage) -> (u32, u32) {
+    ///     image.pixels().filter(|(_, _, p)| p[0] > 0).fold((0, 0), |(r, b), (x, y, _)| (r.max(x + 1), b.max(y + 1)))
+    /// }
+    ///
+    /// // precomposed and combining accents, and a character the font has no glyph for
+    /// for text in &["Crème brûlée", "Cafe\u{301} cre\u{300}me", "Tea \u{2615}"] {
+    ///     let mut dynamic_image = DynamicImage::new_luma8(200, 60);
+    ///     let text_op = TextOp::new(text.to_string(), BoxPosition::BottomRight(150, 40));
+    ///     assert!(text_op.apply(&mut dynamic_image).is_ok());
+    ///
+    ///     let (right, bottom) = right_bottom(&dynamic_image);
+    ///     assert!((right as i64 - 150).abs() <= 1, "{} ends at {}", text, right);
+    ///     assert!((bottom as i64 - 40).abs() <= 1, "{} ends at {}", text, bottom);
+    /// }
+    ///
+    /// // the missing glyph is drawn as a box instead of being left out
+    /// let measure = |text: &str| {
+    ///     let mut dynamic_image = DynamicImage::new_luma8(200, 60);
+    ///     assert!(TextOp::new(text.to_string(), BoxPosition::TopLeft(0, 0)).apply(&mut dynamic_image).is_ok());
+    ///     right_bottom(&dynamic_image).0
+    /// };
+    /// assert!(measure("Tea \u{2615}") > measure("Tea ") + 2);
+    /// ```
+    ///
+    /// The edges of the glyphs are anti-aliased, small texts consist mostly of partially covered pixels:
+    /// ```
+    /// use thumbnailer::generic::BoxPosition;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TextOp;
+    /// use image::{DynamicImage, GenericImageView};
+    ///
+    /// let mut dynamic_image = DynamicImage::new_luma8(120, 30);
+    /// let text_op = TextOp::new("Small caption".to_string(), BoxPosition::TopLeft(2, 2));
+    /// assert!(text_op.apply(&mut dynamic_image).is_ok());
+    ///
+    /// let drawn: Vec<u8> = dynamic_image.pixels().map(|(_, _, pixel)| pixel[0]).filter(|&v| v > 0).collect();
+    /// let partial = drawn.iter().filter(|&&v| v < 255).count();
+    /// assert!(partial * 2 > drawn.len());
+    /// ```
     fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
     where
         Self: Sized,
     {
-        let scale = Scale { x: 12.0, y: 12.0 };
+        let size = match self.height_fraction {
+            Some(fraction) if fraction.is_finite() && fraction > 0.0 => {
+                fraction * image.height() as f32
+            }
+            Some(_) => {
+                return Err(OperationError::new(
+                    Box::new(self.clone()),
+                    OperationErrorInfo::InvalidParameters,
+                ))
+            }
+            None => 12.0,
+        };
+        let scale = Scale::uniform(size);
 
         let font_data: &[u8] = include_bytes!("../../../resources/fonts/Roboto-Regular.ttf");
         let font: Font<'static> = match Font::from_bytes(font_data) {
@@ -80,57 +249,112 @@ impl Operation for TextOp {
             }
         };
 
-        let mut string_width = 0.0;
-        let string_height = font.v_metrics(scale).ascent - font.v_metrics(scale).descent;
+        let glyphs: Vec<PositionedGlyph> = font
+            .layout(&self.text, scale, point(0.0, font.v_metrics(scale).ascent))
+            .collect();
 
-        for glyph in font.glyphs_for(self.text.chars()) {
-            string_width += glyph.scaled(scale).h_metrics().advance_width;
+        // The bounding box of the actually rendered pixels, relative to the layout origin
+        let (min_x, min_y, max_x, max_y) = glyphs
+            .iter()
+            .filter_map(|glyph| glyph.pixel_bounding_box())
+            .fold((i32::MAX, i32::MAX, i32::MIN, i32::MIN), |acc, bb| {
+                (
+                    acc.0.min(bb.min.x),
+                    acc.1.min(bb.min.y),
+                    acc.2.max(bb.max.x),
+                    acc.3.max(bb.max.y),
+                )
+            });
+
+        // Nothing to draw, e.g. the text is empty or consists of whitespace only
+        if min_x > max_x {
+            return Ok(());
         }
 
-        let (pos_x, pos_y) = match self.pos {
-            BoxPosition::TopLeft(x, y) => (x, y),
-            BoxPosition::TopRight(x, y) => {
-                if x >= string_width as u32 {
-                    (x - string_width as u32, y)
-                } else {
-                    return Err(OperationError::new(
-                        Box::new(self.clone()),
-                        OperationErrorInfo::CoordinatesOutOfRange,
-                    ));
-                }
+        let string_width = (max_x - min_x) as i64;
+        let string_height = (max_y - min_y) as i64;
+
+        // The top-left-corner of the bounding box of the text
+        let (left, top) = match self.pos {
+            BoxPosition::TopLeft(x, y) => (x as i64, y as i64),
+            BoxPosition::TopRight(x, y) => (x as i64 - string_width, y as i64),
+            BoxPosition::BottomLeft(x, y) => (x as i64, y as i64 - string_height),
+            BoxPosition::BottomRight(x, y) => (x as i64 - string_width, y as i64 - string_height),
+        };
+
+        if left < 0 || top < 0 {
+            return Err(OperationError::new(
+                Box::new(self.clone()),
+                OperationErrorInfo::CoordinatesOutOfRange,
+            ));
+        }
+
+        let (width, height) = image.dimensions();
+        let offset_x = left - min_x as i64;
+        let offset_y = top - min_y as i64;
+
+        // The pixels covered by the text, with the coverage of each pixel
+        let mut covered: Vec<(u32, u32, f32)> = vec![];
+        for glyph in &glyphs {
+            if let Some(bb) = glyph.pixel_bounding_box() {
+                glyph.draw(|gx, gy, coverage| {
+                    let x = offset_x + bb.min.x as i64 + gx as i64;
+                    let y = offset_y + bb.min.y as i64 + gy as i64;
+
+                    if x < width as i64 && y < height as i64 {
+                        covered.push((x as u32, y as u32, coverage));
+                    }
+                });
             }
-            BoxPosition::BottomLeft(x, y) => {
-                if y >= string_height as u32 {
-                    (x, y - string_height as u32)
-                } else {
-                    return Err(OperationError::new(
-                        Box::new(self.clone()),
-                        OperationErrorInfo::CoordinatesOutOfRange,
-                    ));
-                }
+        }
+
+        // 16-bit images are drawn on directly, to keep their precision
+        match image {
+            DynamicImage::ImageLuma16(buffer) => draw_16_bit(buffer, &covered, |p| p.to_luma()),
+            DynamicImage::ImageLumaA16(buffer) => {
+                draw_16_bit(buffer, &covered, |p| p.to_luma_alpha())
             }
-            BoxPosition::BottomRight(x, y) => {
-                if x >= string_width as u32 && y >= string_height as u32 {
-                    (x - string_width as u32, y - string_height as u32)
-                } else {
-                    return Err(OperationError::new(
-                        Box::new(self.clone()),
-                        OperationErrorInfo::CoordinatesOutOfRange,
-                    ));
+            DynamicImage::ImageRgb16(buffer) => draw_16_bit(buffer, &covered, |p| p.to_rgb()),
+            DynamicImage::ImageRgba16(buffer) => draw_16_bit(buffer, &covered, |p| *p),
+            _ => {
+                for (x, y, coverage) in covered {
+                    let mut pixel = image.get_pixel(x, y);
+                    pixel.blend(&Rgba([255, 255, 255, (coverage * 255.0) as u8]));
+                    image.put_pixel(x, y, pixel);
                 }
             }
-        };
-
-        draw_text_mut(
-            image,
-            Pixel::from_channels(255u8, 255u8, 255u8, 255u8),
-            pos_x,
-            pos_y,
-            scale,
-            &font,
-            &self.text,
-        );
+        }
 
         Ok(())
     }
+
+    /// Gets the serializable representation of the `TextOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Text(self.clone()))
+    }
+}
+
+/// Draws white text with the given coverage onto an image with 16 bits per channel
+///
+/// * buffer: &mut ImageBuffer<P, Vec<u16>> - The image
+/// * covered: &[(u32, u32, f32)] - The pixels covered by the text, with the coverage of each pixel
+/// * from_rgba: fn(&Rgba<u16>) -> P - Converts the blended pixel back to the pixel type of the image
+fn draw_16_bit<P>(
+    buffer: &mut ImageBuffer<P, Vec<u16>>,
+    covered: &[(u32, u32, f32)],
+    from_rgba: fn(&Rgba<u16>) -> P,
+) where
+    P: Pixel<Subpixel = u16> + 'static,
+{
+    for &(x, y, coverage) in covered {
+        let mut pixel = buffer.get_pixel(x, y).to_rgba();
+        pixel.blend(&Rgba([
+            u16::MAX,
+            u16::MAX,
+            u16::MAX,
+            (coverage * u16::MAX as f32) as u16,
+        ]));
+        buffer.put_pixel(x, y, from_rgba(&pixel));
+    }
 }
@@ -0,0 +1,180 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, Rgba};
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// The color mapping applied by the tint-operation
+enum TintMode {
+    /// Blends the image towards a single color with the given strength
+    Color([u8; 3], f32),
+    /// Maps the shadows to the first and the highlights to the second color
+    Duotone([u8; 3], [u8; 3]),
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the tint-operation as a struct
+pub struct TintOp {
+    /// The color mapping, represented by the `TintMode` enum
+    mode: TintMode,
+}
+
+impl TintOp {
+    /// Returns a new `TintOp` struct, that blends the image towards a single hue, with defined:
+    /// * `color`: The RGB color the image is tinted with
+    /// * `strength`: How much the image is tinted. `0.0` keeps the image as it is, `1.0` results in a monochrome image
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TintOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// let gray = DynamicImage::ImageRgb8(RgbImage::from_pixel(10, 10, Rgb([128, 128, 128])));
+    ///
+    /// let mut half = gray.clone();
+    /// assert!(TintOp::new([0, 0, 255], 0.5).apply(&mut half).is_ok());
+    /// let mut full = gray.clone();
+    /// assert!(TintOp::new([0, 0, 255], 1.0).apply(&mut full).is_ok());
+    ///
+    /// let blue_half = half.get_pixel(5, 5)[2] as i32 - 128;
+    /// let blue_full = full.get_pixel(5, 5)[2] as i32 - 128;
+    ///
+    /// // the blue channel is raised proportionally to the strength
+    /// assert!(blue_full > 100);
+    /// assert!((blue_full - 2 * blue_half).abs() <= 2);
+    /// // while red is lowered
+    /// assert!(full.get_pixel(5, 5)[0] < half.get_pixel(5, 5)[0]);
+    /// assert!(half.get_pixel(5, 5)[0] < 128);
+    /// ```
+    pub fn new(color: [u8; 3], strength: f32) -> Self {
+        TintOp {
+            mode: TintMode::Color(color, strength),
+        }
+    }
+
+    /// Returns a new `TintOp` struct, that maps the image to a duotone, with defined:
+    /// * `shadow`: The RGB color the darkest parts of the image are mapped to
+    /// * `highlight`: The RGB color the brightest parts of the image are mapped to
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TintOp;
+    /// use image::{DynamicImage, GenericImage, GenericImageView, Rgba};
+    ///
+    /// let mut dynamic_image = DynamicImage::new_rgb8(2, 1);
+    /// dynamic_image.put_pixel(1, 0, Rgba([255, 255, 255, 255]));
+    ///
+    /// let duotone_op = TintOp::new_duotone([20, 0, 60], [255, 200, 0]);
+    /// assert!(duotone_op.apply(&mut dynamic_image).is_ok());
+    ///
+    /// assert_eq!(dynamic_image.get_pixel(0, 0), Rgba([20, 0, 60, 255]));
+    /// assert_eq!(dynamic_image.get_pixel(1, 0), Rgba([255, 200, 0, 255]));
+    /// ```
+    pub fn new_duotone(shadow: [u8; 3], highlight: [u8; 3]) -> Self {
+        TintOp {
+            mode: TintMode::Duotone(shadow, highlight),
+        }
+    }
+}
+
+/// Linearly interpolates between two RGB colors, `t` being between 0 and 1
+fn mix(from: [f32; 3], to: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        from[0] + (to[0] - from[0]) * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+    ]
+}
+
+/// Converts a RGB color from `u8` to `f32` channels
+fn to_f32(color: [u8; 3]) -> [f32; 3] {
+    [color[0] as f32, color[1] as f32, color[2] as f32]
+}
+
+impl Operation for TintOp {
+    /// Logic for the tint-operation
+    ///
+    /// This function converts each pixel of a `DynamicImage` to its luminance, and maps the luminance to a color:
+    /// * with a single color: Black is mapped to black, mid-gray to the color and white to white.
+    ///   The result is blended with the original pixel, based on the strength.
+    /// * with a duotone: The luminance is mapped to a gradient from the shadow color to the highlight color.
+    ///
+    /// More information: [Duotone](https://en.wikipedia.org/wiki/Duotone)
+    /// The alpha channel is kept.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `TintOp` struct
+    /// * `image` - The `DynamicImage` that should be tinted
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TintOp;
+    /// use image::DynamicImage;
+    ///
+    /// let mut dynamic_image = DynamicImage::new_rgb8(800, 500);
+    ///
+    /// let tint_op = TintOp::new([0, 90, 200], 0.8);
+    /// let res = tint_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let has_alpha = image.color().has_alpha();
+        let mut buffer = image.to_rgba8();
+
+        for pixel in buffer.pixels_mut() {
+            let original = [pixel[0] as f32, pixel[1] as f32, pixel[2] as f32];
+            let luminance =
+                (0.2126 * original[0] + 0.7152 * original[1] + 0.0722 * original[2]) / 255.0;
+
+            let mapped = match self.mode {
+                TintMode::Color(color, strength) => {
+                    let toned = if luminance <= 0.5 {
+                        mix([0.0; 3], to_f32(color), luminance * 2.0)
+                    } else {
+                        mix(to_f32(color), [255.0; 3], luminance * 2.0 - 1.0)
+                    };
+                    mix(original, toned, strength.clamp(0.0, 1.0))
+                }
+                TintMode::Duotone(shadow, highlight) => {
+                    mix(to_f32(shadow), to_f32(highlight), luminance)
+                }
+            };
+
+            *pixel = Rgba([
+                mapped[0].round().clamp(0.0, 255.0) as u8,
+                mapped[1].round().clamp(0.0, 255.0) as u8,
+                mapped[2].round().clamp(0.0, 255.0) as u8,
+                pixel[3],
+            ]);
+        }
+
+        *image = if has_alpha {
+            DynamicImage::ImageRgba8(buffer)
+        } else {
+            DynamicImage::ImageRgb8(DynamicImage::ImageRgba8(buffer).to_rgb8())
+        };
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `TintOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Tint(*self))
+    }
+}
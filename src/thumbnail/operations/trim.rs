@@ -0,0 +1,126 @@
+pub use crate::errors::OperationError;
+use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
+use image::{DynamicImage, GenericImageView, Rgba};
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Representation of the trim-operation as a struct
+pub struct TrimOp {
+    /// The maximum difference of a channel to the background color, for a pixel to count as background
+    tolerance: u8,
+}
+
+impl TrimOp {
+    /// Returns a new `TrimOp` struct with defined:
+    /// * `tolerance`: A pixel is part of the content, if one of its channels differs from the
+    ///   background color by more than `tolerance`
+    pub fn new(tolerance: u8) -> Self {
+        TrimOp { tolerance }
+    }
+
+    /// Checks whether a pixel differs from the background color by more than the tolerance
+    fn is_content(&self, pixel: Rgba<u8>, background: Rgba<u8>) -> bool {
+        pixel
+            .0
+            .iter()
+            .zip(background.0.iter())
+            .any(|(p, b)| (*p as i16 - *b as i16).unsigned_abs() > self.tolerance as u16)
+    }
+}
+
+impl Operation for TrimOp {
+    /// Logic for the trim-operation
+    ///
+    /// This function removes uniform borders from a `DynamicImage`.
+    /// The color of the top-left corner is taken as background color. The image is cropped to the
+    /// bounding box of all pixels, that differ from the background color by more than the tolerance.
+    /// If the whole image has the background color, the image is left unchanged.
+    /// It returns `Ok(())` on success and `Err(OperationError)` in case of an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - The `TrimOp` struct
+    /// * `image` - The `DynamicImage` that should be trimmed
+    ///
+    /// # Panic
+    ///
+    /// This function won't panic.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::TrimOp;
+    /// use image::{DynamicImage, GenericImageView, Rgb, RgbImage};
+    ///
+    /// // a 100x100 image with a white border of 10 pixels
+    /// let bordered = RgbImage::from_fn(100, 100, |x, y| {
+    ///     if (10..90).contains(&x) && (10..90).contains(&y) {
+    ///         Rgb([30, 60, 90])
+    ///     } else {
+    ///         Rgb([255, 255, 255])
+    ///     }
+    /// });
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(bordered);
+    ///
+    /// let trim_op = TrimOp::new(10);
+    /// let res = trim_op.apply(&mut dynamic_image);
+    ///
+    /// assert!(res.is_ok());
+    /// assert_eq!(dynamic_image.dimensions(), (80, 80));
+    ///
+    /// // a scanned 100x100 image with a 50x50 center, on a slightly noisy white margin
+    /// let scanned = RgbImage::from_fn(100, 100, |x, y| {
+    ///     if (25..75).contains(&x) && (25..75).contains(&y) {
+    ///         Rgb([200, 40, 40])
+    ///     } else {
+    ///         let noise = ((x * 7 + y * 13) % 6) as u8;
+    ///         Rgb([255 - noise, 255 - noise, 255 - noise])
+    ///     }
+    /// });
+    /// let mut dynamic_image = DynamicImage::ImageRgb8(scanned);
+    /// assert!(trim_op.apply(&mut dynamic_image).is_ok());
+    /// assert_eq!(dynamic_image.dimensions(), (50, 50));
+    ///
+    /// // a uniform image is not changed
+    /// let mut uniform = DynamicImage::new_rgb8(50, 40);
+    /// assert!(trim_op.apply(&mut uniform).is_ok());
+    /// assert_eq!(uniform.dimensions(), (50, 40));
+    /// ```
+    fn apply(&self, image: &mut DynamicImage) -> Result<(), OperationError>
+    where
+        Self: Sized,
+    {
+        let (width, height) = image.dimensions();
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        let background = image.get_pixel(0, 0);
+        let mut bounds: Option<(u32, u32, u32, u32)> = None;
+
+        for (x, y, pixel) in image.pixels() {
+            if self.is_content(pixel, background) {
+                bounds = Some(match bounds {
+                    None => (x, y, x, y),
+                    Some((min_x, min_y, max_x, max_y)) => {
+                        (min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y))
+                    }
+                });
+            }
+        }
+
+        if let Some((min_x, min_y, max_x, max_y)) = bounds {
+            *image = image.crop_imm(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1);
+        }
+
+        Ok(())
+    }
+
+    /// Gets the serializable representation of the `TrimOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Trim(*self))
+    }
+}
@@ -1,8 +1,11 @@
 pub use crate::errors::OperationError;
 use crate::thumbnail::operations::Operation;
+#[cfg(feature = "serde")]
+use crate::thumbnail::operations::OperationSpec;
 use image::DynamicImage;
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Representation of the unsharpen-operation as a struct
 pub struct UnsharpenOp {
     /// amount to blur the image by
@@ -58,4 +61,10 @@ impl Operation for UnsharpenOp {
         *image = image.unsharpen(self.sigma, self.threshold);
         Ok(())
     }
+
+    /// Gets the serializable representation of the `UnsharpenOp`
+    #[cfg(feature = "serde")]
+    fn to_spec(&self) -> Option<OperationSpec> {
+        Some(OperationSpec::Unsharpen(*self))
+    }
 }
@@ -1,23 +1,77 @@
+use image::imageops::FilterType;
 use image::{DynamicImage, GenericImageView};
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Formatter;
 use std::path::PathBuf;
 
+/// The maximum width and height, to which the image is downsampled before the dominant color is determined
+const DOMINANT_COLOR_SAMPLE_SIZE: u32 = 64;
+/// The number of bits per channel, which decide the histogram bucket of a pixel
+const DOMINANT_COLOR_BUCKET_BITS: u8 = 4;
+/// The width and height of the grayscale image, of which the perceptual hash is computed
+const PHASH_SAMPLE_SIZE: usize = 32;
+/// The number of low frequencies in each direction, that make up the perceptual hash
+const PHASH_FREQUENCIES: usize = 8;
+
 /// The `StaticThumbnail` type.
 ///
 /// This type is a non modifiable image. No operations can be performed on it.
 /// It is used in certain operations as an argument itself (e.g. the combine operation).
 #[derive(Clone)]
 pub struct StaticThumbnail {
-    /// The path from which this image originates from
-    src_path: PathBuf,
+    /// The path from which this image originates from, `None` if the image was created in memory
+    src_path: Option<PathBuf>,
     /// The actual image data
     image: DynamicImage,
 }
 
 impl fmt::Debug for StaticThumbnail {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "StaticThumbnail {{ {:?}, DynamicImage}}", self.src_path)
+        match &self.src_path {
+            Some(path) => write!(f, "StaticThumbnail {{ {}, DynamicImage }}", path.display()),
+            None => write!(f, "StaticThumbnail {{ (in memory), DynamicImage }}"),
+        }
+    }
+}
+
+/// Serializes a `StaticThumbnail` held by an operation by its origin path only
+///
+/// Use it with `#[serde(with = "crate::thumbnail::static_thumb::by_path")]`.
+/// Instead of the image data only the path is serialized, and the image is loaded from that path again
+/// when deserializing. Images without a path, that have been created in memory, cannot be restored.
+#[cfg(feature = "serde")]
+pub(crate) mod by_path {
+    use super::StaticThumbnail;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::path::PathBuf;
+
+    /// Serializes the origin path of the image, `None` if it was created in memory
+    pub(crate) fn serialize<S: Serializer>(
+        thumb: &StaticThumbnail,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        thumb.src_path.serialize(serializer)
+    }
+
+    /// Deserializes the origin path and loads the image from it
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<StaticThumbnail, D::Error> {
+        let path = match Option::<PathBuf>::deserialize(deserializer)? {
+            Some(path) => path,
+            None => return Err(D::Error::custom("Image without a path cannot be loaded")),
+        };
+
+        match image::open(&path) {
+            Ok(image) => Ok(StaticThumbnail::new(path, image)),
+            Err(error) => Err(D::Error::custom(format!(
+                "Image could not be loaded from path {}: {}",
+                path.display(),
+                error
+            ))),
+        }
     }
 }
 
@@ -27,7 +81,41 @@ impl StaticThumbnail {
     /// * src_path: PathBuf - The origin path of the image
     /// * image: DynamicImage - The actual image data
     pub fn new(src_path: PathBuf, image: DynamicImage) -> Self {
-        StaticThumbnail { src_path, image }
+        StaticThumbnail {
+            src_path: Some(src_path),
+            image,
+        }
+    }
+
+    /// Constructs a new `StaticThumbnail` from image data only, without an origin path
+    ///
+    /// * image: DynamicImage - The actual image data
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::generic::BoxPosition;
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use thumbnailer::thumbnail::operations::Operation;
+    /// use thumbnailer::thumbnail::operations::CombineOp;
+    /// use image::{DynamicImage, GenericImageView, Rgba, RgbaImage};
+    ///
+    /// let overlay = DynamicImage::ImageRgba8(RgbaImage::from_pixel(10, 10, Rgba([255, 0, 0, 255])));
+    /// let overlay = StaticThumbnail::from_dynamic_image(overlay);
+    /// assert_eq!(overlay.get_src_path(), None);
+    ///
+    /// let mut background = DynamicImage::new_rgba8(50, 50);
+    /// let combine_op = CombineOp::new(overlay, BoxPosition::TopLeft(20, 20));
+    /// assert!(combine_op.apply(&mut background).is_ok());
+    /// assert!(format!("{:?}", combine_op).contains("in memory"));
+    ///
+    /// assert_eq!(background.get_pixel(25, 25)[0], 255);
+    /// assert_eq!(background.get_pixel(5, 5)[0], 0);
+    /// ```
+    pub fn from_dynamic_image(image: DynamicImage) -> Self {
+        StaticThumbnail {
+            src_path: None,
+            image,
+        }
     }
 
     /// Gets the actual image data
@@ -41,7 +129,199 @@ impl StaticThumbnail {
     }
 
     /// Gets the stored origin path of the image
-    pub fn get_src_path(&self) -> PathBuf {
+    ///
+    /// Returns `None` if the image was created in memory, see `StaticThumbnail::from_dynamic_image()`
+    pub fn get_src_path(&self) -> Option<PathBuf> {
         self.src_path.clone()
     }
+
+    /// Computes the average color of the image as RGBA
+    ///
+    /// Every pixel, including its alpha channel, contributes equally to the average.
+    /// Returns `[0, 0, 0, 0]` for an empty image.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use image::{DynamicImage, Rgb, RgbImage};
+    ///
+    /// let red = RgbImage::from_pixel(10, 10, Rgb([255, 0, 0]));
+    /// let red = StaticThumbnail::from_dynamic_image(DynamicImage::ImageRgb8(red));
+    /// assert_eq!(red.average_color(), [255, 0, 0, 255]);
+    ///
+    /// // The left half is red, the right half is blue
+    /// let split = RgbImage::from_fn(10, 10, |x, _| if x < 5 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) });
+    /// let split = StaticThumbnail::from_dynamic_image(DynamicImage::ImageRgb8(split));
+    /// assert_eq!(split.average_color(), [128, 0, 128, 255]);
+    /// ```
+    pub fn average_color(&self) -> [u8; 4] {
+        average(self.as_dyn().to_rgba8().pixels().map(|pixel| pixel.0))
+    }
+
+    /// Computes the dominant color of the image as RGBA
+    ///
+    /// The image is downsampled to at most 64x64 pixels first. The pixels are then sorted into buckets of similar colors,
+    /// and the average color of the fullest bucket is returned. Fully transparent pixels are ignored.
+    /// Returns `[0, 0, 0, 0]` if the image is empty or fully transparent.
+    ///
+    /// This is useful as a placeholder background, while the actual thumbnail is loading.
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::StaticThumbnail;
+    /// use image::{DynamicImage, Rgb, RgbImage};
+    ///
+    /// let red = RgbImage::from_pixel(10, 10, Rgb([255, 0, 0]));
+    /// let red = StaticThumbnail::from_dynamic_image(DynamicImage::ImageRgb8(red));
+    /// assert_eq!(red.dominant_color(), [255, 0, 0, 255]);
+    ///
+    /// // Three quarters of the image are blue
+    /// let mostly_blue = RgbImage::from_fn(100, 100, |x, _| if x < 25 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) });
+    /// let mostly_blue = StaticThumbnail::from_dynamic_image(DynamicImage::ImageRgb8(mostly_blue));
+    /// assert_eq!(mostly_blue.dominant_color(), [0, 0, 255, 255]);
+    /// ```
+    pub fn dominant_color(&self) -> [u8; 4] {
+        // Nearest neighbor sampling does not blend colors at edges, which would end up in buckets of their own
+        let (width, height) = self.dimensions();
+        let sample = if width > DOMINANT_COLOR_SAMPLE_SIZE || height > DOMINANT_COLOR_SAMPLE_SIZE {
+            self.as_dyn()
+                .resize(
+                    DOMINANT_COLOR_SAMPLE_SIZE,
+                    DOMINANT_COLOR_SAMPLE_SIZE,
+                    FilterType::Nearest,
+                )
+                .to_rgba8()
+        } else {
+            self.as_dyn().to_rgba8()
+        };
+
+        let shift = 8 - DOMINANT_COLOR_BUCKET_BITS;
+        let mut buckets: HashMap<[u8; 3], Vec<[u8; 4]>> = HashMap::new();
+        for pixel in sample.pixels().filter(|pixel| pixel[3] > 0) {
+            let key = [pixel[0] >> shift, pixel[1] >> shift, pixel[2] >> shift];
+            buckets.entry(key).or_default().push(pixel.0);
+        }
+
+        match buckets
+            .into_iter()
+            .max_by_key(|(key, pixels)| (pixels.len(), *key))
+        {
+            Some((_, pixels)) => average(pixels.into_iter()),
+            None => [0, 0, 0, 0],
+        }
+    }
+
+    /// Computes a 64-bit perceptual hash of the image
+    ///
+    /// The image is scaled to 32x32 pixels in grayscale, and transformed with a discrete cosine transform.
+    /// Each bit of the hash is set if the matching coefficient of the lowest 8x8 frequencies is above their median.
+    /// Similar images, e.g. a scaled, slightly blurred or recompressed copy, have hashes that differ in only a few bits.
+    /// Compare hashes with `hamming_distance()`, to detect near-duplicates before processing them.
+    /// Returns `0` for an empty image.
+    /// More information: [Perceptual hashing](https://en.wikipedia.org/wiki/Perceptual_hashing)
+    ///
+    /// # Examples
+    /// ```
+    /// use thumbnailer::thumbnail::{hamming_distance, StaticThumbnail};
+    ///
+    /// let image = image::open("resources/tests/test.jpg").unwrap();
+    /// let blurred = image.blur(1.5);
+    ///
+    /// let original = StaticThumbnail::from_dynamic_image(image);
+    /// let blurred = StaticThumbnail::from_dynamic_image(blurred);
+    /// assert!(hamming_distance(original.phash(), blurred.phash()) < 8);
+    ///
+    /// let empty = StaticThumbnail::from_dynamic_image(image::DynamicImage::new_rgb8(0, 0));
+    /// assert_eq!(empty.phash(), 0);
+    /// ```
+    pub fn phash(&self) -> u64 {
+        let (width, height) = self.dimensions();
+        if width == 0 || height == 0 {
+            return 0;
+        }
+
+        let size = PHASH_SAMPLE_SIZE as u32;
+        let gray = self
+            .as_dyn()
+            .resize_exact(size, size, FilterType::Triangle)
+            .to_luma8();
+
+        let mut cosines = [[0f64; PHASH_SAMPLE_SIZE]; PHASH_FREQUENCIES];
+        for (frequency, row) in cosines.iter_mut().enumerate() {
+            let scale = if frequency == 0 { 0.5f64.sqrt() } else { 1.0 };
+            for (position, cosine) in row.iter_mut().enumerate() {
+                let angle = (2 * position + 1) as f64 * frequency as f64 * std::f64::consts::PI
+                    / (2 * PHASH_SAMPLE_SIZE) as f64;
+                *cosine = scale * angle.cos();
+            }
+        }
+
+        // The transform is separable: first along the rows, then along the columns
+        let mut rows = [[0f64; PHASH_FREQUENCIES]; PHASH_SAMPLE_SIZE];
+        for (y, row) in rows.iter_mut().enumerate() {
+            for (u, coefficient) in row.iter_mut().enumerate() {
+                *coefficient = (0..PHASH_SAMPLE_SIZE)
+                    .map(|x| gray.get_pixel(x as u32, y as u32)[0] as f64 * cosines[u][x])
+                    .sum();
+            }
+        }
+        let mut coefficients = Vec::with_capacity(PHASH_FREQUENCIES * PHASH_FREQUENCIES);
+        for cosines_v in cosines.iter() {
+            for u in 0..PHASH_FREQUENCIES {
+                coefficients.push(
+                    rows.iter()
+                        .zip(cosines_v.iter())
+                        .map(|(row, cosine)| row[u] * cosine)
+                        .sum::<f64>(),
+                );
+            }
+        }
+
+        // The DC coefficient only holds the average brightness, and would dominate the median
+        let mut sorted = coefficients[1..].to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let median = sorted[sorted.len() / 2];
+
+        coefficients
+            .iter()
+            .enumerate()
+            .filter(|(_, coefficient)| **coefficient > median)
+            .fold(0u64, |hash, (bit, _)| hash | (1 << bit))
+    }
+}
+
+/// Counts the bits in which two hashes differ
+///
+/// Use it to compare the results of `StaticThumbnail::phash()`. Identical images have a distance of 0,
+/// unrelated images have a distance of about 32. A distance up to about 10 usually indicates a near-duplicate.
+///
+/// # Examples
+/// ```
+/// use thumbnailer::thumbnail::hamming_distance;
+///
+/// assert_eq!(hamming_distance(0b1011, 0b1011), 0);
+/// assert_eq!(hamming_distance(0b1011, 0b0110), 3);
+/// ```
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Computes the rounded average of each channel of the given RGBA pixels
+///
+/// Returns `[0, 0, 0, 0]` if there are no pixels.
+fn average(pixels: impl Iterator<Item = [u8; 4]>) -> [u8; 4] {
+    let mut sums = [0u64; 4];
+    let mut count = 0u64;
+    for pixel in pixels {
+        for (sum, value) in sums.iter_mut().zip(pixel.iter()) {
+            *sum += *value as u64;
+        }
+        count += 1;
+    }
+
+    let mut color = [0u8; 4];
+    for (channel, sum) in color.iter_mut().zip(sums.iter()) {
+        *channel = (sum + count / 2).checked_div(count).unwrap_or(0) as u8;
+    }
+    color
 }
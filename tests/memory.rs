@@ -0,0 +1,132 @@
+//! Tests of the memory used by operations and decoding
+//!
+//! They replace the global allocator to measure the peak memory, so they are kept in their own test binary.
+
+use image::{
+    ColorType, DynamicImage, GenericImageView, ImageFormat, Rgb, RgbImage, Rgba, RgbaImage,
+};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use thumbnailer::generic::GenericThumbnailOperations;
+use thumbnailer::thumbnail::operations::{
+    AppendOp, LevelsOp, OpacityOp, Operation, PadToRatioOp, SelectiveDesaturateOp, ShadowOp,
+    TemperatureOp,
+};
+use thumbnailer::{Orientation, Resize, StaticThumbnail, Thumbnail};
+
+thread_local! {
+    /// The memory currently allocated by the current thread
+    static ALLOCATED: Cell<usize> = const { Cell::new(0) };
+    /// The highest amount of memory allocated by the current thread, since it was last reset
+    static PEAK_ALLOCATED: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Tracks the memory allocated by each thread
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let _ = ALLOCATED.try_with(|allocated| {
+                allocated.set(allocated.get() + layout.size());
+                let _ = PEAK_ALLOCATED.try_with(|peak| peak.set(peak.get().max(allocated.get())));
+            });
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        // Memory may be freed by another thread than the one that allocated it
+        let _ = ALLOCATED
+            .try_with(|allocated| allocated.set(allocated.get().saturating_sub(layout.size())));
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs the function and returns its result, along with the peak memory it allocated on the current thread
+fn peak_allocated<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let start = ALLOCATED.with(Cell::get);
+    PEAK_ALLOCATED.with(|peak| peak.set(start));
+    let result = f();
+    (result, PEAK_ALLOCATED.with(Cell::get) - start)
+}
+
+#[test]
+fn rgba_images_are_not_copied_by_operations() {
+    // An RGBA image, as the working color RGBA turns every image into one before the operations
+    let rgba = || {
+        let image = RgbImage::from_pixel(200, 100, Rgb([120, 80, 40]));
+        DynamicImage::ImageRgba8(DynamicImage::ImageRgb8(image).to_rgba8())
+    };
+    let image_size = 200 * 100 * 4;
+
+    // These change the pixels in place
+    let in_place: Vec<Box<dyn Operation>> = vec![
+        Box::new(LevelsOp::new(10, 240, 1.5)),
+        Box::new(OpacityOp::new(0.5)),
+        Box::new(SelectiveDesaturateOp::new(0.0, 30.0)),
+        Box::new(TemperatureOp::new(1000)),
+    ];
+    let mut image = rgba();
+    for op in &in_place {
+        let (_, peak) = peak_allocated(|| op.apply(&mut image).unwrap());
+        assert!(peak < image_size / 4, "{:?} allocated {} bytes", op, peak);
+        assert_eq!(image.color(), ColorType::Rgba8);
+    }
+
+    // These need a larger canvas, but do not copy the image to place it on the canvas
+    let overlay = RgbaImage::from_pixel(40, 4, Rgba([0, 0, 255, 255]));
+    let canvas_ops: Vec<Box<dyn Operation>> = vec![
+        Box::new(PadToRatioOp::new(1.0, 1.0, [0, 0, 0, 255])),
+        Box::new(ShadowOp::new(4, 4, 0.0, [0, 0, 0, 255])),
+        Box::new(AppendOp::new(
+            StaticThumbnail::from_dynamic_image(DynamicImage::ImageRgba8(overlay)),
+            Orientation::Vertical,
+        )),
+    ];
+    for op in &canvas_ops {
+        let mut image = rgba();
+        let (_, peak) = peak_allocated(|| op.apply(&mut image).unwrap());
+        let canvas_size = (image.width() * image.height() * 4) as usize;
+        assert!(
+            peak < canvas_size + image_size / 2,
+            "{:?} allocated {} bytes for a canvas of {} bytes",
+            op,
+            peak,
+            canvas_size
+        );
+        assert_eq!(image.color(), ColorType::Rgba8);
+    }
+}
+
+#[test]
+fn striped_tiff_is_reduced_strip_by_strip() {
+    let path = std::env::temp_dir().join("thumbnailer_test_memory/large.tiff");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let large = RgbImage::from_fn(2000, 1500, |x, y| {
+        Rgb([(x / 8) as u8, (y / 6) as u8, ((x + y) % 256) as u8])
+    });
+    DynamicImage::ImageRgb8(large)
+        .save_with_format(&path, ImageFormat::Tiff)
+        .unwrap();
+
+    let full = Thumbnail::load(path.clone()).unwrap();
+    let (_, full_peak) = peak_allocated(|| full.to_dynamic_image().unwrap());
+
+    let mut reduced = Thumbnail::load(path).unwrap();
+    reduced.resize(Resize::BoundingBox(100, 75));
+    let (image, reduced_peak) = peak_allocated(|| reduced.apply_to_dynamic_image().ok().unwrap());
+
+    // Reduced by a factor of 20, while holding only a single strip of about 1 MB in memory
+    assert_eq!(image.dimensions(), (100, 75));
+    assert!(
+        reduced_peak * 4 < full_peak,
+        "{} bytes for the reduced decode, {} bytes for the full decode",
+        reduced_peak,
+        full_peak
+    );
+}